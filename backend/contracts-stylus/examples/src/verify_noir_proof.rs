@@ -18,11 +18,24 @@ use alloy_primitives::{Address, U256};
 
 // Mock ZK verification for demonstration
 // In production, replace with actual ZK proof verification
-fn verify_noir_proof_raw(proof_bytes: &[u8], _public_inputs: &[u8]) -> bool {
+fn verify_noir_proof_raw_with_min_inputs(
+    proof_bytes: &[u8],
+    public_inputs: &[u8],
+    min_public_inputs: usize,
+) -> bool {
     // Simplified mock verification - in production use actual ZK verification
-    !proof_bytes.is_empty() && proof_bytes.len() >= 32
+    !proof_bytes.is_empty()
+        && proof_bytes.len() >= 32
+        && public_inputs.len() % 32 == 0
+        && public_inputs.len() / 32 >= min_public_inputs
 }
 
+/// Number of public input field elements the business eligibility circuit exposes
+const BUSINESS_MIN_PUBLIC_INPUTS: usize = 5;
+
+/// Maximum proofs accepted by a single batch_is_proof_used query
+const MAX_PROOF_STATUS_BATCH: usize = 50;
+
 /// Mock field element for demonstration
 #[derive(Debug, Clone)]
 pub struct FieldElement {
@@ -114,7 +127,7 @@ impl DVoteDAO {
         }
 
         // Verify the ZK proof
-        let is_valid = verify_noir_proof_raw(&proof_bytes, &public_inputs);
+        let is_valid = verify_noir_proof_raw_with_min_inputs(&proof_bytes, &public_inputs, BUSINESS_MIN_PUBLIC_INPUTS);
         if !is_valid {
             return Err(stylus_sdk::stylus_proc::SolidityError::Revert(alloc::vec![]));
         }
@@ -187,7 +200,7 @@ impl DVoteDAO {
         
         for (proof_bytes, public_inputs, member_address) in proofs {
             // Verify proof
-            let is_valid = verify_noir_proof_raw(&proof_bytes, &public_inputs);
+            let is_valid = verify_noir_proof_raw_with_min_inputs(&proof_bytes, &public_inputs, BUSINESS_MIN_PUBLIC_INPUTS);
             results.push(is_valid);
             
             if is_valid {
@@ -209,6 +222,26 @@ impl DVoteDAO {
         Ok(results)
     }
 
+    /// Check which prepared proofs are already registered, in the order given
+    /// 
+    /// Each entry is (proof_bytes, public_inputs), hashed exactly as on join.
+    pub fn batch_is_proof_used(
+        &self,
+        proofs: Vec<(Vec<u8>, Vec<u8>)>,
+    ) -> Result<Vec<bool>, stylus_sdk::stylus_proc::SolidityError> {
+        if proofs.len() > MAX_PROOF_STATUS_BATCH {
+            return Err(stylus_sdk::stylus_proc::SolidityError::Revert(alloc::vec![]));
+        }
+        
+        Ok(proofs
+            .iter()
+            .map(|(proof_bytes, public_inputs)| {
+                let proof_hash = self.hash_proof(proof_bytes, public_inputs);
+                self.proof_registry.get(proof_hash).unwrap_or(false)
+            })
+            .collect())
+    }
+
     /// Check if an address is a verified member
     pub fn is_verified_member(&self, member: Address) -> bool {
         self.verified_members.get(member).unwrap_or(false)
@@ -243,7 +276,7 @@ impl DVoteDAO {
 impl DVoteDAO {
     /// Parse business verification inputs from public input bytes
     fn parse_business_inputs(&self, public_inputs: &[u8]) -> Result<BusinessInputs, &'static str> {
-        if public_inputs.len() < 32 * 5 {  // At least 5 field elements expected
+        if public_inputs.len() < 32 * BUSINESS_MIN_PUBLIC_INPUTS {
             return Err("Invalid public inputs length");
         }
 
@@ -356,5 +389,35 @@ pub struct BusinessInputs {
 // Frontend helpers would go here in a full implementation
 // For this example, we focus on the core Stylus contract functionality
 
-// Tests would be added here in a full implementation
-// For this example, we focus on showing the contract structure
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_is_proof_used() {
+        let mut dao = DVoteDAO::default();
+        let inputs_len = 32 * BUSINESS_MIN_PUBLIC_INPUTS;
+        let proof = |seed: u8| (alloc::vec![seed; 64], alloc::vec![seed; inputs_len]);
+        
+        // Proofs 1 and 3 were consumed by earlier joins
+        for seed in [1u8, 3] {
+            let (proof_bytes, public_inputs) = proof(seed);
+            let proof_hash = dao.hash_proof(&proof_bytes, &public_inputs);
+            dao.proof_registry.insert(proof_hash, true);
+        }
+        
+        let statuses = dao
+            .batch_is_proof_used(alloc::vec![proof(1), proof(2), proof(3), proof(4)])
+            .ok()
+            .unwrap();
+        assert_eq!(statuses, alloc::vec![true, false, true, false]);
+        
+        // Same proof bytes with different public inputs is a different proof
+        let (proof_bytes, _) = proof(1);
+        let rebound = (proof_bytes, alloc::vec![9u8; inputs_len]);
+        assert_eq!(dao.batch_is_proof_used(alloc::vec![rebound]).ok().unwrap(), alloc::vec![false]);
+        
+        let oversized = (0..=MAX_PROOF_STATUS_BATCH as u8).map(proof).collect();
+        assert!(dao.batch_is_proof_used(oversized).is_err());
+    }
+}
\ No newline at end of file
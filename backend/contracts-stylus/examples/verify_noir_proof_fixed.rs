@@ -14,7 +14,7 @@ use stylus_sdk::{
     storage::{StorageAddress, StorageU256, StorageBool, StorageMap},
     msg,
 };
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{keccak256, Address, U256};
 
 // ================================
 // MOCK IMPLEMENTATIONS FOR EXAMPLE
@@ -326,21 +326,13 @@ impl DVoteDAO {
         u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
     }
 
-    /// Hash proof data to create unique identifier
-    /// In production, use proper cryptographic hashing like Keccak256
+    /// Canonical proof identifier, keccak256(proof_bytes ++ public_inputs)
+    /// Matches zk_noir_verifier::proof_id so off-chain attestations line up with this registry
     fn hash_proof(&self, proof_bytes: &[u8], public_inputs: &[u8]) -> U256 {
-        // Simple mock hash - in production use alloy_primitives::keccak256 or similar
-        let mut hash_value = 0u64;
-        
-        // XOR all bytes for a simple hash (NOT cryptographically secure)
-        for byte in proof_bytes.iter().chain(public_inputs.iter()) {
-            hash_value ^= *byte as u64;
-        }
-        
-        // Add length for better distribution
-        hash_value ^= (proof_bytes.len() + public_inputs.len()) as u64;
-        
-        U256::from(hash_value)
+        let mut preimage = Vec::with_capacity(proof_bytes.len() + public_inputs.len());
+        preimage.extend_from_slice(proof_bytes);
+        preimage.extend_from_slice(public_inputs);
+        U256::from_be_bytes(keccak256(&preimage).0)
     }
 }
 
@@ -0,0 +1,188 @@
+//! CommitmentTree - Append-only Merkle tree of membership commitments for Arbitrum Stylus
+//! Semaphore-style anonymous voting proves inclusion of a commitment against `current_root()`
+//! without revealing which leaf it is. Nodes are hashed with the verifier's PoseidonHasher,
+//! which is still a mock: roots match off-chain Semaphore trees only once it is replaced.
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolEvent},
+    prelude::*,
+    msg, evm,
+};
+use alloc::vec::Vec;
+
+use crate::zk_noir_verifier::FieldElement;
+use crate::zk_verifier::PoseidonHasher;
+
+/// Levels in the tree; capacity is 2^TREE_DEPTH commitments
+pub const TREE_DEPTH: usize = 20;
+
+/// Value of an empty leaf
+pub const ZERO_LEAF: [u8; 32] = [0u8; 32];
+
+sol! {
+    event CommitmentInserted(uint256 indexed index, uint256 commitment, bytes32 newRoot);
+}
+
+#[solidity_storage]
+#[entrypoint]
+pub struct CommitmentTree {
+    owner: StorageAddress,
+    current_root: StorageFixedBytes<32>,
+    leaf_count: StorageU256,
+    filled_subtrees: StorageMap<U256, StorageFixedBytes<32>>, // level -> last left node at that level
+}
+
+#[public]
+impl CommitmentTree {
+    /// Initialize with the owner allowed to insert commitments (typically the DAO)
+    pub fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+        if self.owner.get() != Address::ZERO {
+            return Err(b"Already initialized".to_vec());
+        }
+        if owner == Address::ZERO {
+            return Err(b"Invalid address".to_vec());
+        }
+
+        self.owner.set(owner);
+        self.current_root.set(FixedBytes::from(zero_subtree_root(TREE_DEPTH)?));
+        Ok(())
+    }
+
+    /// Append a commitment (a BN254 field element) and update the root (owner only)
+    pub fn insert_commitment(&mut self, commitment: U256) -> Result<U256, Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"Only owner can insert commitments".to_vec());
+        }
+
+        let leaf = FieldElement::from_u256(commitment);
+        if !leaf.is_valid_bn254() {
+            return Err(b"Commitment is not a field element".to_vec());
+        }
+        if leaf.bytes == ZERO_LEAF {
+            return Err(b"Commitment cannot be the empty leaf".to_vec());
+        }
+
+        let (index, new_root) = self.append_leaf(leaf)?;
+
+        evm::log(CommitmentInserted {
+            index,
+            commitment,
+            newRoot: FixedBytes::from(new_root),
+        });
+        Ok(index)
+    }
+
+    /// Root covering every commitment inserted so far
+    pub fn current_root(&self) -> FixedBytes<32> {
+        self.current_root.get()
+    }
+
+    /// Number of commitments inserted so far
+    pub fn leaf_count(&self) -> U256 {
+        self.leaf_count.get()
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+}
+
+impl CommitmentTree {
+    /// Insert a leaf at the next index, storing the new root and leaf count
+    fn append_leaf(&mut self, leaf: FieldElement) -> Result<(U256, [u8; 32]), Vec<u8>> {
+        let index = self.leaf_count.get();
+        if index >= U256::from(1u64) << TREE_DEPTH {
+            return Err(b"Commitment tree is full".to_vec());
+        }
+
+        let mut position = index;
+        let mut current = leaf.bytes;
+        let mut zero = ZERO_LEAF;
+
+        for level in 0..TREE_DEPTH {
+            let level_key = U256::from(level);
+            let (left, right) = if position % U256::from(2) == U256::ZERO {
+                // Left child: remember it for the sibling that arrives later
+                self.filled_subtrees.setter(level_key).set(FixedBytes::from(current));
+                (current, zero)
+            } else {
+                (self.filled_subtrees.get(level_key).0, current)
+            };
+
+            current = hash_pair(left, right)?;
+            zero = hash_pair(zero, zero)?;
+            position = position / U256::from(2);
+        }
+
+        self.current_root.set(FixedBytes::from(current));
+        self.leaf_count.set(index + U256::from(1));
+        Ok((index, current))
+    }
+}
+
+// =============================================================================
+// INTERNAL HELPERS
+// =============================================================================
+
+/// Hash two child nodes into their parent
+fn hash_pair(left: [u8; 32], right: [u8; 32]) -> Result<[u8; 32], Vec<u8>> {
+    PoseidonHasher::new()
+        .hash_multiple(&[left, right])
+        .map_err(|_| b"Poseidon hash failed".to_vec())
+}
+
+/// Root of a subtree of the given height containing only empty leaves
+fn zero_subtree_root(height: usize) -> Result<[u8; 32], Vec<u8>> {
+    let mut zero = ZERO_LEAF;
+    for _ in 0..height {
+        zero = hash_pair(zero, zero)?;
+    }
+    Ok(zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recompute the root from every leaf, padding each level with empty subtrees
+    fn full_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+        let mut level: Vec<[u8; 32]> = leaves.to_vec();
+        let mut zero = ZERO_LEAF;
+        for _ in 0..TREE_DEPTH {
+            if level.is_empty() {
+                level.push(zero);
+            }
+            if level.len() % 2 == 1 {
+                level.push(zero);
+            }
+            level = level.chunks(2).map(|pair| hash_pair(pair[0], pair[1]).unwrap()).collect();
+            zero = hash_pair(zero, zero).unwrap();
+        }
+        level[0]
+    }
+
+    #[test]
+    fn test_root_progression() {
+        let mut tree = CommitmentTree::default();
+        assert_eq!(tree.leaf_count(), U256::ZERO);
+        assert_eq!(full_root(&[]), zero_subtree_root(TREE_DEPTH).unwrap());
+
+        let mut leaves = Vec::new();
+        let mut previous_root = full_root(&[]);
+        for i in 1..=5u64 {
+            let leaf = FieldElement::from_u256(U256::from(i * 1_000 + 7));
+            let (index, root) = tree.append_leaf(leaf).unwrap();
+            leaves.push(leaf.bytes);
+
+            assert_eq!(index, U256::from(i - 1));
+            assert_eq!(root, full_root(&leaves));
+            assert_ne!(root, previous_root);
+            assert_eq!(tree.current_root(), FixedBytes::from(root));
+            assert_eq!(tree.leaf_count(), U256::from(i));
+            previous_root = root;
+        }
+    }
+}
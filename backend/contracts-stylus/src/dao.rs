@@ -10,17 +10,65 @@ use alloc::{string::String, vec::Vec};
 use stylus_sdk::{
     alloy_primitives::{Address, U256, FixedBytes, Bytes},
     alloy_sol_types::{sol, SolEvent, SolCall},
-    block, msg, evm,
+    block, contract, crypto, msg, evm,
     prelude::*,
-    call::{Call, StaticCall},
+    call::{transfer_eth, Call, RawCall, StaticCall},
 };
 
+use crate::math::u256_sqrt;
+
 // =============================================================================
 // EXTERNAL CONTRACT INTERFACES
 // =============================================================================
 
 // External contract call functions - we'll use direct calls instead of interfaces
 
+sol! {
+    // Treasury calls encoded as proposal execution data
+    function queueWithdrawal(address recipient, uint256 amount, string memo) external returns (uint256);
+
+    // Optional proposal settings for create_proposal_with_options; all zero is a plain proposal
+    struct ProposalOptions {
+        uint8 requiredVerificationType; // 0: any verified member, else KYC/KYB bitmask
+        uint256 quorumOverride;         // 0: quorum_percent default, else required votes (owner only)
+        uint8 weightStrategy;           // 0: token-weighted, 1: one person one vote, 2: quadratic
+        uint256 executionDelayOverride; // 0: global execution_delay, else this proposal's timelock
+    }
+}
+
+impl ProposalOptions {
+    /// Options of a plain proposal: any verified member, default quorum, token weighting
+    /// and the global execution delay
+    pub fn standard() -> Self {
+        ProposalOptions {
+            requiredVerificationType: 0,
+            quorumOverride: U256::ZERO,
+            weightStrategy: 0,
+            executionDelayOverride: U256::ZERO,
+        }
+    }
+}
+
+sol_interface! {
+    interface IGovernanceToken {
+        function delegates(address account) external view returns (address);
+        function activateSelfDelegation(address account) external;
+        function getVotes(address account) external view returns (uint256);
+        function getPastVotes(address account, uint256 timepoint) external view returns (uint256);
+        function getPastTotalSupply(uint256 timepoint) external view returns (uint256);
+    }
+
+    interface IShadowIDRegistry {
+        function isVerified(address user) external view returns (bool);
+        function getUser(address user) external view returns (bool, bytes32, bytes32);
+        function getCommitment(address user) external view returns (bytes32);
+    }
+
+    interface IEmergencyStop {
+        function isStopped() external view returns (bool);
+    }
+}
+
 // =============================================================================
 // DAO EVENTS
 // =============================================================================
@@ -43,15 +91,24 @@ sol! {
         bytes32 proofHash
     );
     event ProposalFinalized(uint256 indexed id, uint8 state);
+    event VoteDropped(uint256 indexed id, address indexed voter);
     event ProposalExecuted(uint256 indexed id, address indexed executor);
     event ProposalCancelled(uint256 indexed id, address indexed cancelledBy);
     
     // DAO Configuration Events
     event AllowedTargetUpdated(address indexed target, bool allowed);
+    event ExecutorUpdated(address indexed executor, bool allowed);
     event VotingPeriodUpdated(uint256 oldPeriod, uint256 newPeriod);
+    event VotingDelayUpdated(uint256 oldDelay, uint256 newDelay);
     event QuorumPercentUpdated(uint256 oldPct, uint256 newPct);
     event ExecutionDelayUpdated(uint256 oldDelay, uint256 newDelay);
     event ProposalThresholdUpdated(uint256 oldThreshold, uint256 newThreshold);
+    event VoteWeightBoundsUpdated(uint256 minWeight, uint256 maxWeight);
+    event AutoFinalizeUpdated(bool enabled);
+    event MaxProposalsPerBlockUpdated(uint256 oldMax, uint256 newMax);
+    event ParametersSnapshot(uint256 votingPeriod, uint256 quorumPercent, uint256 executionDelay, uint256 proposalThreshold, uint256 votingDelay);
+    event VerificationValidityUpdated(uint256 oldValidity, uint256 newValidity);
+    event EthRescued(address indexed to, uint256 amount);
     event TreasuryLinked(address indexed newTreasury);
     event ShadowIDRegistryUpdated(address indexed oldRegistry, address indexed newRegistry);
     
@@ -60,12 +117,20 @@ sol! {
     event ProofSubmitted(address indexed user);
     event ZKProofValidated(address indexed user, bytes32 commitment, bytes32 proofHash);
     event UnverifiedAccessAttempt(address indexed user, string action);
+    event ProofInvalidated(address indexed user, bytes32 commitment, address indexed invalidatedBy);
     
     // Member Management Events
     event MemberAdded(address indexed member);
     event KycSubmitted(address indexed member, bytes32 kycHash, bytes32 zkProofHash);
     event KycVerified(address indexed member, address indexed verifier);
-    event KycVerifierAdded(address indexed verifier);
+    event MemberResynced(address indexed member, address indexed registry, bool verified);
+    event MembersImported(uint256 count);
+    event MemberImportFinalized();
+    event RegistryFallbackUpdated(bool enabled);
+    event EmergencyStopContractUpdated(address indexed oldContract, address indexed newContract);
+    event KycVerifierAdded(address indexed verifier, uint8 allowedTypes);
+    event KycVerifierRemoved(address indexed verifier);
+    event VerifierAdminUpdated(address indexed oldAdmin, address indexed newAdmin);
     
     // Errors
     error NotVerified(address user);
@@ -80,6 +145,50 @@ sol! {
 // DATA STRUCTURES
 // =============================================================================
 
+/// Verification type bits (matches MemberData::verification_type: 3 = KYC | KYB)
+pub const VERIFICATION_TYPE_KYC: u8 = 1;
+pub const VERIFICATION_TYPE_KYB: u8 = 2;
+
+/// Reason codes returned by verification_status
+pub const VERIFICATION_REASON_VERIFIED: u8 = 0;
+pub const VERIFICATION_REASON_NOT_MEMBER: u8 = 1;
+pub const VERIFICATION_REASON_NO_COMMITMENT: u8 = 2;
+pub const VERIFICATION_REASON_EXPIRED: u8 = 3;
+pub const VERIFICATION_REASON_REGISTRY_ERROR: u8 = 4;
+pub const VERIFICATION_REASON_NOT_VERIFIED: u8 = 5;
+
+/// Smallest quorum a proposal's quorum override may require
+pub const QUORUM_VOTES: u64 = 100;
+
+/// Maximum addresses accepted by a single are_verified query
+pub const MAX_VERIFICATION_BATCH: usize = 100;
+
+/// Maximum members accepted by a single import_members call
+pub const MAX_IMPORT_BATCH: usize = 100;
+
+/// Longest title a treasury payout proposal can carry; it becomes the withdrawal memo,
+/// which the Treasury caps at this length
+pub const MAX_PAYOUT_MEMO_LENGTH: usize = 256;
+
+/// Longest timelock a proposal's execution delay override may ask for
+pub const MAX_EXECUTION_DELAY: u64 = 30 * 24 * 60 * 60;
+
+/// Shortest voting period set_voting_period accepts
+pub const MIN_VOTING_PERIOD: u64 = 60 * 60;
+
+/// Phases reported by time_remaining
+pub const PHASE_NONE: u8 = 0;
+pub const PHASE_VOTING: u8 = 1;
+pub const PHASE_TIMELOCK: u8 = 2;
+pub const PHASE_PENDING: u8 = 3;
+
+/// ERC-165 id of `supportsInterface(bytes4)`
+pub const ERC165_INTERFACE_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+
+/// XOR of the selectors of createProposal, vote, finalizeProposal, executeProposal and
+/// cancelProposal (see test_supports_interface for the signatures)
+pub const DAO_INTERFACE_ID: [u8; 4] = [0xfd, 0x61, 0xb2, 0x2e];
+
 /// Proposal states as enum
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum ProposalState {
@@ -108,6 +217,43 @@ impl From<ProposalState> for u8 {
     }
 }
 
+/// How a proposal turns a voter's token votes into voting weight
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum WeightStrategy {
+    TokenWeighted,
+    OnePerson,
+    Quadratic,
+}
+
+impl Default for WeightStrategy {
+    fn default() -> Self {
+        WeightStrategy::TokenWeighted
+    }
+}
+
+impl From<WeightStrategy> for u8 {
+    fn from(strategy: WeightStrategy) -> u8 {
+        match strategy {
+            WeightStrategy::TokenWeighted => 0,
+            WeightStrategy::OnePerson => 1,
+            WeightStrategy::Quadratic => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for WeightStrategy {
+    type Error = Vec<u8>;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(WeightStrategy::TokenWeighted),
+            1 => Ok(WeightStrategy::OnePerson),
+            2 => Ok(WeightStrategy::Quadratic),
+            _ => Err(b"Invalid weight strategy".to_vec()),
+        }
+    }
+}
+
 /// Enhanced proposal data with ZK proof integration
 #[derive(Default, Debug, Clone)]
 pub struct ProposalCore {
@@ -125,6 +271,13 @@ pub struct ProposalCore {
     // ZK proof integration
     pub kyc_commitment: [u8; 32],        // KYC commitment from Noir ZK proof
     pub proof_hash: [u8; 32],            // Hash of the ZK proof
+    pub required_verification_type: u8,  // 0: any verified member, else KYC/KYB bitmask
+    pub quorum_override: U256,           // 0: quorum_percent default, else required votes if higher
+    pub description_hash: [u8; 32],      // keccak256 of the description (text may live off-chain)
+    pub weight_strategy: WeightStrategy, // How token votes become voting weight
+    pub execution_delay_override: U256,  // 0: global execution_delay, else this proposal's timelock
+    pub execution_hash: [u8; 32],        // keccak256(target ++ value ++ data) voters approved
+    pub quorum_votes: U256,              // Votes the proposal needed, fixed when it is finalized
 }
 
 /// Execution details for proposals
@@ -156,6 +309,7 @@ pub struct VoteRecord {
     pub weight: U256,
     pub proof_hash: [u8; 32],           // ZK proof for this vote
     pub timestamp: U256,
+    pub raw_weight: U256,                // Snapshot weight before min/max bounds were applied
 }
 
 /// Reentrancy guard helper
@@ -221,13 +375,37 @@ pub struct DAO {
     // ZK verification system - ENHANCED
     members: StorageMap<Address, MemberData>,
     verification_required: StorageBool,
-    kyc_verifiers: StorageMap<Address, bool>, // KYC verifier addresses
+    kyc_verifiers: StorageMap<Address, u8>, // verifier -> bitmask of allowed verification types
     
     // ZK proof validation
     validated_proofs: StorageMap<Address, FixedBytes<32>>, // user -> latest validated commitment
     
     // Reentrancy protection
     reentrancy_guard: ReentrancyGuard,
+    
+    // Everything below is appended so existing slots keep their positions
+    initialized: StorageBool, // Set once by constructor; blocks re-initialization
+    emergency_stop: StorageAddress, // Shared EmergencyStop coordinator (zero = none)
+    proposal_voters: StorageMap<U256, StorageVec<Address>>, // proposal_id -> voters in vote order
+    
+    // Voting parameters
+    voting_delay: StorageU256,       // Review period between creation and voting start
+    min_vote_weight: StorageU256,    // Floor for a single voter's weight (0 = unset)
+    max_vote_weight: StorageU256,    // Cap for a single voter's weight (0 = unset)
+    auto_finalize: StorageBool,      // vote() finalizes proposals whose voting period has ended
+    max_proposals_per_block: StorageU256,        // Anti-sybil cap on proposals per block (0 = off)
+    proposals_per_block: StorageMap<U256, U256>, // block number -> proposals created in it
+    
+    // Execution
+    executors: StorageMap<Address, bool>, // Keepers/relayers that may execute without membership
+    accepting_eth: StorageBool, // Set only while a proposal executes; gates the payable fallback
+    
+    // Membership
+    member_count: StorageU256,          // Addresses that have ever had is_member set
+    verification_validity: StorageU256, // Seconds a verification stays valid (0 = no expiry)
+    registry_fallback: StorageBool,     // On a local cache miss, ask the registry live
+    import_finalized: StorageBool,      // Permanently disables import_members once set
+    verifier_admin: StorageAddress,     // Manages kyc_verifiers independently of the owner
 }
 
 // =============================================================================
@@ -248,6 +426,11 @@ impl DAO {
         execution_delay: U256,
         proposal_threshold: U256,
     ) -> Result<(), Vec<u8>> {
+        // A deployment from before the flag existed already has an owner
+        if self.initialized.get() || self.owner.get() != Address::ZERO {
+            return Err(b"Already initialized".to_vec());
+        }
+        
         // Validate inputs
         if governance_token == Address::ZERO || treasury == Address::ZERO || shadow_id_registry == Address::ZERO {
             evm::log(InvalidAddress { addr: Address::ZERO });
@@ -259,6 +442,7 @@ impl DAO {
         
         // Set initial state
         self.owner.set(msg::sender());
+        self.verifier_admin.set(msg::sender());
         self.governance_token.set(governance_token);
         self.treasury.set(treasury);
         self.shadow_id_registry.set(shadow_id_registry);
@@ -287,7 +471,11 @@ impl DAO {
         });
         
         evm::log(VerificationRequired { required: true });
+        evm::log(VerifierAdminUpdated { oldAdmin: Address::ZERO, newAdmin: msg::sender() });
         
+        self.emit_parameters_snapshot();
+        
+        self.initialized.set(true);
         Ok(())
     }
 
@@ -303,80 +491,198 @@ impl DAO {
         kyc_commitment: [u8; 32],       // KYC commitment from Noir ZK proof
         proof_hash: [u8; 32],          // ZK proof hash
     ) -> Result<U256, Vec<u8>> {
-        let _guard = self.reentrancy_guard.guard()?;
-        let proposer = msg::sender();
-        
-        // STEP 4: DAO checks: if !shadowid.is_verified(user) { revert("KYC required"); }
-        if !self.is_user_verified_in_shadowid(proposer)? {
-            // Emit required event: UserVerificationRequired(address)
-            evm::log(UserVerificationRequired { user: proposer });
-            return Err(b"KYC required".to_vec());
-        }
-        
-        // Validate ZK proof commitment
-        if !self.validate_zk_proof(proposer, kyc_commitment, proof_hash)? {
-            evm::log(InvalidProof {
-                user: proposer,
-                commitment: FixedBytes::from(kyc_commitment),
-            });
-            return Err(b"Invalid ZK proof or commitment".to_vec());
-        }
-        
-        // Check proposal threshold (governance token balance)
-        // This would integrate with governance token contract in full implementation
-        
-        // Check target is allowed
-        if !self.allowed_targets.get(target) {
-            return Err(b"Target contract not allowed".to_vec());
-        }
-        
-        let proposal_id = self.proposal_count.get();
-        let current_time = U256::from(block::timestamp());
-        
-        // Create proposal core data with ZK proof integration
-        let core = ProposalCore {
-            id: proposal_id,
-            proposer,
-            title: title.clone(),
-            description: description.clone(),
-            start_time: current_time,
-            end_time: current_time + self.voting_period.get(),
-            for_votes: U256::ZERO,
-            against_votes: U256::ZERO,
-            abstain_votes: U256::ZERO,
-            state: ProposalState::Active,
-            cancelled: false,
+        self.create_proposal_with_options(
+            title,
+            description,
+            target,
+            value,
+            data,
             kyc_commitment,
             proof_hash,
-        };
-        
-        // Create execution data
-        let execution = ExecutionData {
+            ProposalOptions::standard(),
+        )
+    }
+
+    /// Create new proposal with any combination of the optional settings: required
+    /// verification type, quorum override, weight strategy and execution delay override
+    pub fn create_proposal_with_options(
+        &mut self,
+        title: String,
+        description: String,
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        options: ProposalOptions,
+    ) -> Result<U256, Vec<u8>> {
+        let description_hash = hash_description(&description);
+        self.create_proposal_internal(
+            msg::sender(),
+            title,
+            description,
+            description_hash,
             target,
             value,
             data,
-            executed: false,
-            timelock_end: U256::ZERO,
+            kyc_commitment,
+            proof_hash,
+            options,
+        )
+    }
+
+    /// Create new proposal restricted to members holding a verification type
+    /// (1: KYC, 2: KYB, 3: both; 0 allows any verified member)
+    pub fn create_proposal_with_verification_type(
+        &mut self,
+        title: String,
+        description: String,
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        required_verification_type: u8,
+    ) -> Result<U256, Vec<u8>> {
+        let options = ProposalOptions {
+            requiredVerificationType: required_verification_type,
+            ..ProposalOptions::standard()
+        };
+        self.create_proposal_with_options(
+            title, description, target, value, data, kyc_commitment, proof_hash, options,
+        )
+    }
+
+    /// Create new proposal that needs more participation than the default quorum
+    /// (owner only; the override must be at least QUORUM_VOTES and never lowers the
+    /// quorum_percent default)
+    pub fn create_proposal_with_quorum_override(
+        &mut self,
+        title: String,
+        description: String,
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        quorum_override: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let options = ProposalOptions {
+            quorumOverride: quorum_override,
+            ..ProposalOptions::standard()
+        };
+        self.create_proposal_with_options(
+            title, description, target, value, data, kyc_commitment, proof_hash, options,
+        )
+    }
+
+    /// Create new proposal with its own execution timelock, for proposals that should wait
+    /// longer than the global execution_delay (0 uses the global delay; shorter, or longer
+    /// than MAX_EXECUTION_DELAY, is rejected)
+    pub fn create_proposal_with_execution_delay(
+        &mut self,
+        title: String,
+        description: String,
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        custom_execution_delay: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let options = ProposalOptions {
+            executionDelayOverride: custom_execution_delay,
+            ..ProposalOptions::standard()
+        };
+        self.create_proposal_with_options(
+            title, description, target, value, data, kyc_commitment, proof_hash, options,
+        )
+    }
+
+    /// Create new proposal weighting votes by `weight_strategy` (0: token-weighted,
+    /// 1: one person one vote, 2: quadratic); non-default strategies are owner/governance only
+    pub fn create_proposal_with_weight_strategy(
+        &mut self,
+        title: String,
+        description: String,
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        weight_strategy: u8,
+    ) -> Result<U256, Vec<u8>> {
+        let options = ProposalOptions {
+            weightStrategy: weight_strategy,
+            ..ProposalOptions::standard()
         };
+        self.create_proposal_with_options(
+            title, description, target, value, data, kyc_commitment, proof_hash, options,
+        )
+    }
+
+    /// Create new proposal storing only the keccak256 of its description, for long texts
+    /// hosted off-chain (e.g. IPFS); execute_proposal_with_description checks the full text
+    pub fn create_proposal_hashed(
+        &mut self,
+        title: String,
+        description_hash: [u8; 32],
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+    ) -> Result<U256, Vec<u8>> {
+        if description_hash.iter().all(|&b| b == 0) {
+            return Err(b"Invalid description hash".to_vec());
+        }
         
-        // Store proposal data
-        self.proposal_core.setter(proposal_id).set(core);
-        self.execution_data.setter(proposal_id).set(execution);
+        self.create_proposal_internal(
+            msg::sender(),
+            title,
+            String::new(),
+            description_hash,
+            target,
+            value,
+            data,
+            kyc_commitment,
+            proof_hash,
+            ProposalOptions::standard(),
+        )
+    }
+
+    /// Create a proposal that queues a treasury withdrawal to `recipient` when executed
+    pub fn create_treasury_payout_proposal(
+        &mut self,
+        title: String,
+        description: String,
+        recipient: Address,
+        amount: U256,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+    ) -> Result<U256, Vec<u8>> {
+        if recipient == Address::ZERO {
+            evm::log(InvalidAddress { addr: recipient });
+            return Err(b"Invalid payout recipient".to_vec());
+        }
+        if amount == U256::ZERO {
+            return Err(b"Payout amount must be greater than 0".to_vec());
+        }
+        if title.len() > MAX_PAYOUT_MEMO_LENGTH {
+            return Err(b"Payout title too long for withdrawal memo".to_vec());
+        }
         
-        // Increment counter for next proposal
-        self.proposal_count.set(proposal_id + U256::from(1));
+        let data = queueWithdrawalCall { recipient, amount, memo: title.clone() }.abi_encode();
         
-        // Emit event with ZK proof info
-        evm::log(ProposalCreated {
-            id: proposal_id,
-            proposer,
+        self.create_proposal(
             title,
-            startTime: current_time,
-            endTime: current_time + self.voting_period.get(),
-            kycCommitment: FixedBytes::from(kyc_commitment),
-        });
-        
-        Ok(proposal_id)
+            description,
+            self.treasury.get(),
+            U256::ZERO,
+            data,
+            kyc_commitment,
+            proof_hash,
+        )
     }
 
     /// Vote on proposal with ShadowID verification (0: For, 1: Against, 2: Abstain)
@@ -388,10 +694,11 @@ impl DAO {
         kyc_commitment: [u8; 32],       // KYC commitment from Noir ZK proof
         proof_hash: [u8; 32],          // ZK proof hash for this vote
     ) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
         let voter = msg::sender();
         
         // STEP 4: DAO checks: if !shadowid.is_verified(user) { revert("KYC required"); }
-        if !self.is_user_verified_in_shadowid(voter)? {
+        if !self.verify_and_cache(voter)? {
             // Emit required event: UserVerificationRequired(address)
             evm::log(UserVerificationRequired { user: voter });
             return Err(b"KYC required".to_vec());
@@ -422,22 +729,30 @@ impl DAO {
             return Err(b"Proposal is not active".to_vec());
         }
         
-        // Check voting period
-        let current_time = U256::from(block::timestamp());
-        if current_time > core.end_time {
-            return Err(b"Voting period has ended".to_vec());
+        // Opt-in: a late vote settles the proposal instead of reverting. Reverting would undo
+        // the finalization too, so the uncounted vote is reported through VoteDropped
+        let now = U256::from(block::timestamp());
+        if should_auto_finalize(self.auto_finalize.get(), now, core.end_time) {
+            let default_quorum = self.default_quorum(&core)?;
+            self.finalize_active(proposal_id, core, default_quorum, now)?;
+            evm::log(VoteDropped { id: proposal_id, voter });
+            return Ok(());
+        }
+        
+        let voter_type = self.members.get(voter).verification_type;
+        if !satisfies_verification_type(voter_type, core.required_verification_type) {
+            return Err(b"Verification type not accepted for this proposal".to_vec());
         }
         
-        // Get voting weight (would integrate with governance token in full implementation)
-        let weight = U256::from(1); // Simplified: each verified user gets 1 vote
+        // Check voting window (opens after the voting delay)
+        check_voting_window(now, core.start_time, core.end_time)?;
+        
+        // Delegated token votes at the proposal snapshot (never the live balance, so votes
+        // cannot be bought with a flash loan), within the configured bounds
+        let (raw_weight, weight) = self.vote_weight(core.start_time, core.weight_strategy, voter)?;
         
         // Record vote based on choice
-        match choice {
-            0 => core.for_votes += weight,      // For
-            1 => core.against_votes += weight,  // Against  
-            2 => core.abstain_votes += weight,  // Abstain
-            _ => return Err(b"Invalid vote choice (must be 0, 1, or 2)".to_vec()),
-        }
+        tally_vote(&mut core, choice, weight)?;
         
         // Create detailed vote record
         let vote_record = VoteRecord {
@@ -445,11 +760,13 @@ impl DAO {
             choice,
             weight,
             proof_hash,
-            timestamp: current_time,
+            timestamp: now,
+            raw_weight,
         };
         
         // Store vote record
         self.user_votes.setter((proposal_id, voter)).set(vote_record);
+        self.proposal_voters.setter(proposal_id).push(voter);
         
         // Save updated proposal
         self.proposal_core.setter(proposal_id).set(core);
@@ -472,8 +789,45 @@ impl DAO {
         Ok(())
     }
 
+    /// Self-delegate on the governance token if the caller never delegated, then vote.
+    /// The DAO must hold the token's DAO_ROLE to activate delegation on the caller's behalf
+    ///
+    /// Snapshot note: delegation writes a voting checkpoint at the current block time, after
+    /// the proposal's snapshot, so the activated votes only count for proposals created from
+    /// now on. This vote is weighted by the votes held at the snapshot; with none it reverts
+    /// rather than recording a zero-weight vote that would use up the caller's ballot.
+    pub fn delegate_self_and_vote(
+        &mut self,
+        proposal_id: U256,
+        choice: u8,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+    ) -> Result<(), Vec<u8>> {
+        let voter = msg::sender();
+        let token = IGovernanceToken::new(self.governance_token.get());
+        
+        let current_delegate = token
+            .delegates(StaticCall::new(), voter)
+            .map_err(|_| b"Failed to query delegate".to_vec())?;
+        
+        if current_delegate == Address::ZERO {
+            token
+                .activate_self_delegation(Call::new(), voter)
+                .map_err(|_| b"Self-delegation failed".to_vec())?;
+        }
+        
+        let core = self.proposal_core.get(proposal_id);
+        let (_, weight) = self.vote_weight(core.start_time, core.weight_strategy, voter)?;
+        if weight == U256::ZERO {
+            return Err(b"No voting power at proposal snapshot".to_vec());
+        }
+        
+        self.vote(proposal_id, choice, kyc_commitment, proof_hash)
+    }
+
     /// Finalize proposal after voting period ends
     pub fn finalize_proposal(&mut self, proposal_id: U256) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
         let mut core = self.proposal_core.getter(proposal_id).get();
         
         if core.state != ProposalState::Active {
@@ -481,23 +835,43 @@ impl DAO {
         }
         
         // Check voting period has ended
-        if U256::from(block::timestamp()) <= core.end_time {
+        let now = U256::from(block::timestamp());
+        if now <= core.end_time {
             return Err(b"Voting period not ended".to_vec());
         }
         
-        // Determine outcome based on votes
-        let total_votes = core.for_votes + core.against_votes + core.abstain_votes;
-        let quorum_required = U256::from(100); // Simplified quorum check
+        let default_quorum = self.default_quorum(&core)?;
+        self.finalize_active(proposal_id, core, default_quorum, now)
+    }
+
+    /// Whether a vote cast after the voting period finalizes the proposal instead of reverting
+    pub fn auto_finalize_enabled(&self) -> bool {
+        self.auto_finalize.get()
+    }
+
+    /// Get the cap on proposals created in a single block (0 = unlimited)
+    pub fn max_proposals_per_block(&self) -> U256 {
+        self.max_proposals_per_block.get()
+    }
+
+    /// Update the per-block proposal cap, 0 disables it (owner only)
+    pub fn set_max_proposals_per_block(&mut self, new_max: U256) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
         
-        if total_votes >= quorum_required && core.for_votes > core.against_votes {
-            core.state = ProposalState::Passed;
-        } else {
-            core.state = ProposalState::Rejected;
-        }
+        let old_max = self.max_proposals_per_block.get();
+        self.max_proposals_per_block.set(new_max);
         
-        let final_state = core.state;
-        self.proposal_core.setter(proposal_id).set(core);
-        evm::log(ProposalFinalized { id: proposal_id, state: final_state as u8 });
+        evm::log(MaxProposalsPerBlockUpdated { oldMax: old_max, newMax: new_max });
+        Ok(())
+    }
+
+    /// Enable or disable finalization from late votes (owner only)
+    pub fn set_auto_finalize(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
+        
+        self.auto_finalize.set(enabled);
+        
+        evm::log(AutoFinalizeUpdated { enabled });
         Ok(())
     }
 
@@ -509,18 +883,23 @@ impl DAO {
         kyc_commitment: [u8; 32],       // KYC commitment from Noir ZK proof
         proof_hash: [u8; 32],          // ZK proof hash for execution
     ) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
         let _guard = self.reentrancy_guard.guard()?;
         let executor = msg::sender();
         
+        // Allowlisted executors skip membership; everyone else must be a verified member
+        let allowlisted = self.executors.get(executor);
+        let member_executor = !allowlisted && self.verify_and_cache(executor)?;
+        
         // STEP 4: DAO checks: if !shadowid.is_verified(user) { revert("KYC required"); }
-        if !self.is_user_verified_in_shadowid(executor)? {
+        if !executor_authorized(allowlisted, member_executor) {
             // Emit required event: UserVerificationRequired(address)
             evm::log(UserVerificationRequired { user: executor });
             return Err(b"KYC required".to_vec());
         }
         
-        // Validate ZK proof for execution
-        if !self.validate_zk_proof(executor, kyc_commitment, proof_hash)? {
+        // Validate ZK proof for member execution
+        if member_executor && !self.validate_zk_proof(executor, kyc_commitment, proof_hash)? {
             evm::log(InvalidProof {
                 user: executor,
                 commitment: FixedBytes::from(kyc_commitment),
@@ -534,41 +913,111 @@ impl DAO {
             return Err(b"Proposal not in passed state".to_vec()); 
         }
         
+        let executor_type = self.members.get(executor).verification_type;
+        if member_executor && !satisfies_verification_type(executor_type, core.required_verification_type) {
+            return Err(b"Verification type not accepted for this proposal".to_vec());
+        }
+        
         let mut execution = self.execution_data.getter(proposal_id).get();
         if execution.executed { 
             return Err(b"Proposal already executed".to_vec()); 
         }
+        check_execution_hash(core.execution_hash, &execution)?;
         
         // Check timelock delay
-        let current_time = U256::from(block::timestamp());
-        if current_time < execution.timelock_end {
-            return Err(b"Timelock period not expired".to_vec());
-        }
+        check_execution_window(U256::from(block::timestamp()), execution.timelock_end)?;
         
         // Mark as executed
         execution.executed = true;
+        let (target, value, data) = (execution.target, execution.value, execution.data.clone());
         let mut core_mut = self.proposal_core.getter(proposal_id).get();
         core_mut.state = ProposalState::Executed;
         
-        // Save state
+        // Save state before calling out, so the target can't re-execute the proposal
         self.execution_data.setter(proposal_id).set(execution);
         self.proposal_core.setter(proposal_id).set(core_mut);
         
+        // ETH sent back by the target during execution is accepted; any other send reverts
+        self.accepting_eth.set(true);
+        let result = RawCall::new_with_value(value).call(target, &data);
+        self.accepting_eth.set(false);
+        result.map_err(|_| b"Proposal execution failed".to_vec())?;
+        
         // Emit event
         evm::log(ProposalExecuted {
             id: proposal_id,
             executor,
         });
         
-        evm::log(ZKProofValidated {
-            user: executor,
-            commitment: FixedBytes::from(kyc_commitment),
-            proofHash: FixedBytes::from(proof_hash),
-        });
+        if member_executor {
+            evm::log(ZKProofValidated {
+                user: executor,
+                commitment: FixedBytes::from(kyc_commitment),
+                proofHash: FixedBytes::from(proof_hash),
+            });
+        }
+        
+        Ok(())
+    }
+
+    /// Allow or revoke an address to execute passed proposals without membership (owner only)
+    pub fn set_executor(&mut self, executor: Address, allowed: bool) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can manage executors".to_vec());
+        }
+        if executor == Address::ZERO {
+            evm::log(InvalidAddress { addr: executor });
+            return Err(b"Invalid executor".to_vec());
+        }
+        
+        self.executors.setter(executor).set(allowed);
+        evm::log(ExecutorUpdated { executor, allowed });
+        Ok(())
+    }
+
+    /// Check if an address is an allowlisted executor
+    pub fn is_executor(&self, executor: Address) -> bool {
+        self.executors.get(executor)
+    }
+
+    /// Allow or disallow several proposal execution targets at once (owner only)
+    pub fn set_allowed_targets(
+        &mut self,
+        targets: Vec<Address>,
+        allowed: Vec<bool>,
+    ) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can manage allowed targets".to_vec());
+        }
         
+        check_allowed_targets(&targets, &allowed)?;
+        self.store_allowed_targets(&targets, &allowed);
         Ok(())
     }
 
+    /// Check if proposals may call an address
+    pub fn is_allowed_target(&self, target: Address) -> bool {
+        self.allowed_targets.get(target)
+    }
+
+    /// Execute a passed proposal after checking the supplied description against its stored hash
+    pub fn execute_proposal_with_description(
+        &mut self,
+        proposal_id: U256,
+        description: String,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+    ) -> Result<(), Vec<u8>> {
+        let core = self.proposal_core.get(proposal_id);
+        check_description(core.description_hash, &description)?;
+        
+        self.execute_proposal(proposal_id, kyc_commitment, proof_hash)
+    }
+
     /// Cancel proposal (owner only)
     pub fn cancel_proposal(&mut self, proposal_id: U256) -> Result<(), Vec<u8>> {
         let caller = msg::sender();
@@ -605,14 +1054,58 @@ impl DAO {
         
         let mut member_data = self.members.getter(member).get();
         member_data.is_member = true;
-        self.members.setter(member).set(member_data);
+        self.store_member(member, member_data);
         
         evm::log(MemberAdded { member });
         Ok(())
     }
 
+    /// Bulk-write verified members carried over from a legacy DAO (owner only, until
+    /// finalize_member_import is called)
+    pub fn import_members(
+        &mut self,
+        users: Vec<Address>,
+        commitments: Vec<[u8; 32]>,
+        proof_hashes: Vec<[u8; 32]>,
+        verification_types: Vec<u8>,
+        timestamps: Vec<U256>,
+    ) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can import members".to_vec());
+        }
+        
+        self.store_imported_members(
+            users,
+            commitments,
+            proof_hashes,
+            verification_types,
+            timestamps,
+        )
+    }
+
+    /// Permanently disable import_members (owner only)
+    pub fn finalize_member_import(&mut self) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can finalize import".to_vec());
+        }
+        
+        self.import_finalized.set(true);
+        evm::log(MemberImportFinalized {});
+        Ok(())
+    }
+
+    /// Whether member imports have been permanently disabled
+    pub fn member_import_finalized(&self) -> bool {
+        self.import_finalized.get()
+    }
+
     /// Submit KYC data and ZK proof hash
     pub fn submit_kyc_proof(&mut self, kyc_hash: FixedBytes<32>, zk_proof_hash: FixedBytes<32>) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
         let member = msg::sender();
         let mut member_data = self.members.getter(member).get();
         
@@ -626,12 +1119,17 @@ impl DAO {
 
     /// Verify member KYC (verifier only)
     pub fn verify_member(&mut self, member: Address) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
         let verifier = msg::sender();
-        if !self.kyc_verifiers.get(verifier) {
+        let allowed_types = self.kyc_verifiers.get(verifier);
+        if allowed_types == 0 {
             return Err(b"Not authorized verifier".to_vec());
         }
         
         let mut member_data = self.members.getter(member).get();
+        if !verifier_can_verify(allowed_types, member_data.verification_type) {
+            return Err(b"Verifier not authorized for verification type".to_vec());
+        }
         member_data.verified = true;
         member_data.verification_timestamp = U256::from(block::timestamp());
         self.members.setter(member).set(member_data);
@@ -640,15 +1138,60 @@ impl DAO {
         Ok(())
     }
 
-    /// Add KYC verifier (owner only)
-    pub fn add_verifier(&mut self, verifier: Address) -> Result<(), Vec<u8>> {
+    /// Add KYC verifier scoped to a bitmask of verification types (verifier admin only)
+    pub fn add_verifier(&mut self, verifier: Address, allowed_types: u8) -> Result<(), Vec<u8>> {
+        self.only_verifier_admin()?;
+        
+        if allowed_types == 0 || allowed_types & !(VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB) != 0 {
+            return Err(b"Invalid verification type mask".to_vec());
+        }
+        
+        self.kyc_verifiers.setter(verifier).set(allowed_types);
+        evm::log(KycVerifierAdded { verifier, allowedTypes: allowed_types });
+        Ok(())
+    }
+
+    /// Remove verification types from a KYC verifier (verifier admin only)
+    pub fn remove_verifier(&mut self, verifier: Address, removed_types: u8) -> Result<(), Vec<u8>> {
+        self.only_verifier_admin()?;
+        
+        let remaining = self.kyc_verifiers.get(verifier) & !removed_types;
+        self.kyc_verifiers.setter(verifier).set(remaining);
+        
+        if remaining == 0 {
+            evm::log(KycVerifierRemoved { verifier });
+        } else {
+            evm::log(KycVerifierAdded { verifier, allowedTypes: remaining });
+        }
+        Ok(())
+    }
+
+    /// Get the verification types a verifier may attest to
+    pub fn get_verifier_types(&self, verifier: Address) -> u8 {
+        self.kyc_verifiers.get(verifier)
+    }
+
+    /// Get the address that manages KYC verifiers
+    pub fn verifier_admin(&self) -> Address {
+        self.verifier_admin.get()
+    }
+
+    /// Hand the KYC verifier admin role to a new address (verifier admin or owner)
+    pub fn set_verifier_admin(&mut self, new_admin: Address) -> Result<(), Vec<u8>> {
         let caller = msg::sender();
-        if caller != self.owner.get() {
-            return Err(b"Only owner".to_vec());
+        if !can_set_verifier_admin(caller, self.verifier_admin.get(), self.owner.get()) {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only verifier admin or owner".to_vec());
         }
+        if new_admin == Address::ZERO {
+            evm::log(InvalidAddress { addr: new_admin });
+            return Err(b"Invalid verifier admin".to_vec());
+        }
+        
+        let old_admin = self.verifier_admin.get();
+        self.verifier_admin.set(new_admin);
         
-        self.kyc_verifiers.setter(verifier).set(true);
-        evm::log(KycVerifierAdded { verifier });
+        evm::log(VerifierAdminUpdated { oldAdmin: old_admin, newAdmin: new_admin });
         Ok(())
     }
 
@@ -676,6 +1219,62 @@ impl DAO {
         )
     }
 
+    /// Get the keccak256 of a proposal's description
+    pub fn get_proposal_description_hash(&self, proposal_id: U256) -> [u8; 32] {
+        self.proposal_core.get(proposal_id).description_hash
+    }
+
+    /// Get the KYC commitment and proof hash that backed a proposal's creation
+    pub fn get_proposal_zk(&self, proposal_id: U256) -> ([u8; 32], [u8; 32]) {
+        let core = self.proposal_core.get(proposal_id);
+        (core.kyc_commitment, core.proof_hash)
+    }
+
+    /// Get the verification type a proposal requires (0 = any verified member)
+    pub fn get_proposal_verification_type(&self, proposal_id: U256) -> u8 {
+        self.proposal_core.get(proposal_id).required_verification_type
+    }
+
+    /// Get what a proposal requires of voters: (verification type, named proof types).
+    /// Shared shape with ZkEnhancedDAO; the base DAO gates on verification type only
+    pub fn proposal_requirements(&self, proposal_id: U256) -> (u8, Vec<String>) {
+        (self.get_proposal_verification_type(proposal_id), Vec::new())
+    }
+
+    /// Get the timelock a proposal waits once passed: its override if longer than the
+    /// global execution delay
+    pub fn get_proposal_execution_delay(&self, proposal_id: U256) -> U256 {
+        let delay_override = self.proposal_core.get(proposal_id).execution_delay_override;
+        required_execution_delay(delay_override, self.execution_delay.get())
+    }
+
+    /// Get a proposal's weight strategy (0: token-weighted, 1: one person, 2: quadratic)
+    pub fn get_proposal_weight_strategy(&self, proposal_id: U256) -> u8 {
+        self.proposal_core.get(proposal_id).weight_strategy.into()
+    }
+
+    /// Get proposal details including ZK commitment and proof hash
+    pub fn get_proposal_full(&self, proposal_id: U256) -> (
+        U256, Address, String, String, U256, U256, U256, U256, U256, u8, bool, [u8; 32], [u8; 32]
+    ) {
+        let core = self.proposal_core.get(proposal_id);
+        (
+            core.id,
+            core.proposer,
+            core.title,
+            core.description,
+            core.start_time,
+            core.end_time,
+            core.for_votes,
+            core.against_votes,
+            core.abstain_votes,
+            core.state as u8,
+            core.cancelled,
+            core.kyc_commitment,
+            core.proof_hash,
+        )
+    }
+
     /// Check if address is verified member
     pub fn is_verified_member(&self, member: Address) -> bool {
         let member_data = self.members.get(member);
@@ -692,6 +1291,105 @@ impl DAO {
         )
     }
 
+    /// Update voting period (owner only)
+    pub fn set_voting_period(&mut self, new_period: U256) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
+        if new_period < U256::from(MIN_VOTING_PERIOD) {
+            return Err(b"Voting period too short".to_vec());
+        }
+        
+        let old_period = self.voting_period.get();
+        self.voting_period.set(new_period);
+        
+        evm::log(VotingPeriodUpdated { oldPeriod: old_period, newPeriod: new_period });
+        self.emit_parameters_snapshot();
+        Ok(())
+    }
+
+    /// Get delay between proposal creation and the start of voting
+    pub fn voting_delay(&self) -> U256 {
+        self.voting_delay.get()
+    }
+
+    /// Update voting delay for new proposals (owner only)
+    pub fn set_voting_delay(&mut self, new_delay: U256) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
+        
+        let old_delay = self.voting_delay.get();
+        self.voting_delay.set(new_delay);
+        
+        evm::log(VotingDelayUpdated { oldDelay: old_delay, newDelay: new_delay });
+        self.emit_parameters_snapshot();
+        Ok(())
+    }
+
+    /// Update quorum percentage (owner only)
+    pub fn set_quorum_percent(&mut self, new_pct: U256) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
+        if new_pct > U256::from(100) {
+            return Err(b"Quorum cannot exceed 100%".to_vec());
+        }
+        
+        let old_pct = self.quorum_percent.get();
+        self.quorum_percent.set(new_pct);
+        
+        evm::log(QuorumPercentUpdated { oldPct: old_pct, newPct: new_pct });
+        self.emit_parameters_snapshot();
+        Ok(())
+    }
+
+    /// Update execution delay (owner only)
+    pub fn set_execution_delay(&mut self, new_delay: U256) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
+        
+        let old_delay = self.execution_delay.get();
+        self.execution_delay.set(new_delay);
+        
+        evm::log(ExecutionDelayUpdated { oldDelay: old_delay, newDelay: new_delay });
+        self.emit_parameters_snapshot();
+        Ok(())
+    }
+
+    /// Update proposal threshold (owner only)
+    pub fn set_proposal_threshold(&mut self, new_threshold: U256) -> Result<(), Vec<u8>> {
+        self.only_owner_param_update()?;
+        
+        let old_threshold = self.proposal_threshold.get();
+        self.proposal_threshold.set(new_threshold);
+        
+        evm::log(ProposalThresholdUpdated { oldThreshold: old_threshold, newThreshold: new_threshold });
+        self.emit_parameters_snapshot();
+        Ok(())
+    }
+
+    /// Get per-voter weight bounds (0 = unset)
+    pub fn get_vote_weight_bounds(&self) -> (U256, U256) {
+        (self.min_vote_weight.get(), self.max_vote_weight.get())
+    }
+
+    /// Set per-voter weight bounds, 0 disables a bound (owner only)
+    pub fn set_vote_weight_bounds(&mut self, min_weight: U256, max_weight: U256) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can update vote weight bounds".to_vec());
+        }
+        
+        if min_weight > U256::ZERO && max_weight > U256::ZERO && min_weight > max_weight {
+            return Err(b"Minimum weight exceeds maximum weight".to_vec());
+        }
+        
+        self.min_vote_weight.set(min_weight);
+        self.max_vote_weight.set(max_weight);
+        
+        evm::log(VoteWeightBoundsUpdated {
+            minWeight: min_weight,
+            maxWeight: max_weight,
+        });
+        
+        Ok(())
+    }
+
     /// Get current proposal count
     pub fn proposal_count(&self) -> U256 {
         self.proposal_count.get()
@@ -731,135 +1429,206 @@ impl DAO {
         Ok(())
     }
 
-    // =============================================================================
-    // PRIVATE/INTERNAL HELPER FUNCTIONS
-    // =============================================================================
-
-    /// STEP 4: DAO checks: if !shadowid.is_verified(user) { revert("KYC required"); }
-    /// Verifies user through ShadowIDRegistry contract integration
-    /// Flow: Backend sends proof_hash to ShadowIDRegistry → DAO checks verification status
-    fn is_user_verified_in_shadowid(&self, user: Address) -> Result<bool, Vec<u8>> {
-        let registry = self.shadow_id_registry.get();
+    /// Whether a member's cached commitment still matches the ShadowIDRegistry
+    /// (false on mismatch or registry error; such members need resync_member)
+    pub fn is_commitment_current(&self, user: Address) -> bool {
+        let registry_commitment = IShadowIDRegistry::new(self.shadow_id_registry.get())
+            .get_commitment(StaticCall::new(), user)
+            .ok()
+            .map(|commitment| commitment.0);
         
-        // PRODUCTION: External call to ShadowIDRegistry.isVerified(user)
-        // let is_verified = Call::new_in(self).call(registry, &IsVerifiedCall { user })?;
+        commitment_is_current(self.members.get(user).kyc_commitment, registry_commitment)
+    }
+
+    /// Get the EmergencyStop coordinator address (zero = none)
+    pub fn emergency_stop_contract(&self) -> Address {
+        self.emergency_stop.get()
+    }
+
+    /// Point the DAO at a shared EmergencyStop coordinator, or zero to detach (owner only)
+    pub fn set_emergency_stop_contract(&mut self, new_contract: Address) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can set emergency stop".to_vec());
+        }
         
-        // CURRENT: Check if user has valid proof registered (simulates ShadowIDRegistry state)
-        // This represents: Backend sent proof_hash to ShadowIDRegistry after Aztec ZK proof generation
-        let member_data = self.members.get(user);
-        let is_verified = member_data.verified && 
-                         !member_data.kyc_commitment.iter().all(|&b| b == 0) &&
-                         !member_data.proof_hash.iter().all(|&b| b == 0);
+        let old_contract = self.emergency_stop.get();
+        self.emergency_stop.set(new_contract);
         
-        Ok(is_verified)
+        evm::log(EmergencyStopContractUpdated {
+            oldContract: old_contract,
+            newContract: new_contract,
+        });
+        Ok(())
     }
 
-    /// Validate ZK proof against commitment
-    fn validate_zk_proof(&mut self, user: Address, commitment: [u8; 32], proof_hash: [u8; 32]) -> Result<bool, Vec<u8>> {
-        // Check if commitment is not zero
-        if commitment.iter().all(|&b| b == 0) {
-            return Ok(false);
+    /// Overwrite a member's local verification state from the current ShadowIDRegistry (owner only)
+    /// Use after update_shadow_id_registry so `members` reflects the new registry
+    pub fn resync_member(&mut self, member: Address) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can resync members".to_vec());
         }
         
-        // Check if proof hash is not zero
-        if proof_hash.iter().all(|&b| b == 0) {
-            return Ok(false);
+        self.resync_member_from_registry(member)
+    }
+
+    /// Whether a local verification miss falls through to a live registry isVerified call
+    pub fn registry_fallback_enabled(&self) -> bool {
+        self.registry_fallback.get()
+    }
+
+    /// Enable or disable the live registry fallback for unsynced members (owner only)
+    pub fn set_registry_fallback(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can set registry fallback".to_vec());
         }
         
-        // In a full implementation, this would:
-        // 1. Call ShadowIDRegistry.hasValidProof(user, commitment)
-        // 2. Verify the ZK proof using Noir verification logic
-        // 3. Check that the commitment matches user's KYC data
-        
-        // For now, store the validated proof
-        self.validated_proofs.setter(user).set(FixedBytes::from(commitment));
-        
-        // Update member data with latest proof
-        let mut member_data = self.members.getter(user).get();
-        member_data.kyc_commitment = commitment;
-        member_data.proof_hash = proof_hash;
-        member_data.verification_timestamp = U256::from(block::timestamp());
-        self.members.setter(user).set(member_data);
+        self.registry_fallback.set(enabled);
         
-        Ok(true) // Simplified: assume valid if non-zero
+        evm::log(RegistryFallbackUpdated { enabled });
+        Ok(())
     }
 
-    /// COMPLETE FLOW INTEGRATION: Backend submits proof_hash to ShadowIDRegistry
-    /// Flow: 1. User uploads KYC → 2. generates commitment via Noir → 3. generates ZK proof → Aztec
-    /// → 4. Backend sends proof_hash to ShadowIDRegistry (this function simulates this step)
-    pub fn submit_zk_proof(
-        &mut self,
-        user: Address,
-        kyc_commitment: [u8; 32],     // STEP 1: KYC commitment from Noir circuit
-        proof_hash: [u8; 32],        // STEP 2-3: ZK proof hash from Aztec generation
-    ) -> Result<(), Vec<u8>> {
+    /// Batch version of resync_member (owner only)
+    pub fn resync_members(&mut self, members: Vec<Address>) -> Result<(), Vec<u8>> {
         let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can resync members".to_vec());
+        }
         
-        // Only authorized backend service can submit proofs (simulates backend integration)
+        for member in members {
+            self.resync_member_from_registry(member)?;
+        }
+        Ok(())
+    }
+
+    /// Get linked Treasury address
+    pub fn treasury(&self) -> Address {
+        self.treasury.get()
+    }
+
+    /// ERC-165 interface detection
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool {
+        interface_id == FixedBytes(ERC165_INTERFACE_ID)
+            || interface_id == FixedBytes(DAO_INTERFACE_ID)
+    }
+
+    /// Number of addresses registered as members
+    pub fn member_count(&self) -> U256 {
+        self.member_count.get()
+    }
+
+    /// Dashboard view: (owner, treasury, registry, proposalCount, memberCount, votingPeriod,
+    /// quorumPercent, verificationRequired)
+    pub fn get_dao_summary(&self) -> (Address, Address, Address, U256, U256, U256, U256, bool) {
+        let (voting_period, quorum_percent, _, _) = self.get_parameters();
+        (
+            self.owner(),
+            self.treasury(),
+            self.shadow_id_registry(),
+            self.proposal_count(),
+            self.member_count(),
+            voting_period,
+            quorum_percent,
+            self.verification_required.get(),
+        )
+    }
+
+    /// Relink the Treasury and allow it as an execution target (owner only)
+    pub fn set_treasury(&mut self, new_treasury: Address, remove_old: bool) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
         if caller != self.owner.get() {
             evm::log(Unauthorized { caller });
-            return Err(b"Only authorized backend can submit proofs".to_vec());
+            return Err(b"Only owner can update treasury".to_vec());
         }
         
-        // Validate proof data from the complete KYC → Noir → Aztec flow
-        if kyc_commitment.iter().all(|&b| b == 0) || proof_hash.iter().all(|&b| b == 0) {
-            return Err(b"Invalid commitment or proof hash from ZK flow".to_vec());
+        if new_treasury == Address::ZERO {
+            evm::log(InvalidAddress { addr: new_treasury });
+            return Err(b"Invalid treasury address".to_vec());
         }
         
-        // Update member verification status (simulates ShadowIDRegistry state)
-        // In production: Backend would call ShadowIDRegistry.submitProof(user, proof_hash)
-        let member_data = MemberData {
-            is_member: true,
-            verified: true,                               // User is now verified in ShadowID system
-            kyc_commitment,
-            proof_hash,
-            verification_timestamp: U256::from(block::timestamp()),
-            verification_type: 1, // ShadowID KYC verification
-        };
+        let old_treasury = self.treasury.get();
+        self.treasury.set(new_treasury);
         
-        self.members.setter(user).set(member_data);
-        self.validated_proofs.setter(user).set(FixedBytes::from(kyc_commitment));
+        if remove_old && old_treasury != new_treasury {
+            self.allowed_targets.setter(old_treasury).set(false);
+            evm::log(AllowedTargetUpdated { target: old_treasury, allowed: false });
+        }
         
-        // Emit required events per specification
-        evm::log(ProofSubmitted { user });              // Required event: ProofSubmitted(address)
-        
-        evm::log(ZKProofValidated {
-            user,
-            commitment: FixedBytes::from(kyc_commitment),
-            proofHash: FixedBytes::from(proof_hash),
-        });
+        self.allowed_targets.setter(new_treasury).set(true);
+        evm::log(AllowedTargetUpdated { target: new_treasury, allowed: true });
+        evm::log(TreasuryLinked { newTreasury: new_treasury });
         
         Ok(())
     }
 
-    /// Register verified user (called after ShadowIDRegistry verification)
-    /// This is the callback from ShadowIDRegistry after proof verification
-    pub fn register_verified_user(
-        &mut self,
-        user: Address,
-        kyc_commitment: [u8; 32],
-        proof_hash: [u8; 32],
-        verification_type: u8,
-    ) -> Result<(), Vec<u8>> {
+    /// Recover ETH force-sent to the DAO (owner only)
+    pub fn rescue_eth(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let _guard = self.reentrancy_guard.guard()?;
         let caller = msg::sender();
-        
-        // Only ShadowIDRegistry or owner can register verified users
-        if caller != self.shadow_id_registry.get() && caller != self.owner.get() {
+        if caller != self.owner.get() {
             evm::log(Unauthorized { caller });
-            return Err(b"Only ShadowIDRegistry or owner can register verified users".to_vec());
+            return Err(b"Only owner can rescue ETH".to_vec());
+        }
+        
+        if to == Address::ZERO {
+            evm::log(InvalidAddress { addr: to });
+            return Err(b"Invalid rescue recipient".to_vec());
         }
+        if amount == U256::ZERO || amount > contract::balance() {
+            return Err(b"Invalid rescue amount".to_vec());
+        }
+        
+        transfer_eth(to, amount).map_err(|_| b"ETH transfer failed".to_vec())?;
         
+        evm::log(EthRescued { to, amount });
+        Ok(())
+    }
+
+    // =============================================================================
+    // PRIVATE/INTERNAL HELPER FUNCTIONS
+    // =============================================================================
+
+    /// Store a verified member record and its validated commitment from the ZK flow
+    fn record_zk_proof(&mut self, user: Address, kyc_commitment: [u8; 32], proof_hash: [u8; 32], now: U256) {
         let member_data = MemberData {
             is_member: true,
-            verified: true,
+            verified: true,                               // User is now verified in ShadowID system
             kyc_commitment,
             proof_hash,
-            verification_timestamp: U256::from(block::timestamp()),
-            verification_type,
+            verification_timestamp: now,
+            verification_type: 1, // ShadowID KYC verification
         };
         
-        self.members.setter(user).set(member_data);
+        self.store_member(user, member_data);
         self.validated_proofs.setter(user).set(FixedBytes::from(kyc_commitment));
+    }
+
+    /// Validate and record a backend-submitted proof (submit_zk_proof after its access checks)
+    fn register_zk_proof(
+        &mut self,
+        user: Address,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        now: U256,
+    ) -> Result<(), Vec<u8>> {
+        // Validate proof data from the complete KYC → Noir → Aztec flow
+        if kyc_commitment.iter().all(|&b| b == 0) || proof_hash.iter().all(|&b| b == 0) {
+            return Err(b"Invalid commitment or proof hash from ZK flow".to_vec());
+        }
+        
+        // Update member verification status (simulates ShadowIDRegistry state)
+        // In production: Backend would call ShadowIDRegistry.submitProof(user, proof_hash)
+        self.record_zk_proof(user, kyc_commitment, proof_hash, now);
+        
+        // Emit required events per specification
+        evm::log(ProofSubmitted { user });              // Required event: ProofSubmitted(address)
         
         evm::log(ZKProofValidated {
             user,
@@ -870,61 +1639,3263 @@ impl DAO {
         Ok(())
     }
 
-    /// Get user verification status and proof data
-    pub fn get_user_verification(&self, user: Address) -> (bool, [u8; 32], [u8; 32], u8, U256) {
-        let member_data = self.members.get(user);
-        (
-            member_data.verified,
-            member_data.kyc_commitment,
-            member_data.proof_hash,
-            member_data.verification_type,
-            member_data.verification_timestamp,
-        )
+    /// Write an import_members batch once it passes the finalization and shape checks
+    fn store_imported_members(
+        &mut self,
+        users: Vec<Address>,
+        commitments: Vec<[u8; 32]>,
+        proof_hashes: Vec<[u8; 32]>,
+        verification_types: Vec<u8>,
+        timestamps: Vec<U256>,
+    ) -> Result<(), Vec<u8>> {
+        if self.import_finalized.get() {
+            return Err(b"Member import finalized".to_vec());
+        }
+        check_member_import(
+            &users,
+            &commitments,
+            &proof_hashes,
+            &verification_types,
+            &timestamps,
+            U256::from(block::timestamp()),
+        )?;
+        // Imports seed the DAO; live members are managed through proofs and resyncs
+        if users.iter().any(|&user| self.members.get(user).is_member) {
+            return Err(b"Already a member".to_vec());
+        }
+        
+        for i in 0..users.len() {
+            let member_data = MemberData {
+                is_member: true,
+                verified: true,
+                kyc_commitment: commitments[i],
+                proof_hash: proof_hashes[i],
+                verification_timestamp: timestamps[i],
+                verification_type: verification_types[i],
+            };
+            self.store_member(users[i], member_data);
+            self.validated_proofs.setter(users[i]).set(FixedBytes::from(commitments[i]));
+        }
+        
+        evm::log(MembersImported { count: U256::from(users.len()) });
+        Ok(())
     }
 
-    /// Get vote record for user on specific proposal
-    pub fn get_vote_record(&self, proposal_id: U256, user: Address) -> (bool, u8, U256, [u8; 32], U256) {
-        let vote_record = self.user_votes.get((proposal_id, user));
-        (
-            vote_record.has_voted,
-            vote_record.choice,
-            vote_record.weight,
-            vote_record.proof_hash,
-            vote_record.timestamp,
-        )
+    /// Proposal checks that do not depend on the proposer: the target must be allowed
+    /// and the current block must have a free proposal slot
+    fn check_proposal_admission(&self, target: Address) -> Result<(), Vec<u8>> {
+        if !self.allowed_targets.get(target) {
+            return Err(b"Target contract not allowed".to_vec());
+        }
+        
+        self.slot_available(U256::from(block::number()))?;
+        Ok(())
     }
 
-    /// Check if user is verified in ShadowIDRegistry (public view function)
-    /// This implements the check: shadowid.is_verified(user)
-    pub fn is_user_verified(&self, user: Address) -> bool {
-        match self.is_user_verified_in_shadowid(user) {
-            Ok(verified) => verified,
-            Err(_) => false,
+    /// Count a new proposal against its block's max_proposals_per_block allowance
+    fn consume_proposal_slot(&mut self, block_number: U256) -> Result<(), Vec<u8>> {
+        let created = self.slot_available(block_number)?;
+        self.proposals_per_block.setter(block_number).set(created + U256::from(1));
+        Ok(())
+    }
+
+    /// Proposals already created in a block, or an error if it has used its allowance
+    fn slot_available(&self, block_number: U256) -> Result<U256, Vec<u8>> {
+        let created = self.proposals_per_block.get(block_number);
+        let max = self.max_proposals_per_block.get();
+        if max != U256::ZERO && created >= max {
+            return Err(b"Proposal rate limit".to_vec());
         }
+        Ok(created)
     }
 
-    /// Get KYC flow status for frontend integration
-    /// Returns: (needs_kyc_upload, needs_zk_proof, is_verified, proof_submitted)
-    pub fn get_kyc_status(&self, user: Address) -> (bool, bool, bool, bool) {
-        let member_data = self.members.get(user);
+    /// Settle an active proposal whose voting period has ended as Passed or Rejected,
+    /// recording the quorum it was held to
+    fn finalize_active(
+        &mut self,
+        proposal_id: U256,
+        mut core: ProposalCore,
+        default_quorum: U256,
+        now: U256,
+    ) -> Result<(), Vec<u8>> {
+        // Determine outcome based on votes
+        core.quorum_votes = required_quorum(core.quorum_override, default_quorum);
+        core.state = tally_outcome(&core);
+        if core.state == ProposalState::Passed {
+            // Start the execution timelock
+            let mut execution = self.execution_data.getter(proposal_id).get();
+            let delay = required_execution_delay(
+                core.execution_delay_override,
+                self.execution_delay.get(),
+            );
+            execution.timelock_end = now
+                .checked_add(delay)
+                .ok_or_else(|| b"Execution delay overflow".to_vec())?;
+            self.execution_data.setter(proposal_id).set(execution);
+        }
         
-        let has_commitment = !member_data.kyc_commitment.iter().all(|&b| b == 0);
-        let has_proof = !member_data.proof_hash.iter().all(|&b| b == 0);
+        let final_state = core.state;
+        self.proposal_core.setter(proposal_id).set(core);
+        evm::log(ProposalFinalized { id: proposal_id, state: final_state as u8 });
+        Ok(())
+    }
+
+    /// Record each target's allowed flag and announce it
+    fn store_allowed_targets(&mut self, targets: &[Address], allowed: &[bool]) {
+        for (&target, &is_allowed) in targets.iter().zip(allowed) {
+            self.allowed_targets.setter(target).set(is_allowed);
+            evm::log(AllowedTargetUpdated { target, allowed: is_allowed });
+        }
+    }
+
+    /// Write a member record, counting addresses the first time they become members
+    fn store_member(&mut self, user: Address, member_data: MemberData) {
+        if member_data.is_member && !self.members.get(user).is_member {
+            self.member_count.set(self.member_count.get() + U256::from(1));
+        }
+        self.members.setter(user).set(member_data);
+    }
+
+    /// Voting weight shared by vote and effective_weight: the voter's delegated token votes at
+    /// the proposal snapshot, and that weight clamped to the min/max weight bounds
+    fn vote_weight(
+        &self,
+        start_time: U256,
+        strategy: WeightStrategy,
+        voter: Address,
+    ) -> Result<(U256, U256), Vec<u8>> {
+        let past_votes = self.past_votes_at_snapshot(start_time, voter)?;
+        Ok(self.weigh_votes(strategy, past_votes))
+    }
+
+    /// Turn snapshot token votes into (raw strategy weight, weight within the min/max bounds)
+    fn weigh_votes(&self, strategy: WeightStrategy, past_votes: U256) -> (U256, U256) {
+        let raw_weight = strategy_weight(strategy, past_votes);
+        let weight = clamp_vote_weight(
+            raw_weight,
+            self.min_vote_weight.get(),
+            self.max_vote_weight.get(),
+        );
+        (raw_weight, weight)
+    }
+
+    /// Votes a proposal needs by default: quorum_percent of the token supply at its snapshot for
+    /// token-weighted proposals. Quadratic and one-person weight has no snapshotted total to take
+    /// a percentage of, so those proposals keep the fixed QUORUM_VOTES
+    fn default_quorum(&self, core: &ProposalCore) -> Result<U256, Vec<u8>> {
+        match core.weight_strategy {
+            WeightStrategy::TokenWeighted => {
+                let supply = self.past_supply_at_snapshot(core.start_time)?;
+                Ok(percent_quorum(supply, self.quorum_percent.get()))
+            }
+            WeightStrategy::OnePerson | WeightStrategy::Quadratic => Ok(U256::from(QUORUM_VOTES)),
+        }
+    }
+
+    /// Governance token supply at the snapshot of a proposal starting at `start_time`
+    fn past_supply_at_snapshot(&self, start_time: U256) -> Result<U256, Vec<u8>> {
+        let snapshot = vote_snapshot(start_time);
+        if snapshot >= U256::from(block::timestamp()) {
+            return Err(b"Voting snapshot not reached".to_vec());
+        }
         
-        let needs_kyc_upload = !has_commitment;
-        let needs_zk_proof = has_commitment && !has_proof;
-        let is_verified = member_data.verified;
-        let proof_submitted = has_proof;
+        IGovernanceToken::new(self.governance_token.get())
+            .get_past_total_supply(StaticCall::new(), snapshot)
+            .map_err(|_| b"Failed to query total supply".to_vec())
+    }
+
+    /// Delegated token votes of `voter` at the snapshot of a proposal starting at `start_time`
+    fn past_votes_at_snapshot(&self, start_time: U256, voter: Address) -> Result<U256, Vec<u8>> {
+        let snapshot = vote_snapshot(start_time);
+        if snapshot >= U256::from(block::timestamp()) {
+            return Err(b"Voting snapshot not reached".to_vec());
+        }
         
-        (needs_kyc_upload, needs_zk_proof, is_verified, proof_submitted)
+        IGovernanceToken::new(self.governance_token.get())
+            .get_past_votes(StaticCall::new(), voter, snapshot)
+            .map_err(|_| b"Failed to query voting power".to_vec())
     }
 
-    /// Trigger KYC requirement check (helper function for frontend)
-    pub fn require_verification(&self, user: Address) -> Result<(), Vec<u8>> {
-        if !self.is_user_verified(user) {
-            evm::log(UserVerificationRequired { user });
+    /// Token votes of `voter` for a proposal starting at `start_time`: the snapshot value once
+    /// it has been taken, before that the current votes the snapshot would record unchanged
+    fn projected_votes_at_snapshot(&self, start_time: U256, voter: Address) -> Result<U256, Vec<u8>> {
+        if vote_snapshot(start_time) < U256::from(block::timestamp()) {
+            return self.past_votes_at_snapshot(start_time, voter);
+        }
+        
+        IGovernanceToken::new(self.governance_token.get())
+            .get_votes(StaticCall::new(), voter)
+            .map_err(|_| b"Failed to query voting power".to_vec())
+    }
+
+    /// Shared proposal creation path behind the public create_proposal variants
+    fn create_proposal_internal(
+        &mut self,
+        proposer: Address,
+        title: String,
+        description: String,
+        description_hash: [u8; 32],
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        options: ProposalOptions,
+    ) -> Result<U256, Vec<u8>> {
+        self.when_not_stopped()?;
+        let _guard = self.reentrancy_guard.guard()?;
+        
+        let required_verification_type = options.requiredVerificationType;
+        let quorum_override = options.quorumOverride;
+        let weight_strategy = WeightStrategy::try_from(options.weightStrategy)?;
+        let execution_delay_override = options.executionDelayOverride;
+        
+        if required_verification_type & !(VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB) != 0 {
+            return Err(b"Invalid verification type".to_vec());
+        }
+        
+        if quorum_override != U256::ZERO && proposer != self.owner.get() {
+            evm::log(Unauthorized { caller: proposer });
+            return Err(b"Only owner can override quorum".to_vec());
+        }
+        check_quorum_override(quorum_override, U256::from(QUORUM_VOTES))?;
+        check_execution_delay_override(execution_delay_override, self.execution_delay.get())?;
+        
+        // Only the owner, or the DAO itself through a passed proposal, may change weighting
+        let governance = proposer == self.owner.get() || proposer == contract::address();
+        if weight_strategy != WeightStrategy::TokenWeighted && !governance {
+            evm::log(Unauthorized { caller: proposer });
+            return Err(b"Only owner or governance can set weight strategy".to_vec());
+        }
+        
+        // STEP 4: DAO checks: if !shadowid.is_verified(user) { revert("KYC required"); }
+        if !self.verify_and_cache(proposer)? {
+            // Emit required event: UserVerificationRequired(address)
+            evm::log(UserVerificationRequired { user: proposer });
             return Err(b"KYC required".to_vec());
         }
+        
+        // Validate ZK proof commitment
+        if !self.validate_zk_proof(proposer, kyc_commitment, proof_hash)? {
+            evm::log(InvalidProof {
+                user: proposer,
+                commitment: FixedBytes::from(kyc_commitment),
+            });
+            return Err(b"Invalid ZK proof or commitment".to_vec());
+        }
+        
+        // Check proposal threshold (governance token balance)
+        // This would integrate with governance token contract in full implementation
+        
+        self.check_proposal_admission(target)?;
+        self.consume_proposal_slot(U256::from(block::number()))?;
+        
+        let proposal_id = self.proposal_count.get();
+        let current_time = U256::from(block::timestamp());
+        let start_time = current_time + self.voting_delay.get();
+        let end_time = start_time + self.voting_period.get();
+        
+        // Create proposal core data with ZK proof integration
+        let core = ProposalCore {
+            id: proposal_id,
+            proposer,
+            title: title.clone(),
+            description: description.clone(),
+            start_time,
+            end_time,
+            for_votes: U256::ZERO,
+            against_votes: U256::ZERO,
+            abstain_votes: U256::ZERO,
+            state: ProposalState::Active,
+            cancelled: false,
+            kyc_commitment,
+            proof_hash,
+            required_verification_type,
+            quorum_override,
+            description_hash,
+            weight_strategy,
+            execution_delay_override,
+            execution_hash: hash_execution(target, value, &data),
+        };
+        
+        // Create execution data
+        let execution = ExecutionData {
+            target,
+            value,
+            data,
+            executed: false,
+            timelock_end: U256::ZERO,
+        };
+        
+        // Store proposal data
+        self.proposal_core.setter(proposal_id).set(core);
+        self.execution_data.setter(proposal_id).set(execution);
+        
+        // Increment counter for next proposal
+        self.proposal_count.set(proposal_id + U256::from(1));
+        
+        // Emit event with ZK proof info
+        evm::log(ProposalCreated {
+            id: proposal_id,
+            proposer,
+            title,
+            startTime: start_time,
+            endTime: end_time,
+            kycCommitment: FixedBytes::from(kyc_commitment),
+        });
+        
+        Ok(proposal_id)
+    }
+
+    /// Zero a user's validated proof and drop their verified flag, returning the cleared commitment
+    fn clear_validated_proof(&mut self, user: Address) -> FixedBytes<32> {
+        let commitment = self.validated_proofs.get(user);
+        self.validated_proofs.setter(user).set(FixedBytes::ZERO);
+        
+        let mut member_data = self.members.getter(user).get();
+        member_data.verified = false;
+        self.members.setter(user).set(member_data);
+        
+        commitment
+    }
+
+    /// Reject state changes while the shared EmergencyStop coordinator is engaged
+    fn when_not_stopped(&self) -> Result<(), Vec<u8>> {
+        let coordinator = self.emergency_stop.get();
+        if coordinator == Address::ZERO {
+            return Ok(());
+        }
+        
+        // Fail closed: an unreachable coordinator blocks state changes
+        let stopped = IEmergencyStop::new(coordinator)
+            .is_stopped(StaticCall::new())
+            .map_err(|_| b"Emergency stop check failed".to_vec())?;
+        if stopped {
+            return Err(b"Emergency stop active".to_vec());
+        }
+        Ok(())
+    }
+
+    /// Pull a member's verification status and commitment from the registry into `members`
+    fn resync_member_from_registry(&mut self, member: Address) -> Result<(), Vec<u8>> {
+        let registry_address = self.shadow_id_registry.get();
+        let registry = IShadowIDRegistry::new(registry_address);
+        
+        let verified = registry
+            .is_verified(StaticCall::new(), member)
+            .map_err(|_| b"Registry isVerified call failed".to_vec())?;
+        let (_, commitment, proof_hash) = registry
+            .get_user(StaticCall::new(), member)
+            .map_err(|_| b"Registry getUser call failed".to_vec())?;
+        
+        let existing = self.members.getter(member).get();
+        let updated = resynced_member(
+            existing,
+            verified,
+            commitment.0,
+            proof_hash.0,
+            U256::from(block::timestamp()),
+        );
+        self.store_member(member, updated);
+        
+        evm::log(MemberResynced { member, registry: registry_address, verified });
+        Ok(())
+    }
+
+    /// Restrict governance parameter updates to the owner
+    fn only_owner_param_update(&self) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can update parameters".to_vec());
+        }
+        Ok(())
+    }
+
+    /// Restrict KYC verifier management to the verifier admin
+    fn only_verifier_admin(&self) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if !is_verifier_admin(caller, self.verifier_admin.get()) {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only verifier admin".to_vec());
+        }
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Emit the full governance parameter set so indexers can rebuild history from one event
+    fn emit_parameters_snapshot(&self) {
+        evm::log(ParametersSnapshot {
+            votingPeriod: self.voting_period.get(),
+            quorumPercent: self.quorum_percent.get(),
+            executionDelay: self.execution_delay.get(),
+            proposalThreshold: self.proposal_threshold.get(),
+            votingDelay: self.voting_delay.get(),
+        });
+    }
+
+    /// STEP 4: DAO checks: if !shadowid.is_verified(user) { revert("KYC required"); }
+    /// Verifies user through ShadowIDRegistry contract integration
+    /// Flow: Backend sends proof_hash to ShadowIDRegistry → DAO checks verification status
+    fn is_user_verified_in_shadowid(&self, user: Address) -> Result<bool, Vec<u8>> {
+        let registry = self.shadow_id_registry.get();
+        
+        // Check if user has valid proof registered (local cache of ShadowIDRegistry state)
+        // This represents: Backend sent proof_hash to ShadowIDRegistry after Aztec ZK proof generation
+        let reason = self.local_verification_reason(user);
+        if !should_consult_registry(reason, self.registry_fallback.get(), registry) {
+            return Ok(reason == VERIFICATION_REASON_VERIFIED);
+        }
+        
+        // Cache miss with the fallback enabled: the registry may have verified the user
+        // before the DAO was synced. Revoked and expired members never reach this point
+        IShadowIDRegistry::new(registry)
+            .is_verified(StaticCall::new(), user)
+            .map_err(|_| b"Registry isVerified call failed".to_vec())
+    }
+
+    /// Classify the cached member record, applying expiry
+    fn local_verification_reason(&self, user: Address) -> u8 {
+        verification_reason(
+            &self.members.get(user),
+            U256::from(block::timestamp()),
+            self.verification_validity.get(),
+        )
+    }
+
+    /// Local verification check against the cached member record, applying expiry
+    fn is_locally_verified(&self, user: Address) -> bool {
+        self.local_verification_reason(user) == VERIFICATION_REASON_VERIFIED
+    }
+
+    /// is_user_verified_in_shadowid for state-changing paths: a positive live registry answer
+    /// is cached by resyncing the member, so the commitment checks that follow see it
+    fn verify_and_cache(&mut self, user: Address) -> Result<bool, Vec<u8>> {
+        if self.is_locally_verified(user) {
+            return Ok(true);
+        }
+        if !self.is_user_verified_in_shadowid(user)? {
+            return Ok(false);
+        }
+        
+        self.resync_member_from_registry(user)?;
+        Ok(self.is_locally_verified(user))
+    }
+
+    /// Validate ZK proof against commitment
+    fn validate_zk_proof(&mut self, user: Address, commitment: [u8; 32], proof_hash: [u8; 32]) -> Result<bool, Vec<u8>> {
+        // Check if commitment is not zero
+        if commitment.iter().all(|&b| b == 0) {
+            return Ok(false);
+        }
+        
+        // Check if proof hash is not zero
+        if proof_hash.iter().all(|&b| b == 0) {
+            return Ok(false);
+        }
+        
+        // The commitment must equal the one stored when the user was verified
+        // (submit_zk_proof / register_verified_user); the proof itself is not re-checked here
+        let mut member_data = self.members.getter(user).get();
+        if !commitment_matches(member_data.kyc_commitment, commitment) {
+            return Ok(false);
+        }
+        
+        // In a full implementation, this would also:
+        // 1. Call ShadowIDRegistry.hasValidProof(user, commitment)
+        // 2. Verify the ZK proof using Noir verification logic
+        
+        // For now, store the validated proof
+        self.validated_proofs.setter(user).set(FixedBytes::from(commitment));
+        
+        // Update member data with latest proof; verification_timestamp is only moved by a
+        // new proof or a registry resync, so acting does not extend the validity window
+        member_data.proof_hash = proof_hash;
+        self.members.setter(user).set(member_data);
+        
+        Ok(true) // Simplified: assume valid if non-zero
+    }
+
+    /// COMPLETE FLOW INTEGRATION: Backend submits proof_hash to ShadowIDRegistry
+    /// Flow: 1. User uploads KYC → 2. generates commitment via Noir → 3. generates ZK proof → Aztec
+    /// → 4. Backend sends proof_hash to ShadowIDRegistry (this function simulates this step)
+    pub fn submit_zk_proof(
+        &mut self,
+        user: Address,
+        kyc_commitment: [u8; 32],     // STEP 1: KYC commitment from Noir circuit
+        proof_hash: [u8; 32],        // STEP 2-3: ZK proof hash from Aztec generation
+    ) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
+        let caller = msg::sender();
+        
+        // Only authorized backend service can submit proofs (simulates backend integration)
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only authorized backend can submit proofs".to_vec());
+        }
+        
+        self.register_zk_proof(user, kyc_commitment, proof_hash, U256::from(block::timestamp()))
+    }
+
+    /// Onboard a backend-sponsored user and create a proposal on their behalf in one
+    /// transaction (owner/backend only). The proof is registered exactly as submit_zk_proof
+    /// does; if proposal creation then fails, the registration reverts with it
+    pub fn submit_proof_and_create_proposal(
+        &mut self,
+        user: Address,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        title: String,
+        description: String,
+        target: Address,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<U256, Vec<u8>> {
+        self.when_not_stopped()?;
+        let caller = msg::sender();
+        
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only authorized backend can submit proofs".to_vec());
+        }
+        
+        // Reject a proposal that would fail anyway before the user is registered
+        self.check_proposal_admission(target)?;
+        self.register_zk_proof(user, kyc_commitment, proof_hash, U256::from(block::timestamp()))?;
+        
+        let description_hash = hash_description(&description);
+        self.create_proposal_internal(
+            user,
+            title,
+            description,
+            description_hash,
+            target,
+            value,
+            data,
+            kyc_commitment,
+            proof_hash,
+            ProposalOptions::standard(),
+        )
+    }
+
+    /// Revoke a user's validated proof after it is found invalid off-chain (owner only)
+    /// The user stays unverified until a new proof is submitted
+    pub fn invalidate_proof(&mut self, user: Address) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can invalidate proofs".to_vec());
+        }
+        
+        let member_data = self.members.get(user);
+        if !member_data.verified && self.validated_proofs.get(user) == FixedBytes::ZERO {
+            return Err(b"No proof to invalidate".to_vec());
+        }
+        
+        let commitment = self.clear_validated_proof(user);
+        
+        evm::log(ProofInvalidated { user, commitment, invalidatedBy: caller });
+        Ok(())
+    }
+
+    /// Register verified user (called after ShadowIDRegistry verification)
+    /// This is the callback from ShadowIDRegistry after proof verification
+    pub fn register_verified_user(
+        &mut self,
+        user: Address,
+        kyc_commitment: [u8; 32],
+        proof_hash: [u8; 32],
+        verification_type: u8,
+    ) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
+        let caller = msg::sender();
+        
+        // Only ShadowIDRegistry or owner can register verified users
+        if caller != self.shadow_id_registry.get() && caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only ShadowIDRegistry or owner can register verified users".to_vec());
+        }
+        
+        let member_data = MemberData {
+            is_member: true,
+            verified: true,
+            kyc_commitment,
+            proof_hash,
+            verification_timestamp: U256::from(block::timestamp()),
+            verification_type,
+        };
+        
+        self.store_member(user, member_data);
+        self.validated_proofs.setter(user).set(FixedBytes::from(kyc_commitment));
+        
+        evm::log(ZKProofValidated {
+            user,
+            commitment: FixedBytes::from(kyc_commitment),
+            proofHash: FixedBytes::from(proof_hash),
+        });
+        
+        Ok(())
+    }
+
+    /// Get user verification status and proof data
+    pub fn get_user_verification(&self, user: Address) -> (bool, [u8; 32], [u8; 32], u8, U256) {
+        let member_data = self.members.get(user);
+        (
+            member_data.verified,
+            member_data.kyc_commitment,
+            member_data.proof_hash,
+            member_data.verification_type,
+            member_data.verification_timestamp,
+        )
+    }
+
+    /// Get the raw KYC commitment stored in a member's record
+    pub fn member_commitment(&self, user: Address) -> [u8; 32] {
+        self.members.get(user).kyc_commitment
+    }
+
+    /// Get the raw proof hash stored in a member's record
+    pub fn member_proof_hash(&self, user: Address) -> [u8; 32] {
+        self.members.get(user).proof_hash
+    }
+
+    /// Get the commitment from `validated_proofs`, which can diverge from `members`
+    /// after partial updates (e.g. submit_kyc_proof or a registry resync)
+    pub fn validated_commitment(&self, user: Address) -> FixedBytes<32> {
+        self.validated_proofs.get(user)
+    }
+
+    /// Get vote record for user on specific proposal
+    /// Returns (has_voted, choice, weight, proof_hash, timestamp, raw_weight); raw_weight above
+    /// weight means the voter was capped
+    pub fn get_vote_record(
+        &self,
+        proposal_id: U256,
+        user: Address,
+    ) -> (bool, u8, U256, [u8; 32], U256, U256) {
+        let vote_record = self.user_votes.get((proposal_id, user));
+        (
+            vote_record.has_voted,
+            vote_record.choice,
+            vote_record.weight,
+            vote_record.proof_hash,
+            vote_record.timestamp,
+            vote_record.raw_weight,
+        )
+    }
+
+    /// Get a page of voters for a proposal in vote order
+    pub fn get_proposal_voters(&self, proposal_id: U256, offset: U256, limit: U256) -> Vec<Address> {
+        let voters = self.proposal_voters.get(proposal_id);
+        let (start, end) = page_bounds(voters.len(), offset, limit);
+        
+        let mut result = Vec::new();
+        for i in start..end {
+            if let Some(voter) = voters.get(i) {
+                result.push(voter);
+            }
+        }
+        result
+    }
+
+    /// Get vote records parallel to `get_proposal_voters` for the same page
+    pub fn get_proposal_vote_records(
+        &self,
+        proposal_id: U256,
+        offset: U256,
+        limit: U256,
+    ) -> Vec<(bool, u8, U256, [u8; 32], U256, U256)> {
+        self.get_proposal_voters(proposal_id, offset, limit)
+            .into_iter()
+            .map(|voter| self.get_vote_record(proposal_id, voter))
+            .collect()
+    }
+
+    /// Get live quorum progress for a proposal: (current participation, required, reached)
+    /// `required` is 0 until the proposal's vote snapshot has been taken
+    pub fn quorum_progress(&self, proposal_id: U256) -> Result<(U256, U256, bool), Vec<u8>> {
+        if proposal_id == U256::ZERO || proposal_id >= self.proposal_count.get() {
+            return Err(b"Proposal does not exist".to_vec());
+        }
+        
+        let core = self.proposal_core.getter(proposal_id).get();
+        let required = match core.state {
+            ProposalState::Passed | ProposalState::Rejected | ProposalState::Executed => {
+                core.quorum_votes
+            }
+            _ if vote_snapshot(core.start_time) >= U256::from(block::timestamp()) => U256::ZERO,
+            _ => required_quorum(core.quorum_override, self.default_quorum(&core)?),
+        };
+        Ok(quorum_progress_of(core.for_votes + core.against_votes + core.abstain_votes, required))
+    }
+
+    /// Seconds left in a proposal's current phase: (PHASE_* code, secondsLeft)
+    pub fn time_remaining(&self, proposal_id: U256) -> Result<(u8, U256), Vec<u8>> {
+        if proposal_id == U256::ZERO || proposal_id >= self.proposal_count.get() {
+            return Err(b"Proposal does not exist".to_vec());
+        }
+        
+        let core = self.proposal_core.get(proposal_id);
+        let execution = self.execution_data.get(proposal_id);
+        Ok(phase_time_remaining(
+            U256::from(block::timestamp()),
+            core.state,
+            core.start_time,
+            core.end_time,
+            execution.timelock_end,
+        ))
+    }
+
+    /// Get the weight `vote` would credit `user` on a proposal, without voting. Before the
+    /// vote snapshot is taken this projects from the user's current delegated votes
+    pub fn effective_weight(&self, proposal_id: U256, user: Address) -> Result<U256, Vec<u8>> {
+        if proposal_id == U256::ZERO || proposal_id >= self.proposal_count.get() {
+            return Err(b"Proposal does not exist".to_vec());
+        }
+        
+        let core = self.proposal_core.getter(proposal_id).get();
+        let votes = self.projected_votes_at_snapshot(core.start_time, user)?;
+        let (_, weight) = self.weigh_votes(core.weight_strategy, votes);
+        Ok(weight)
+    }
+
+    /// Get `voter`'s delegated token votes at a proposal's snapshot, whether or not they voted.
+    /// Before the snapshot is taken this is their current votes, which it would record unchanged.
+    /// Unlike effective_weight this is before the weight strategy and min/max bounds apply
+    pub fn delegated_weight_at_snapshot(
+        &self,
+        proposal_id: U256,
+        voter: Address,
+    ) -> Result<U256, Vec<u8>> {
+        if proposal_id == U256::ZERO || proposal_id >= self.proposal_count.get() {
+            return Err(b"Proposal does not exist".to_vec());
+        }
+        
+        let start_time = self.proposal_core.getter(proposal_id).get().start_time;
+        self.projected_votes_at_snapshot(start_time, voter)
+    }
+
+    /// Recompute a settled proposal's outcome from its stored tallies and quorum, and check
+    /// that both the stored state and `claimed_state` agree with it (Executed counts as Passed).
+    /// Active and cancelled proposals have no outcome to verify and return false
+    pub fn verify_outcome(&self, proposal_id: U256, claimed_state: u8) -> Result<bool, Vec<u8>> {
+        if proposal_id == U256::ZERO || proposal_id >= self.proposal_count.get() {
+            return Err(b"Proposal does not exist".to_vec());
+        }
+        
+        let core = self.proposal_core.get(proposal_id);
+        let expected = tally_outcome(&core);
+        Ok(outcome_matches(core.state.into(), expected) && outcome_matches(claimed_state, expected))
+    }
+
+    /// Get number of voters on a proposal
+    pub fn get_proposal_voter_count(&self, proposal_id: U256) -> U256 {
+        U256::from(self.proposal_voters.get(proposal_id).len())
+    }
+
+    /// Check if user is verified in ShadowIDRegistry (public view function)
+    /// This implements the check: shadowid.is_verified(user)
+    pub fn is_user_verified(&self, user: Address) -> bool {
+        match self.is_user_verified_in_shadowid(user) {
+            Ok(verified) => verified,
+            Err(_) => false,
+        }
+    }
+
+    /// Check many addresses at once; results are parallel to `users`, each decided exactly
+    /// as is_user_verified would, registry fallback included
+    pub fn are_verified(&self, users: Vec<Address>) -> Result<Vec<bool>, Vec<u8>> {
+        if users.len() > MAX_VERIFICATION_BATCH {
+            return Err(b"Too many addresses".to_vec());
+        }
+        
+        Ok(users.into_iter().map(|user| self.is_user_verified(user)).collect())
+    }
+
+    /// Get verification status with a reason code for the frontend
+    /// Reasons: 0 verified, 1 not a member, 2 no commitment/proof, 3 expired,
+    /// 4 registry error, 5 submitted but not yet verified
+    pub fn verification_status(&self, user: Address) -> (bool, u8) {
+        if let Err(_) = self.is_user_verified_in_shadowid(user) {
+            return (false, VERIFICATION_REASON_REGISTRY_ERROR);
+        }
+        
+        let reason = verification_reason(
+            &self.members.get(user),
+            U256::from(block::timestamp()),
+            self.verification_validity.get(),
+        );
+        (reason == VERIFICATION_REASON_VERIFIED, reason)
+    }
+
+    /// Get how long a verification stays valid (0 = no expiry)
+    pub fn verification_validity(&self) -> U256 {
+        self.verification_validity.get()
+    }
+
+    /// Set how long a verification stays valid, 0 disables expiry (owner only)
+    pub fn set_verification_validity(&mut self, validity: U256) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.owner.get() {
+            evm::log(Unauthorized { caller });
+            return Err(b"Only owner can update verification validity".to_vec());
+        }
+        
+        let old_validity = self.verification_validity.get();
+        self.verification_validity.set(validity);
+        
+        evm::log(VerificationValidityUpdated {
+            oldValidity: old_validity,
+            newValidity: validity,
+        });
+        
+        Ok(())
+    }
+
+    /// Get KYC flow status for frontend integration
+    /// Returns: (needs_kyc_upload, needs_zk_proof, is_verified, proof_submitted)
+    pub fn get_kyc_status(&self, user: Address) -> (bool, bool, bool, bool) {
+        let member_data = self.members.get(user);
+        
+        let has_commitment = !member_data.kyc_commitment.iter().all(|&b| b == 0);
+        let has_proof = !member_data.proof_hash.iter().all(|&b| b == 0);
+        
+        let needs_kyc_upload = !has_commitment;
+        let needs_zk_proof = has_commitment && !has_proof;
+        let is_verified = member_data.verified;
+        let proof_submitted = has_proof;
+        
+        (needs_kyc_upload, needs_zk_proof, is_verified, proof_submitted)
+    }
+
+    /// Trigger KYC requirement check (helper function for frontend)
+    pub fn require_verification(&self, user: Address) -> Result<(), Vec<u8>> {
+        if !self.is_user_verified(user) {
+            evm::log(UserVerificationRequired { user });
+            return Err(b"KYC required".to_vec());
+        }
+        Ok(())
+    }
+}
+
+// Crate-internal hooks for contracts that wrap the DAO (ZkEnhancedDAO)
+impl DAO {
+    /// Check a private vote could be counted now, under the same existence, state and
+    /// voting-window rules as `vote`
+    pub(crate) fn check_private_vote(&self, proposal_id: U256) -> Result<ProposalCore, Vec<u8>> {
+        self.when_not_stopped()?;
+        if proposal_id == U256::ZERO || proposal_id >= self.proposal_count.get() {
+            return Err(b"Proposal does not exist".to_vec());
+        }
+        
+        let core = self.proposal_core.getter(proposal_id).get();
+        if core.state != ProposalState::Active {
+            return Err(b"Proposal is not active".to_vec());
+        }
+        check_voting_window(U256::from(block::timestamp()), core.start_time, core.end_time)?;
+        Ok(core)
+    }
+}
+
+// Payable fallback: stray ETH reverts instead of being locked (rescue_eth recovers force-sends)
+#[payable]
+impl DAO {
+    fn fallback(&mut self) -> Result<(), Vec<u8>> {
+        check_incoming_eth(self.accepting_eth.get(), msg::value())
+    }
+}
+
+// =============================================================================
+// INTERNAL HELPERS
+// =============================================================================
+
+/// ETH is only accepted while a proposal is executing
+fn check_incoming_eth(accepting_eth: bool, value: U256) -> Result<(), Vec<u8>> {
+    if value > U256::ZERO && !accepting_eth {
+        return Err(b"DAO does not accept ETH".to_vec());
+    }
+    Ok(())
+}
+
+/// Check that a verifier's type mask covers a member's verification type
+/// (members without a declared type are treated as plain KYC)
+fn verifier_can_verify(allowed_types: u8, verification_type: u8) -> bool {
+    let required = if verification_type == 0 {
+        VERIFICATION_TYPE_KYC
+    } else {
+        verification_type
+    };
+    allowed_types & required == required
+}
+
+/// Whether `caller` holds the KYC verifier admin role (an unset role matches nobody)
+fn is_verifier_admin(caller: Address, verifier_admin: Address) -> bool {
+    verifier_admin != Address::ZERO && caller == verifier_admin
+}
+
+/// The verifier admin role can be reassigned by its holder or by the DAO owner
+fn can_set_verifier_admin(caller: Address, verifier_admin: Address, owner: Address) -> bool {
+    is_verifier_admin(caller, verifier_admin) || (owner != Address::ZERO && caller == owner)
+}
+
+/// Passed proposals may be executed by an allowlisted executor or a verified member
+fn executor_authorized(allowlisted: bool, verified_member: bool) -> bool {
+    allowlisted || verified_member
+}
+
+/// Check a member's verification type against a proposal's requirement
+fn satisfies_verification_type(member_type: u8, required_type: u8) -> bool {
+    member_type & required_type == required_type
+}
+
+/// Classify a member's verification state into a verification_status reason code
+fn verification_reason(member_data: &MemberData, now: U256, validity: U256) -> u8 {
+    let has_commitment = !member_data.kyc_commitment.iter().all(|&b| b == 0);
+    let has_proof = !member_data.proof_hash.iter().all(|&b| b == 0);
+    
+    if !member_data.is_member && !member_data.verified {
+        return VERIFICATION_REASON_NOT_MEMBER;
+    }
+    if !has_commitment || !has_proof {
+        return VERIFICATION_REASON_NO_COMMITMENT;
+    }
+    if !member_data.verified {
+        return VERIFICATION_REASON_NOT_VERIFIED;
+    }
+    if validity > U256::ZERO && now > member_data.verification_timestamp + validity {
+        return VERIFICATION_REASON_EXPIRED;
+    }
+    VERIFICATION_REASON_VERIFIED
+}
+
+/// Check a submitted commitment against the one bound to the user at verification
+fn commitment_matches(registered: [u8; 32], submitted: [u8; 32]) -> bool {
+    !registered.iter().all(|&b| b == 0) && registered == submitted
+}
+
+/// Compare a cached commitment with the registry's (None = registry call failed)
+fn commitment_is_current(local: [u8; 32], registry: Option<[u8; 32]>) -> bool {
+    match registry {
+        Some(registered) => commitment_matches(registered, local),
+        None => false,
+    }
+}
+
+/// Add a vote's weight to the tally for its choice (0: For, 1: Against, 2: Abstain)
+fn tally_vote(core: &mut ProposalCore, choice: u8, weight: U256) -> Result<(), Vec<u8>> {
+    match choice {
+        0 => core.for_votes += weight,
+        1 => core.against_votes += weight,
+        2 => core.abstain_votes += weight,
+        _ => return Err(b"Invalid vote choice (must be 0, 1, or 2)".to_vec()),
+    }
+    Ok(())
+}
+
+/// Clamp a voter's weight into the configured bounds (0 = bound unset). The floor lifts small
+/// holders only: a voter with no weight at the snapshot stays at zero
+fn clamp_vote_weight(weight: U256, min_weight: U256, max_weight: U256) -> U256 {
+    let mut clamped = weight;
+    if min_weight > U256::ZERO && clamped > U256::ZERO && clamped < min_weight {
+        clamped = min_weight;
+    }
+    if max_weight > U256::ZERO && clamped > max_weight {
+        clamped = max_weight;
+    }
+    clamped
+}
+
+/// Voting weight a strategy assigns to a voter holding `token_votes`
+fn strategy_weight(strategy: WeightStrategy, token_votes: U256) -> U256 {
+    match strategy {
+        WeightStrategy::TokenWeighted => token_votes,
+        WeightStrategy::OnePerson => U256::from(1),
+        WeightStrategy::Quadratic => u256_sqrt(token_votes),
+    }
+}
+
+/// Rebuild a member record from registry state. An unchanged, still-verified commitment keeps
+/// its original timestamp so resyncing does not extend the verification validity period
+fn resynced_member(
+    existing: MemberData,
+    registry_verified: bool,
+    commitment: [u8; 32],
+    proof_hash: [u8; 32],
+    now: U256,
+) -> MemberData {
+    let unchanged = existing.verified && registry_verified && existing.kyc_commitment == commitment;
+    MemberData {
+        is_member: existing.is_member || registry_verified,
+        verified: registry_verified,
+        kyc_commitment: commitment,
+        proof_hash,
+        verification_timestamp: if unchanged {
+            existing.verification_timestamp
+        } else if registry_verified {
+            now
+        } else {
+            U256::ZERO
+        },
+        verification_type: existing.verification_type,
+    }
+}
+
+/// The registry is asked live only for users the DAO has no proof for, when the fallback is
+/// on and one is linked; a local revocation or expiry is final until the owner resyncs
+fn should_consult_registry(
+    local_reason: u8,
+    fallback_enabled: bool,
+    registry: Address,
+) -> bool {
+    let unsynced = local_reason == VERIFICATION_REASON_NOT_MEMBER
+        || local_reason == VERIFICATION_REASON_NO_COMMITMENT;
+    unsynced && fallback_enabled && registry != Address::ZERO
+}
+
+/// A vote arriving after `end_time` finalizes the proposal when auto-finalize is enabled
+fn should_auto_finalize(enabled: bool, now: U256, end_time: U256) -> bool {
+    enabled && now > end_time
+}
+
+/// Votes are accepted from `start_time` (creation + voting delay) through `end_time`
+fn check_voting_window(now: U256, start_time: U256, end_time: U256) -> Result<(), Vec<u8>> {
+    if now < start_time {
+        return Err(b"Voting not started".to_vec());
+    }
+    if now > end_time {
+        return Err(b"Voting period has ended".to_vec());
+    }
+    Ok(())
+}
+
+/// A passed proposal executes once its timelock has run out
+fn check_execution_window(now: U256, timelock_end: U256) -> Result<(), Vec<u8>> {
+    if now < timelock_end {
+        return Err(b"Timelock period not expired".to_vec());
+    }
+    Ok(())
+}
+
+/// Current phase and seconds until it ends (zero once elapsed). Active proposals are pending
+/// through the voting delay, then voting until finalized; passed ones wait out the timelock
+fn phase_time_remaining(
+    now: U256,
+    state: ProposalState,
+    start_time: U256,
+    end_time: U256,
+    timelock_end: U256,
+) -> (u8, U256) {
+    match state {
+        ProposalState::Active if now < start_time => (PHASE_PENDING, start_time - now),
+        ProposalState::Active => (PHASE_VOTING, end_time.saturating_sub(now)),
+        ProposalState::Passed if now < timelock_end => (PHASE_TIMELOCK, timelock_end - now),
+        _ => (PHASE_NONE, U256::ZERO),
+    }
+}
+
+/// Timepoint at which voting weight is read: the last second before voting opens, so a
+/// vote cast at `start_time` looks strictly into the past as getPastVotes requires
+fn vote_snapshot(start_time: U256) -> U256 {
+    start_time.saturating_sub(U256::from(1))
+}
+
+/// Hash a proposal description as stored in ProposalCore::description_hash
+fn hash_description(description: &str) -> [u8; 32] {
+    crypto::keccak(description.as_bytes()).0
+}
+
+/// Hash a proposal's call as stored in ProposalCore::execution_hash: target ++ value ++ data
+fn hash_execution(target: Address, value: U256, data: &[u8]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(20 + 32 + data.len());
+    preimage.extend_from_slice(target.as_slice());
+    preimage.extend_from_slice(&value.to_be_bytes::<32>());
+    preimage.extend_from_slice(data);
+    crypto::keccak(&preimage).0
+}
+
+/// Reject execution data that no longer matches the call voters approved
+fn check_execution_hash(stored_hash: [u8; 32], execution: &ExecutionData) -> Result<(), Vec<u8>> {
+    if hash_execution(execution.target, execution.value, &execution.data) != stored_hash {
+        return Err(b"Execution data mismatch".to_vec());
+    }
+    Ok(())
+}
+
+/// An import batch needs parallel arrays within MAX_IMPORT_BATCH, non-zero members and
+/// commitments, and a KYC and/or KYB verification type for each member
+fn check_member_import(
+    users: &[Address],
+    commitments: &[[u8; 32]],
+    proof_hashes: &[[u8; 32]],
+    verification_types: &[u8],
+    timestamps: &[U256],
+    now: U256,
+) -> Result<(), Vec<u8>> {
+    let len = users.len();
+    if [commitments.len(), proof_hashes.len(), verification_types.len(), timestamps.len()]
+        .iter()
+        .any(|&n| n != len)
+    {
+        return Err(b"Array length mismatch".to_vec());
+    }
+    if len > MAX_IMPORT_BATCH {
+        return Err(b"Batch too large".to_vec());
+    }
+    if users.contains(&Address::ZERO) {
+        return Err(b"Invalid member".to_vec());
+    }
+    if users.iter().enumerate().any(|(i, user)| users[..i].contains(user)) {
+        return Err(b"Duplicate member".to_vec());
+    }
+    if commitments.iter().any(|c| c.iter().all(|&b| b == 0)) {
+        return Err(b"Invalid commitment".to_vec());
+    }
+    let all_types = VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB;
+    if verification_types.iter().any(|&t| t == 0 || t & !all_types != 0) {
+        return Err(b"Invalid verification type".to_vec());
+    }
+    // A future timestamp would stretch the verification validity window
+    if timestamps.iter().any(|&t| t > now) {
+        return Err(b"Verification timestamp in the future".to_vec());
+    }
+    Ok(())
+}
+
+/// Reject a supplied description that does not match the proposal's stored hash
+fn check_description(stored_hash: [u8; 32], description: &str) -> Result<(), Vec<u8>> {
+    if hash_description(description) != stored_hash {
+        return Err(b"Description hash mismatch".to_vec());
+    }
+    Ok(())
+}
+
+/// A batch of allowed targets needs one flag per target and no zero address
+fn check_allowed_targets(targets: &[Address], allowed: &[bool]) -> Result<(), Vec<u8>> {
+    if targets.len() != allowed.len() {
+        return Err(b"Array length mismatch".to_vec());
+    }
+    if targets.contains(&Address::ZERO) {
+        return Err(b"Invalid target".to_vec());
+    }
+    Ok(())
+}
+
+/// Votes a proposal needs: its override when that is higher, otherwise the DAO default
+fn required_quorum(quorum_override: U256, default_quorum: U256) -> U256 {
+    if quorum_override > default_quorum {
+        quorum_override
+    } else {
+        default_quorum
+    }
+}
+
+/// `quorum_percent` of the eligible weight, rounded up so the quorum is never undercut
+fn percent_quorum(eligible: U256, quorum_percent: U256) -> U256 {
+    let hundred = U256::from(100);
+    (eligible.saturating_mul(quorum_percent) + hundred - U256::from(1)) / hundred
+}
+
+/// A quorum override may only raise the bar, never weaken it
+fn check_quorum_override(quorum_override: U256, default_quorum: U256) -> Result<(), Vec<u8>> {
+    if quorum_override != U256::ZERO && quorum_override < default_quorum {
+        return Err(b"Quorum override below default".to_vec());
+    }
+    Ok(())
+}
+
+/// Timelock a passed proposal waits: its override when longer, otherwise the global delay.
+/// Taking the larger keeps an override from undercutting a global delay raised later
+fn required_execution_delay(delay_override: U256, global_delay: U256) -> U256 {
+    if delay_override > global_delay {
+        delay_override
+    } else {
+        global_delay
+    }
+}
+
+/// An execution delay override may only lengthen the timelock, never shorten it, and is
+/// capped at MAX_EXECUTION_DELAY
+fn check_execution_delay_override(delay_override: U256, global_delay: U256) -> Result<(), Vec<u8>> {
+    if delay_override != U256::ZERO && delay_override < global_delay {
+        return Err(b"Execution delay override below global delay".to_vec());
+    }
+    if delay_override > U256::from(MAX_EXECUTION_DELAY) {
+        return Err(b"Execution delay override too long".to_vec());
+    }
+    Ok(())
+}
+
+/// Outcome finalization assigns from the tallies: Passed with the recorded quorum and a
+/// For majority, otherwise Rejected
+fn tally_outcome(core: &ProposalCore) -> ProposalState {
+    let (_, _, quorum_reached) = quorum_progress_of(
+        core.for_votes + core.against_votes + core.abstain_votes,
+        core.quorum_votes,
+    );
+    
+    if quorum_reached && core.for_votes > core.against_votes {
+        ProposalState::Passed
+    } else {
+        ProposalState::Rejected
+    }
+}
+
+/// Whether a state code reflects `outcome`; an executed proposal had passed
+fn outcome_matches(state_code: u8, outcome: ProposalState) -> bool {
+    state_code == u8::from(outcome)
+        || (outcome == ProposalState::Passed && state_code == u8::from(ProposalState::Executed))
+}
+
+/// Compare participation against the required quorum
+fn quorum_progress_of(current: U256, required: U256) -> (U256, U256, bool) {
+    (current, required, current >= required)
+}
+
+/// Resolve an (offset, limit) page into index bounds clamped to `len`
+fn page_bounds(len: usize, offset: U256, limit: U256) -> (usize, usize) {
+    let start = if offset >= U256::from(len) { len } else { offset.to::<usize>() };
+    let remaining = len - start;
+    let count = if limit >= U256::from(remaining) { remaining } else { limit.to::<usize>() };
+    (start, start + count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::testing::TestVM;
+
+    fn owner() -> Address { Address::repeat_byte(0x01) }
+    fn token() -> Address { Address::repeat_byte(0x02) }
+    fn treasury() -> Address { Address::repeat_byte(0x03) }
+    fn registry() -> Address { Address::repeat_byte(0x04) }
+
+    /// A DAO deployed by owner() at t=1_000: 1h voting, 20% quorum, 1 day timelock
+    fn setup_dao() -> (TestVM, DAO) {
+        let vm = TestVM::default();
+        vm.set_sender(owner());
+        vm.set_block_timestamp(1_000);
+        vm.set_block_number(1);
+        let mut dao = DAO::from(&vm);
+        dao.constructor(
+            token(),
+            treasury(),
+            registry(),
+            U256::from(3_600),
+            U256::from(20),
+            U256::from(86_400),
+            U256::ZERO,
+        ).unwrap();
+        (vm, dao)
+    }
+
+    /// Per-user KYC commitment and proof hash
+    fn zk_of(user: Address) -> ([u8; 32], [u8; 32]) {
+        let tag = user.0[19];
+        ([tag; 32], [tag ^ 0xff; 32])
+    }
+
+    /// Register `user` through the backend (owner) proof submission
+    fn verify_member(vm: &TestVM, dao: &mut DAO, user: Address) {
+        let (commitment, proof_hash) = zk_of(user);
+        vm.set_sender(owner());
+        dao.submit_zk_proof(user, commitment, proof_hash).unwrap();
+    }
+
+    mod token_abi {
+        stylus_sdk::alloy_sol_types::sol! {
+            function delegates(address account) external view returns (address);
+            function activateSelfDelegation(address account) external;
+            function getVotes(address account) external view returns (uint256);
+            function getPastVotes(address account, uint256 timepoint) external view returns (uint256);
+            function getPastTotalSupply(uint256 timepoint) external view returns (uint256);
+        }
+    }
+
+    /// Answer the governance token's getPastVotes(voter, timepoint)
+    fn mock_past_votes(vm: &TestVM, voter: Address, timepoint: U256, votes: U256) {
+        let call = token_abi::getPastVotesCall { account: voter, timepoint };
+        let votes = token_abi::getPastVotesCall::abi_encode_returns(&(votes,));
+        vm.mock_static_call(token(), call.abi_encode(), Ok(votes));
+    }
+
+    /// Answer the governance token's getVotes(voter) with its current votes
+    fn mock_current_votes(vm: &TestVM, voter: Address, votes: U256) {
+        let call = token_abi::getVotesCall { account: voter };
+        let votes = token_abi::getVotesCall::abi_encode_returns(&(votes,));
+        vm.mock_static_call(token(), call.abi_encode(), Ok(votes));
+    }
+
+    /// Answer the governance token's getPastTotalSupply(timepoint)
+    fn mock_past_supply(vm: &TestVM, timepoint: U256, supply: U256) {
+        let call = token_abi::getPastTotalSupplyCall { timepoint };
+        let supply = token_abi::getPastTotalSupplyCall::abi_encode_returns(&(supply,));
+        vm.mock_static_call(token(), call.abi_encode(), Ok(supply));
+    }
+
+    /// `voter` votes `choice` on `proposal_id` holding `votes` at its snapshot
+    fn vote_with(
+        vm: &TestVM,
+        dao: &mut DAO,
+        voter: Address,
+        proposal_id: U256,
+        choice: u8,
+        votes: u64,
+    ) -> Result<(), Vec<u8>> {
+        let snapshot = vote_snapshot(dao.proposal_core.get(proposal_id).start_time);
+        mock_past_votes(vm, voter, snapshot, U256::from(votes));
+        let (commitment, proof_hash) = zk_of(voter);
+        vm.set_sender(voter);
+        dao.vote(proposal_id, choice, commitment, proof_hash)
+    }
+
+    /// Carry an open proposal to Passed: `voter` casts all 100 votes of a 100 token supply
+    /// and the voting period runs out
+    fn pass_proposal(vm: &TestVM, dao: &mut DAO, voter: Address, proposal_id: U256) {
+        let core = dao.proposal_core.get(proposal_id);
+        vm.set_block_timestamp(core.start_time.to::<u64>());
+        vote_with(vm, dao, voter, proposal_id, 0, 100).unwrap();
+        mock_past_supply(vm, vote_snapshot(core.start_time), U256::from(100));
+        vm.set_block_timestamp(core.end_time.to::<u64>() + 1);
+        dao.finalize_proposal(proposal_id).unwrap();
+        assert!(dao.proposal_core.get(proposal_id).state == ProposalState::Passed);
+    }
+
+    /// `proposer` opens a plain treasury proposal
+    fn propose(vm: &TestVM, dao: &mut DAO, proposer: Address) -> U256 {
+        let (commitment, proof_hash) = zk_of(proposer);
+        vm.set_sender(proposer);
+        dao.create_proposal(
+            "Title".into(),
+            "Description".into(),
+            treasury(),
+            U256::ZERO,
+            Vec::new(),
+            commitment,
+            proof_hash,
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_kyc_only_verifier_cannot_verify_kyb_member() {
+        assert!(verifier_can_verify(VERIFICATION_TYPE_KYC, VERIFICATION_TYPE_KYC));
+        assert!(!verifier_can_verify(VERIFICATION_TYPE_KYC, VERIFICATION_TYPE_KYB));
+        assert!(!verifier_can_verify(VERIFICATION_TYPE_KYC, VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB));
+    }
+
+    #[test]
+    fn test_verifier_admin_role() {
+        let (vm, mut dao) = setup_dao();
+        let compliance = Address::repeat_byte(0x0c);
+        let random = Address::repeat_byte(0x0d);
+        let verifier = Address::repeat_byte(0x0e);
+        
+        // Initialized to the owner
+        assert_eq!(dao.verifier_admin(), owner());
+        
+        vm.set_sender(random);
+        assert_eq!(dao.set_verifier_admin(random), Err(b"Only verifier admin or owner".to_vec()));
+        vm.set_sender(owner());
+        assert_eq!(dao.set_verifier_admin(Address::ZERO), Err(b"Invalid verifier admin".to_vec()));
+        dao.set_verifier_admin(compliance).unwrap();
+        assert_eq!(dao.verifier_admin(), compliance);
+        
+        // Only compliance manages verifiers now; the owner and a random account cannot
+        for caller in [owner(), random] {
+            vm.set_sender(caller);
+            assert_eq!(
+                dao.add_verifier(verifier, VERIFICATION_TYPE_KYC),
+                Err(b"Only verifier admin".to_vec())
+            );
+        }
+        assert_eq!(dao.get_verifier_types(verifier), 0);
+        vm.set_sender(compliance);
+        dao.add_verifier(verifier, VERIFICATION_TYPE_KYC).unwrap();
+        assert_eq!(dao.get_verifier_types(verifier), VERIFICATION_TYPE_KYC);
+        
+        // The owner can still reassign the role
+        vm.set_sender(owner());
+        dao.set_verifier_admin(owner()).unwrap();
+        assert_eq!(dao.verifier_admin(), owner());
+    }
+
+    #[test]
+    fn test_executor_allowlist() {
+        let (vm, mut dao) = setup_dao();
+        let keeper = Address::repeat_byte(0x0e);
+        let random = Address::repeat_byte(0x0f);
+        verify_member(&vm, &mut dao, owner());
+        let proposal_id = propose(&vm, &mut dao, owner());
+        pass_proposal(&vm, &mut dao, owner(), proposal_id);
+        let timelock_end = dao.execution_data.get(proposal_id).timelock_end;
+        vm.set_block_timestamp(timelock_end.to::<u64>());
+        vm.mock_call(treasury(), Vec::new(), U256::ZERO, Ok(Vec::new()));
+        
+        vm.set_sender(keeper);
+        assert_eq!(
+            dao.set_executor(keeper, true),
+            Err(b"Only owner can manage executors".to_vec())
+        );
+        vm.set_sender(owner());
+        dao.set_executor(keeper, true).unwrap();
+        
+        // A random non-member is turned away, whatever proof they send
+        let (commitment, proof_hash) = zk_of(random);
+        vm.set_sender(random);
+        assert_eq!(
+            dao.execute_proposal(proposal_id, commitment, proof_hash),
+            Err(b"KYC required".to_vec())
+        );
+        assert!(dao.proposal_core.get(proposal_id).state == ProposalState::Passed);
+        
+        // The allowlisted keeper executes without membership or a proof
+        assert!(!dao.is_verified_member(keeper));
+        vm.set_sender(keeper);
+        dao.execute_proposal(proposal_id, [0u8; 32], [0u8; 32]).unwrap();
+        assert!(dao.proposal_core.get(proposal_id).state == ProposalState::Executed);
+        assert!(dao.execution_data.get(proposal_id).executed);
+    }
+
+    #[test]
+    fn test_verifier_type_masks() {
+        let both = VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB;
+        assert!(verifier_can_verify(VERIFICATION_TYPE_KYB, VERIFICATION_TYPE_KYB));
+        assert!(verifier_can_verify(both, VERIFICATION_TYPE_KYB));
+        assert!(verifier_can_verify(both, both));
+        
+        // Members without a declared type need the KYC bit
+        assert!(verifier_can_verify(VERIFICATION_TYPE_KYC, 0));
+        assert!(!verifier_can_verify(VERIFICATION_TYPE_KYB, 0));
+        assert!(!verifier_can_verify(0, VERIFICATION_TYPE_KYC));
+    }
+
+    #[test]
+    fn test_vote_weight_clamping() {
+        let min = U256::from(10);
+        let max = U256::from(1_000);
+        
+        // Large holder is capped, tiny holder is floored
+        assert_eq!(clamp_vote_weight(U256::from(50_000), min, max), max);
+        assert_eq!(clamp_vote_weight(U256::from(1), min, max), min);
+        assert_eq!(clamp_vote_weight(U256::from(500), min, max), U256::from(500));
+        
+        // No weight is not lifted to the floor
+        assert_eq!(clamp_vote_weight(U256::ZERO, min, max), U256::ZERO);
+        
+        // Unset bounds leave weight untouched
+        assert_eq!(clamp_vote_weight(U256::from(50_000), U256::ZERO, U256::ZERO), U256::from(50_000));
+        assert_eq!(clamp_vote_weight(U256::from(1), U256::ZERO, max), U256::from(1));
+        
+        // Through vote: a small holder is floored, a voter without delegated votes counts nothing
+        let (vm, mut dao) = setup_dao();
+        let (small, empty) = (Address::repeat_byte(0x6a), Address::repeat_byte(0x6b));
+        verify_member(&vm, &mut dao, small);
+        verify_member(&vm, &mut dao, empty);
+        dao.set_vote_weight_bounds(min, max).unwrap();
+        let proposal_id = propose(&vm, &mut dao, small);
+        vote_with(&vm, &mut dao, small, proposal_id, 0, 1).unwrap();
+        vote_with(&vm, &mut dao, empty, proposal_id, 0, 0).unwrap();
+        assert_eq!(dao.get_vote_record(proposal_id, small).2, min);
+        assert_eq!(dao.get_vote_record(proposal_id, empty).2, U256::ZERO);
+        assert_eq!(dao.proposal_core.get(proposal_id).for_votes, min);
+    }
+
+    #[test]
+    fn test_capped_whale_keeps_raw_weight() {
+        let mut dao = DAO::default();
+        let proposal_id = U256::from(1);
+        let whale = Address::repeat_byte(0x77);
+        
+        let raw_weight = U256::from(1_000_000);
+        let weight = clamp_vote_weight(raw_weight, U256::ZERO, U256::from(10_000));
+        dao.user_votes.setter((proposal_id, whale)).set(VoteRecord {
+            has_voted: true,
+            choice: 0,
+            weight,
+            raw_weight,
+            ..Default::default()
+        });
+        
+        let (has_voted, _, recorded, _, _, recorded_raw) = dao.get_vote_record(proposal_id, whale);
+        assert!(has_voted);
+        assert_eq!(recorded, U256::from(10_000));
+        assert_eq!(recorded_raw, raw_weight);
+        assert!(recorded_raw > recorded);
+    }
+
+    #[test]
+    fn test_weight_strategies() {
+        let token_votes = U256::from(10_000);
+        let (min, max) = (U256::ZERO, U256::ZERO);
+
+        let weight = |strategy: WeightStrategy| {
+            clamp_vote_weight(strategy_weight(strategy, token_votes), min, max)
+        };
+        assert_eq!(weight(WeightStrategy::TokenWeighted), U256::from(10_000));
+        assert_eq!(weight(WeightStrategy::OnePerson), U256::from(1));
+        assert_eq!(weight(WeightStrategy::Quadratic), U256::from(100));
+
+        // Quadratic floors non-square balances; bounds still apply after the strategy
+        assert_eq!(strategy_weight(WeightStrategy::Quadratic, U256::from(99)), U256::from(9));
+        let quadratic = strategy_weight(WeightStrategy::Quadratic, token_votes);
+        let capped = clamp_vote_weight(quadratic, min, U256::from(50));
+        assert_eq!(capped, U256::from(50));
+
+        for code in 0..3u8 {
+            assert_eq!(u8::from(WeightStrategy::try_from(code).unwrap()), code);
+        }
+        assert_eq!(WeightStrategy::try_from(3), Err(b"Invalid weight strategy".to_vec()));
+        assert_eq!(WeightStrategy::default(), WeightStrategy::TokenWeighted);
+
+        let mut dao = DAO::default();
+        let proposal_id = U256::from(1);
+        let core = ProposalCore {
+            id: proposal_id,
+            weight_strategy: WeightStrategy::Quadratic,
+            ..Default::default()
+        };
+        dao.proposal_core.setter(proposal_id).set(core);
+        assert_eq!(dao.get_proposal_weight_strategy(proposal_id), 2);
+    }
+
+    #[test]
+    fn test_effective_weight_matches_recorded_weight() {
+        let (vm, mut dao) = setup_dao();
+        let (delegated, whale, late) = (
+            Address::repeat_byte(0x6c),
+            Address::repeat_byte(0x6d),
+            Address::repeat_byte(0x6e),
+        );
+        for voter in [delegated, whale, late] {
+            verify_member(&vm, &mut dao, voter);
+        }
+        dao.set_voting_delay(U256::from(600)).unwrap();
+        dao.set_vote_weight_bounds(U256::ZERO, U256::from(1_000)).unwrap();
+        let proposal_id = propose(&vm, &mut dao, delegated);
+        let snapshot = U256::from(1_599);
+        
+        // Before voting opens the weight is projected from current delegated votes
+        mock_current_votes(&vm, delegated, U256::from(300));
+        mock_current_votes(&vm, late, U256::from(500));
+        assert_eq!(dao.effective_weight(proposal_id, delegated), Ok(U256::from(300)));
+        assert_eq!(dao.effective_weight(proposal_id, late), Ok(U256::from(500)));
+        
+        // Once open, effective_weight reads the snapshot and vote records exactly that
+        vm.set_block_timestamp(1_600);
+        let cases = [
+            (delegated, 300, 300), // delegated votes held at the snapshot
+            (whale, 5_000, 1_000), // capped at the maximum weight
+            (late, 0, 0),          // delegation landed after the snapshot
+        ];
+        for (voter, past_votes, expected) in cases {
+            mock_past_votes(&vm, voter, snapshot, U256::from(past_votes));
+            let effective = dao.effective_weight(proposal_id, voter).unwrap();
+            assert_eq!(effective, U256::from(expected));
+            vote_with(&vm, &mut dao, voter, proposal_id, 0, past_votes).unwrap();
+            assert_eq!(dao.get_vote_record(proposal_id, voter).2, effective);
+        }
+        assert_eq!(dao.proposal_core.get(proposal_id).for_votes, U256::from(1_300));
+        
+        // No underflow for a proposal opening at time zero
+        assert_eq!(vote_snapshot(U256::ZERO), U256::ZERO);
+        assert_eq!(dao.effective_weight(U256::from(9), delegated), Err(b"Proposal does not exist".to_vec()));
+    }
+
+    #[test]
+    fn test_vote_tallies_accumulate_snapshot_weights() {
+        let (vm, mut dao) = setup_dao();
+        let voters: Vec<Address> = (0x11..=0x16).map(Address::repeat_byte).collect();
+        for voter in &voters {
+            verify_member(&vm, &mut dao, *voter);
+        }
+        let id = propose(&vm, &mut dao, voters[0]);
+        let start_time = dao.proposal_core.get(id).start_time;
+        vm.set_block_timestamp(start_time.to::<u64>());
+        
+        // Each ballot counts the token's getPastVotes at the snapshot
+        vote_with(&vm, &mut dao, voters[0], id, 0, 500).unwrap();
+        vote_with(&vm, &mut dao, voters[1], id, 0, 250).unwrap();
+        vote_with(&vm, &mut dao, voters[2], id, 1, 300).unwrap();
+        vote_with(&vm, &mut dao, voters[3], id, 2, 40).unwrap();
+        
+        // Votes borrowed after the snapshot count for nothing
+        mock_current_votes(&vm, voters[4], U256::from(1_000_000));
+        vote_with(&vm, &mut dao, voters[4], id, 0, 0).unwrap();
+        assert_eq!(dao.get_vote_record(id, voters[4]).2, U256::ZERO);
+        
+        let (_, _, _, _, _, _, for_votes, against_votes, abstain_votes, _, _) = dao.get_proposal(id);
+        assert_eq!(for_votes, U256::from(750));
+        assert_eq!(against_votes, U256::from(300));
+        assert_eq!(abstain_votes, U256::from(40));
+        assert_eq!(dao.get_vote_record(id, voters[1]).2, U256::from(250));
+        
+        // A failing token call reverts the vote instead of counting a default weight
+        let call = token_abi::getPastVotesCall { account: voters[5], timepoint: vote_snapshot(start_time) };
+        vm.mock_static_call(token(), call.abi_encode(), Err(Vec::new()));
+        let (commitment, proof_hash) = zk_of(voters[5]);
+        vm.set_sender(voters[5]);
+        assert_eq!(
+            dao.vote(id, 0, commitment, proof_hash),
+            Err(b"Failed to query voting power".to_vec())
+        );
+        assert!(!dao.get_vote_record(id, voters[5]).0);
+        assert_eq!(dao.get_proposal(id).6, U256::from(750));
+        
+        assert_eq!(
+            vote_with(&vm, &mut dao, voters[5], id, 3, 10),
+            Err(b"Invalid vote choice (must be 0, 1, or 2)".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_undelegated_holder_delegates_and_votes_in_one_call() {
+        let (vm, mut dao) = setup_dao();
+        let holder = Address::repeat_byte(0x21);
+        let empty = Address::repeat_byte(0x22);
+        let delegated = Address::repeat_byte(0x23);
+        for member in [holder, empty, delegated] {
+            verify_member(&vm, &mut dao, member);
+        }
+        let id = propose(&vm, &mut dao, holder);
+        let start_time = dao.proposal_core.get(id).start_time;
+        vm.set_block_timestamp(start_time.to::<u64>());
+        let snapshot = vote_snapshot(start_time);
+        let mock_delegate = |account: Address, delegatee: Address| {
+            let call = token_abi::delegatesCall { account };
+            let answer = token_abi::delegatesCall::abi_encode_returns(&(delegatee,));
+            vm.mock_static_call(token(), call.abi_encode(), Ok(answer));
+        };
+        let activation = |account: Address| token_abi::activateSelfDelegationCall { account }.abi_encode();
+        let delegate_and_vote = |dao: &mut DAO, voter: Address| {
+            let (commitment, proof_hash) = zk_of(voter);
+            vm.set_sender(voter);
+            dao.delegate_self_and_vote(id, 0, commitment, proof_hash)
+        };
+        
+        // An undelegated holder holding votes delegated to them at the snapshot
+        mock_delegate(holder, Address::ZERO);
+        mock_past_votes(&vm, holder, snapshot, U256::from(40));
+        vm.mock_call(token(), activation(holder), U256::ZERO, Err(Vec::new()));
+        assert_eq!(delegate_and_vote(&mut dao, holder), Err(b"Self-delegation failed".to_vec()));
+        
+        // Delegation is activated and the vote cast in the same call
+        vm.mock_call(token(), activation(holder), U256::ZERO, Ok(Vec::new()));
+        delegate_and_vote(&mut dao, holder).unwrap();
+        let record = dao.get_vote_record(id, holder);
+        assert!(record.0);
+        assert_eq!(record.2, U256::from(40));
+        assert_eq!(dao.get_proposal(id).6, U256::from(40));
+        
+        // With nothing at the snapshot the call reverts instead of spending the ballot
+        mock_delegate(empty, Address::ZERO);
+        mock_past_votes(&vm, empty, snapshot, U256::ZERO);
+        vm.mock_call(token(), activation(empty), U256::ZERO, Ok(Vec::new()));
+        assert_eq!(
+            delegate_and_vote(&mut dao, empty),
+            Err(b"No voting power at proposal snapshot".to_vec())
+        );
+        assert!(!dao.get_vote_record(id, empty).0);
+        
+        // Holders who already delegated skip activation and just vote
+        mock_delegate(delegated, delegated);
+        mock_past_votes(&vm, delegated, snapshot, U256::from(25));
+        vm.mock_call(token(), activation(delegated), U256::ZERO, Err(Vec::new()));
+        delegate_and_vote(&mut dao, delegated).unwrap();
+        assert_eq!(dao.get_vote_record(id, delegated).2, U256::from(25));
+        
+        let call = token_abi::delegatesCall { account: Address::repeat_byte(0x24) };
+        vm.mock_static_call(token(), call.abi_encode(), Err(Vec::new()));
+        assert_eq!(
+            delegate_and_vote(&mut dao, Address::repeat_byte(0x24)),
+            Err(b"Failed to query delegate".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_delegated_weight_at_snapshot_matches_vote_weight() {
+        let (vm, mut dao) = setup_dao();
+        let voter = Address::repeat_byte(0x0a);
+        verify_member(&vm, &mut dao, voter);
+        dao.set_voting_delay(U256::from(600)).unwrap();
+        dao.set_vote_weight_bounds(U256::ZERO, U256::from(100)).unwrap();
+        let id = propose(&vm, &mut dao, voter);
+        let start_time = dao.proposal_core.get(id).start_time;
+        
+        // Before the snapshot the view projects the voter's current delegation
+        mock_current_votes(&vm, voter, U256::from(250));
+        assert_eq!(dao.delegated_weight_at_snapshot(id, voter), Ok(U256::from(250)));
+        
+        // Once voting opens it reads the snapshot, and reports it unbounded while vote
+        // credits the capped weight
+        vm.set_block_timestamp(start_time.to::<u64>());
+        vote_with(&vm, &mut dao, voter, id, 0, 300).unwrap();
+        assert_eq!(dao.delegated_weight_at_snapshot(id, voter), Ok(U256::from(300)));
+        assert_eq!(dao.get_vote_record(id, voter).2, U256::from(100));
+        
+        for missing in [U256::ZERO, dao.proposal_count()] {
+            assert_eq!(
+                dao.delegated_weight_at_snapshot(missing, voter),
+                Err(b"Proposal does not exist".to_vec())
+            );
+        }
+    }
+
+    #[test]
+    fn test_voter_paging() {
+        let (vm, mut dao) = setup_dao();
+        let voters: Vec<Address> = (0x11..=0x15).map(Address::repeat_byte).collect();
+        for voter in &voters {
+            verify_member(&vm, &mut dao, *voter);
+        }
+        let id = propose(&vm, &mut dao, voters[0]);
+        let start_time = dao.proposal_core.get(id).start_time.to::<u64>();
+        
+        // Five ballots, one second apart, with distinct choices and weights
+        for (i, voter) in voters.iter().enumerate() {
+            vm.set_block_timestamp(start_time + i as u64);
+            vote_with(&vm, &mut dao, *voter, id, (i % 3) as u8, 100 + i as u64).unwrap();
+        }
+        
+        // A repeated ballot is rejected and not listed twice
+        assert_eq!(
+            vote_with(&vm, &mut dao, voters[1], id, 0, 1),
+            Err(b"User already voted on this proposal".to_vec())
+        );
+        assert_eq!(dao.get_proposal_voter_count(id), U256::from(5));
+        
+        let page = |dao: &DAO, offset: u64, limit: u64| {
+            dao.get_proposal_voters(id, U256::from(offset), U256::from(limit))
+        };
+        assert_eq!(page(&dao, 0, 10), voters);
+        assert_eq!(page(&dao, 0, 2), voters[..2].to_vec());
+        assert_eq!(page(&dao, 2, 2), voters[2..4].to_vec());
+        assert_eq!(page(&dao, 4, 2), voters[4..].to_vec());
+        assert!(page(&dao, 5, 2).is_empty());
+        assert!(page(&dao, 1, 0).is_empty());
+        assert!(dao.get_proposal_voters(id, U256::MAX, U256::MAX).is_empty());
+        
+        // Records line up with the same page of voters
+        let records = dao.get_proposal_vote_records(id, U256::from(1), U256::from(3));
+        assert_eq!(records.len(), 3);
+        for (i, record) in records.iter().enumerate() {
+            let voter = voters[i + 1];
+            assert_eq!(*record, dao.get_vote_record(id, voter));
+            assert!(record.0);
+            assert_eq!(record.1, ((i + 1) % 3) as u8);
+            assert_eq!(record.2, U256::from(101 + i as u64));
+            assert_eq!(record.3, zk_of(voter).1);
+            assert_eq!(record.4, U256::from(start_time + 1 + i as u64));
+        }
+        
+        // Other proposals have their own, empty, list
+        let other = propose(&vm, &mut dao, voters[0]);
+        assert!(dao.get_proposal_voters(other, U256::ZERO, U256::from(10)).is_empty());
+        assert!(dao.get_proposal_vote_records(other, U256::ZERO, U256::from(10)).is_empty());
+    }
+
+    #[test]
+    fn test_commitment_binding() {
+        let registered = [0xabu8; 32];
+        assert!(commitment_matches(registered, registered));
+        
+        let mut tampered = registered;
+        tampered[31] ^= 0x01;
+        assert!(!commitment_matches(registered, tampered));
+        
+        // No commitment bound yet means nothing can match
+        assert!(!commitment_matches([0u8; 32], registered));
+        
+        // A verified member presenting anything but their bound commitment is turned away
+        let (vm, mut dao) = setup_dao();
+        let member = Address::repeat_byte(0x31);
+        verify_member(&vm, &mut dao, member);
+        let (commitment, proof_hash) = zk_of(member);
+        let mut forged = commitment;
+        forged[0] ^= 0x01;
+        
+        vm.set_sender(member);
+        let before = dao.proposal_count();
+        assert_eq!(
+            dao.create_proposal(
+                "Title".into(),
+                "Description".into(),
+                treasury(),
+                U256::ZERO,
+                Vec::new(),
+                forged,
+                proof_hash,
+            ),
+            Err(b"Invalid ZK proof or commitment".to_vec())
+        );
+        assert_eq!(dao.proposal_count(), before);
+        
+        let proposal_id = propose(&vm, &mut dao, member);
+        let core = dao.proposal_core.get(proposal_id);
+        vm.set_block_timestamp(core.start_time.to::<u64>());
+        mock_past_votes(&vm, member, vote_snapshot(core.start_time), U256::from(100));
+        vm.set_sender(member);
+        assert_eq!(
+            dao.vote(proposal_id, 0, forged, proof_hash),
+            Err(b"Invalid ZK proof for vote".to_vec())
+        );
+        assert!(!dao.user_votes.get((proposal_id, member)).has_voted);
+        
+        // The bound commitment still goes through
+        dao.vote(proposal_id, 0, commitment, proof_hash).unwrap();
+        assert!(dao.user_votes.get((proposal_id, member)).has_voted);
+    }
+
+    #[test]
+    fn test_commitment_currency_against_registry() {
+        let (vm, mut dao) = setup_dao();
+        let user = Address::repeat_byte(0x31);
+        verify_member(&vm, &mut dao, user);
+        let (cached, _) = zk_of(user);
+        
+        // Registry still holds the same commitment
+        mock_registry_commitment(&vm, user, Ok(cached));
+        assert!(dao.is_commitment_current(user));
+        
+        // Registry moved on after an off-chain KYC update or migration
+        mock_registry_commitment(&vm, user, Ok([0x22u8; 32]));
+        assert!(!dao.is_commitment_current(user));
+        
+        // Registry unreachable
+        mock_registry_commitment(&vm, user, Err(Vec::new()));
+        assert!(!dao.is_commitment_current(user));
+        
+        // Unknown on both sides is not a match
+        let stranger = Address::repeat_byte(0x32);
+        mock_registry_commitment(&vm, stranger, Ok([0u8; 32]));
+        assert!(!dao.is_commitment_current(stranger));
+    }
+
+    #[test]
+    fn test_verification_reason_codes() {
+        let verified = MemberData {
+            is_member: true,
+            verified: true,
+            kyc_commitment: [1u8; 32],
+            proof_hash: [2u8; 32],
+            verification_timestamp: U256::from(1_000),
+            verification_type: VERIFICATION_TYPE_KYC,
+        };
+        let now = U256::from(2_000);
+        
+        assert_eq!(verification_reason(&verified, now, U256::ZERO), VERIFICATION_REASON_VERIFIED);
+        assert_eq!(verification_reason(&verified, now, U256::from(5_000)), VERIFICATION_REASON_VERIFIED);
+        assert_eq!(verification_reason(&verified, now, U256::from(500)), VERIFICATION_REASON_EXPIRED);
+        
+        assert_eq!(
+            verification_reason(&MemberData::default(), now, U256::ZERO),
+            VERIFICATION_REASON_NOT_MEMBER
+        );
+        
+        let no_commitment = MemberData { kyc_commitment: [0u8; 32], ..verified.clone() };
+        assert_eq!(verification_reason(&no_commitment, now, U256::ZERO), VERIFICATION_REASON_NO_COMMITMENT);
+        
+        let pending = MemberData { verified: false, ..verified.clone() };
+        assert_eq!(verification_reason(&pending, now, U256::ZERO), VERIFICATION_REASON_NOT_VERIFIED);
+    }
+
+    #[test]
+    fn test_batch_verification_flags() {
+        let (vm, mut dao) = setup_dao();
+        let (expired, revoked, fresh, synced_elsewhere, stranger) = (
+            Address::repeat_byte(0x71),
+            Address::repeat_byte(0x72),
+            Address::repeat_byte(0x73),
+            Address::repeat_byte(0x74),
+            Address::repeat_byte(0x75),
+        );
+        verify_member(&vm, &mut dao, expired);
+        verify_member(&vm, &mut dao, revoked);
+        dao.invalidate_proof(revoked).unwrap();
+        vm.set_block_timestamp(1_600);
+        verify_member(&vm, &mut dao, fresh);
+        dao.set_verification_validity(U256::from(500)).unwrap();
+        
+        // The registry only answers for users the DAO has no record of
+        dao.set_registry_fallback(true).unwrap();
+        mock_registry_verified(&vm, synced_elsewhere);
+        let is_verified = registry_abi::isVerifiedCall { user: stranger };
+        let unverified = registry_abi::isVerifiedCall::abi_encode_returns(&(false,));
+        vm.mock_static_call(registry(), is_verified.abi_encode(), Ok(unverified));
+        
+        let users = vec![fresh, expired, revoked, synced_elsewhere, stranger];
+        let flags = dao.are_verified(users.clone()).unwrap();
+        assert_eq!(flags, vec![true, false, false, true, false]);
+        let one_by_one: Vec<bool> = users.iter().map(|&user| dao.is_user_verified(user)).collect();
+        assert_eq!(flags, one_by_one);
+        
+        let oversized = vec![Address::ZERO; MAX_VERIFICATION_BATCH + 1];
+        assert_eq!(dao.are_verified(oversized), Err(b"Too many addresses".to_vec()));
+    }
+
+    #[test]
+    fn test_proposal_options_combine_in_one_call() {
+        let (vm, mut dao) = setup_dao();
+        let member = Address::repeat_byte(0x31);
+        verify_member(&vm, &mut dao, owner());
+        verify_member(&vm, &mut dao, member);
+        let create = |dao: &mut DAO, proposer: Address, options: ProposalOptions| {
+            let (commitment, proof_hash) = zk_of(proposer);
+            vm.set_sender(proposer);
+            dao.create_proposal_with_options(
+                "Title".into(),
+                "Description".into(),
+                treasury(),
+                U256::ZERO,
+                Vec::new(),
+                commitment,
+                proof_hash,
+                options,
+            )
+        };
+        
+        // A KYB-only proposal with its own delay, quorum and weighting
+        let options = ProposalOptions {
+            requiredVerificationType: VERIFICATION_TYPE_KYB,
+            quorumOverride: U256::from(QUORUM_VOTES * 2),
+            weightStrategy: u8::from(WeightStrategy::OnePerson),
+            executionDelayOverride: U256::from(2 * 86_400),
+        };
+        let id = create(&mut dao, owner(), options.clone()).unwrap();
+        let core = dao.proposal_core.get(id);
+        assert_eq!(core.required_verification_type, VERIFICATION_TYPE_KYB);
+        assert_eq!(core.quorum_override, U256::from(QUORUM_VOTES * 2));
+        assert_eq!(core.weight_strategy, WeightStrategy::OnePerson);
+        assert_eq!(core.execution_delay_override, U256::from(2 * 86_400));
+        
+        // Members may combine the options open to them
+        let member_options = ProposalOptions {
+            requiredVerificationType: VERIFICATION_TYPE_KYB,
+            executionDelayOverride: U256::from(2 * 86_400),
+            ..ProposalOptions::standard()
+        };
+        let id = create(&mut dao, member, member_options.clone()).unwrap();
+        assert_eq!(dao.get_proposal_verification_type(id), VERIFICATION_TYPE_KYB);
+        assert_eq!(dao.proposal_core.get(id).execution_delay_override, U256::from(2 * 86_400));
+        
+        // Each option is still checked on its own
+        assert_eq!(
+            create(&mut dao, member, options),
+            Err(b"Only owner can override quorum".to_vec())
+        );
+        let bad_strategy = ProposalOptions { weightStrategy: 3, ..member_options.clone() };
+        assert_eq!(create(&mut dao, member, bad_strategy), Err(b"Invalid weight strategy".to_vec()));
+        let bad_type = ProposalOptions { requiredVerificationType: 4, ..member_options };
+        assert_eq!(create(&mut dao, member, bad_type), Err(b"Invalid verification type".to_vec()));
+        
+        // The single-option entrypoints are shorthands for the same call
+        let plain = propose(&vm, &mut dao, member);
+        assert_eq!(dao.get_proposal_verification_type(plain), 0);
+        assert_eq!(dao.proposal_core.get(plain).execution_delay_override, U256::ZERO);
+    }
+
+    #[test]
+    fn test_proposal_verification_type() {
+        let (vm, mut dao) = setup_dao();
+        let kyc_member = Address::repeat_byte(0x31);
+        let kyb_member = Address::repeat_byte(0x32);
+        let both_member = Address::repeat_byte(0x33);
+        for (member, verification_type) in [
+            (kyc_member, VERIFICATION_TYPE_KYC),
+            (kyb_member, VERIFICATION_TYPE_KYB),
+            (both_member, VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB),
+        ] {
+            let (commitment, proof_hash) = zk_of(member);
+            dao.register_verified_user(member, commitment, proof_hash, verification_type).unwrap();
+        }
+        let propose_for = |dao: &mut DAO, proposer: Address, required_type: u8| {
+            let (commitment, proof_hash) = zk_of(proposer);
+            vm.set_sender(proposer);
+            dao.create_proposal_with_verification_type(
+                "Title".into(),
+                "Description".into(),
+                treasury(),
+                U256::ZERO,
+                Vec::new(),
+                commitment,
+                proof_hash,
+                required_type,
+            )
+        };
+        
+        assert_eq!(propose_for(&mut dao, kyc_member, 4), Err(b"Invalid verification type".to_vec()));
+        let kyb_only = propose_for(&mut dao, both_member, VERIFICATION_TYPE_KYB).unwrap();
+        let kyc_only = propose_for(&mut dao, both_member, VERIFICATION_TYPE_KYC).unwrap();
+        let unrestricted = propose(&vm, &mut dao, kyc_member);
+        assert_eq!(dao.get_proposal_verification_type(kyb_only), VERIFICATION_TYPE_KYB);
+        assert_eq!(dao.get_proposal_verification_type(unrestricted), 0);
+        vm.set_block_timestamp(dao.proposal_core.get(kyb_only).start_time.to::<u64>());
+        
+        // A KYC-only member cannot vote on a KYB-only proposal, nor a KYB-only member on a KYC one
+        assert_eq!(
+            vote_with(&vm, &mut dao, kyc_member, kyb_only, 0, 10),
+            Err(b"Verification type not accepted for this proposal".to_vec())
+        );
+        assert!(!dao.get_vote_record(kyb_only, kyc_member).0);
+        assert_eq!(
+            vote_with(&vm, &mut dao, kyb_member, kyc_only, 0, 10),
+            Err(b"Verification type not accepted for this proposal".to_vec())
+        );
+        vote_with(&vm, &mut dao, kyb_member, kyb_only, 2, 10).unwrap();
+        
+        // The "Both" member is accepted everywhere, and anyone verified on unrestricted proposals
+        vote_with(&vm, &mut dao, both_member, kyc_only, 0, 10).unwrap();
+        vote_with(&vm, &mut dao, both_member, unrestricted, 0, 10).unwrap();
+        vote_with(&vm, &mut dao, kyb_member, unrestricted, 0, 10).unwrap();
+        
+        pass_proposal(&vm, &mut dao, both_member, kyb_only);
+        let timelock_end = dao.execution_data.get(kyb_only).timelock_end;
+        vm.set_block_timestamp(timelock_end.to::<u64>());
+        vm.mock_call(treasury(), Vec::new(), U256::ZERO, Ok(Vec::new()));
+        
+        // Execution is held to the same requirement
+        let (commitment, proof_hash) = zk_of(kyc_member);
+        vm.set_sender(kyc_member);
+        assert_eq!(
+            dao.execute_proposal(kyb_only, commitment, proof_hash),
+            Err(b"Verification type not accepted for this proposal".to_vec())
+        );
+        assert!(dao.proposal_core.get(kyb_only).state == ProposalState::Passed);
+        
+        let (commitment, proof_hash) = zk_of(both_member);
+        vm.set_sender(both_member);
+        dao.execute_proposal(kyb_only, commitment, proof_hash).unwrap();
+        assert!(dao.proposal_core.get(kyb_only).state == ProposalState::Executed);
+    }
+
+    #[test]
+    fn test_quorum_progress() {
+        let required = U256::from(QUORUM_VOTES);
+
+        assert_eq!(quorum_progress_of(U256::ZERO, required), (U256::ZERO, required, false));
+        assert_eq!(quorum_progress_of(U256::from(37), required), (U256::from(37), required, false));
+        assert_eq!(quorum_progress_of(required, required), (required, required, true));
+        assert_eq!(quorum_progress_of(U256::from(250), required), (U256::from(250), required, true));
+    }
+
+    #[test]
+    fn test_hashed_description() {
+        let mut dao = DAO::default();
+        let proposal_id = U256::from(1);
+        let description = "Fund the audit described at ipfs://bafy...";
+        let stored_hash = hash_description(description);
+        
+        let core = ProposalCore {
+            id: proposal_id,
+            description_hash: stored_hash,
+            ..Default::default()
+        };
+        dao.proposal_core.setter(proposal_id).set(core);
+        assert_eq!(dao.get_proposal_description_hash(proposal_id), stored_hash);
+        
+        assert!(check_description(stored_hash, description).is_ok());
+        assert_eq!(
+            check_description(stored_hash, "Fund the audit described elsewhere"),
+            Err(b"Description hash mismatch".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_quorum_override() {
+        let default_quorum = U256::from(QUORUM_VOTES);
+        let elevated = U256::from(QUORUM_VOTES * 3);
+        
+        // Participation that meets the default quorum fails a higher override
+        let participation = default_quorum;
+        let default_required = required_quorum(U256::ZERO, default_quorum);
+        let (_, _, reached) = quorum_progress_of(participation, default_required);
+        assert!(reached);
+        let elevated_required = required_quorum(elevated, default_quorum);
+        let (_, required, reached) = quorum_progress_of(participation, elevated_required);
+        assert_eq!(required, elevated);
+        assert!(!reached);
+        
+        // Overrides can raise but not lower the default
+        assert!(check_quorum_override(U256::ZERO, default_quorum).is_ok());
+        assert!(check_quorum_override(elevated, default_quorum).is_ok());
+        assert_eq!(
+            check_quorum_override(U256::from(1), default_quorum),
+            Err(b"Quorum override below default".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_execution_delay_override() {
+        let day = 24 * 60 * 60u64;
+        let global = U256::from(day);
+        let week = U256::from(7 * day);
+        
+        // Overrides can lengthen but not shorten the global delay
+        assert!(check_execution_delay_override(U256::ZERO, global).is_ok());
+        assert!(check_execution_delay_override(week, global).is_ok());
+        assert_eq!(
+            check_execution_delay_override(U256::from(60), global),
+            Err(b"Execution delay override below global delay".to_vec())
+        );
+        assert_eq!(required_execution_delay(U256::ZERO, global), global);
+        
+        // A passed proposal with a week-long override is timelocked for the week
+        let mut dao = DAO::default();
+        dao.execution_delay.set(global);
+        let proposal_id = U256::from(1);
+        let now = U256::from(50_000);
+        dao.finalize_active(proposal_id, ProposalCore {
+            id: proposal_id,
+            for_votes: U256::from(QUORUM_VOTES),
+            execution_delay_override: week,
+            ..Default::default()
+        }, U256::from(QUORUM_VOTES), now).unwrap();
+        assert_eq!(dao.execution_data.get(proposal_id).timelock_end, now + week);
+        assert_eq!(dao.get_proposal_execution_delay(proposal_id), week);
+        
+        // If the global delay later exceeds the override, the longer global delay applies
+        let month = U256::from(30 * day);
+        assert_eq!(required_execution_delay(week, month), month);
+    }
+
+    #[test]
+    fn test_treasury_payout_proposal_queues_withdrawal() {
+        use crate::treasury::Treasury;
+        
+        let (vm, mut dao) = setup_dao();
+        let dao_address = Address::repeat_byte(0xda);
+        let recipient = Address::repeat_byte(0x77);
+        let amount = U256::from(400);
+        verify_member(&vm, &mut dao, owner());
+        
+        // The linked treasury, deployed with this DAO as its controller
+        let treasury_vm = TestVM::default();
+        treasury_vm.set_sender(owner());
+        treasury_vm.set_balance(treasury_vm.contract_address(), U256::from(1_000));
+        let mut treasury_contract = Treasury::from(&treasury_vm);
+        treasury_contract.init(owner()).ok().unwrap();
+        treasury_contract.set_dao(dao_address).ok().unwrap();
+        
+        let (commitment, proof_hash) = zk_of(owner());
+        vm.set_sender(owner());
+        let proposal_id = dao.create_treasury_payout_proposal(
+            "Grant".into(),
+            "Pay the grant".into(),
+            recipient,
+            amount,
+            commitment,
+            proof_hash,
+        ).unwrap();
+        pass_proposal(&vm, &mut dao, owner(), proposal_id);
+        
+        // Nothing runs before the timelock ends
+        vm.set_sender(owner());
+        assert!(dao.execute_proposal(proposal_id, commitment, proof_hash).is_err());
+        let timelock_end = dao.execution_data.get(proposal_id).timelock_end;
+        vm.set_block_timestamp(timelock_end.to::<u64>());
+        
+        // The DAO's call is relayed to the treasury, which queues the withdrawal
+        let calldata = queueWithdrawalCall { recipient, amount, memo: "Grant".into() }.abi_encode();
+        treasury_vm.set_sender(dao_address);
+        let withdrawal_id = treasury_contract
+            .queue_withdrawal(recipient, amount, "Grant".into())
+            .ok()
+            .unwrap();
+        let returned = queueWithdrawalCall::abi_encode_returns(&(withdrawal_id,));
+        vm.mock_call(treasury(), calldata, U256::ZERO, Ok(returned));
+        
+        dao.execute_proposal(proposal_id, commitment, proof_hash).unwrap();
+        assert!(dao.proposal_core.get(proposal_id).state == ProposalState::Executed);
+        assert!(!dao.accepting_eth.get());
+        
+        let (queued_to, queued_amount, _, executed, cancelled, memo) =
+            treasury_contract.get_withdrawal(withdrawal_id);
+        assert_eq!((queued_to, queued_amount, executed, cancelled), (recipient, amount, false, false));
+        assert_eq!(memo, "Grant");
+        assert_eq!(treasury_contract.available_eth(), U256::from(600));
+        
+        // A proposal executes once
+        assert_eq!(
+            dao.execute_proposal(proposal_id, commitment, proof_hash),
+            Err(b"Proposal already executed".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_execution_delay_override_is_capped() {
+        let (vm, mut dao) = setup_dao();
+        verify_member(&vm, &mut dao, owner());
+        let (commitment, proof_hash) = zk_of(owner());
+        let propose_with_delay = |dao: &mut DAO, delay: U256| {
+            dao.create_proposal_with_execution_delay(
+                "Title".into(),
+                "Description".into(),
+                treasury(),
+                U256::ZERO,
+                Vec::new(),
+                commitment,
+                proof_hash,
+                delay,
+            )
+        };
+        
+        // An unbounded override would lock the proposal forever (or overflow at finalize)
+        assert_eq!(
+            propose_with_delay(&mut dao, U256::MAX),
+            Err(b"Execution delay override too long".to_vec())
+        );
+        assert_eq!(
+            propose_with_delay(&mut dao, U256::from(MAX_EXECUTION_DELAY + 1)),
+            Err(b"Execution delay override too long".to_vec())
+        );
+        let proposal_id = propose_with_delay(&mut dao, U256::from(MAX_EXECUTION_DELAY)).unwrap();
+        assert_eq!(
+            dao.get_proposal_execution_delay(proposal_id),
+            U256::from(MAX_EXECUTION_DELAY)
+        );
+        
+        // A global delay raised past the cap still can't wrap timelock_end
+        dao.execution_delay.set(U256::MAX);
+        assert_eq!(
+            dao.finalize_active(proposal_id, ProposalCore {
+                id: proposal_id,
+                for_votes: U256::from(QUORUM_VOTES),
+                ..Default::default()
+            }, U256::from(QUORUM_VOTES), U256::from(1_000)),
+            Err(b"Execution delay overflow".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_resync_member_across_registries() {
+        let (vm, mut dao) = setup_dao();
+        let kept = Address::repeat_byte(0x41);
+        let revoked = Address::repeat_byte(0x42);
+        let joined = Address::repeat_byte(0x43);
+        verify_member(&vm, &mut dao, kept);
+        verify_member(&vm, &mut dao, revoked);
+        
+        let new_registry = Address::repeat_byte(0x5e);
+        let mock_user = |user: Address, verified: bool, commitment: [u8; 32], proof_hash: [u8; 32]| {
+            let is_verified = registry_abi::isVerifiedCall { user };
+            let answer = registry_abi::isVerifiedCall::abi_encode_returns(&(verified,));
+            vm.mock_static_call(new_registry, is_verified.abi_encode(), Ok(answer));
+            let get_user = registry_abi::getUserCall { user };
+            let record = registry_abi::getUserCall::abi_encode_returns(&(
+                verified,
+                FixedBytes::from(commitment),
+                FixedBytes::from(proof_hash),
+            ));
+            vm.mock_static_call(new_registry, get_user.abi_encode(), Ok(record));
+        };
+        
+        vm.set_sender(kept);
+        assert_eq!(dao.resync_member(kept), Err(b"Only owner can resync members".to_vec()));
+        assert_eq!(dao.resync_members(vec![kept]), Err(b"Only owner can resync members".to_vec()));
+        
+        vm.set_sender(owner());
+        dao.update_shadow_id_registry(new_registry).unwrap();
+        vm.set_block_timestamp(5_000);
+        let (kept_commitment, kept_proof) = zk_of(kept);
+        mock_user(kept, true, kept_commitment, kept_proof);
+        mock_user(revoked, false, [0u8; 32], [0u8; 32]);
+        mock_user(joined, true, [0x77; 32], [0x88; 32]);
+        let logs_before = vm.get_emitted_logs().len();
+        dao.resync_members(vec![kept, revoked, joined]).unwrap();
+        
+        // The new registry agrees on `kept`: its verification keeps the original timestamp
+        assert_eq!(
+            dao.get_user_verification(kept),
+            (true, kept_commitment, kept_proof, VERIFICATION_TYPE_KYC, U256::from(1_000))
+        );
+        
+        // It no longer verifies `revoked`: local state is cleared, membership kept
+        assert_eq!(dao.get_user_verification(revoked), (false, [0u8; 32], [0u8; 32], VERIFICATION_TYPE_KYC, U256::ZERO));
+        assert!(dao.members.get(revoked).is_member);
+        assert!(!dao.is_verified_member(revoked));
+        
+        // A member only the new registry knows is picked up as of now
+        assert!(dao.members.get(joined).is_member);
+        assert_eq!(dao.get_user_verification(joined).1, [0x77; 32]);
+        assert_eq!(dao.get_user_verification(joined).4, U256::from(5_000));
+        
+        // One MemberResynced per member, naming the registry it came from
+        let logs = vm.get_emitted_logs();
+        assert_eq!(logs.len(), logs_before + 3);
+        let (topics, data) = &logs[logs_before + 1];
+        assert_eq!(topics[0], MemberResynced::SIGNATURE_HASH);
+        assert_eq!(topics[1], revoked.into_word());
+        assert_eq!(topics[2], new_registry.into_word());
+        assert!(!MemberResynced::abi_decode_data(data, true).unwrap().0);
+        
+        // A rotated commitment replaces the old one and restarts the validity period
+        vm.set_block_timestamp(6_000);
+        mock_user(kept, true, [0x99; 32], [0xaa; 32]);
+        dao.resync_member(kept).unwrap();
+        assert_eq!(
+            dao.get_user_verification(kept),
+            (true, [0x99; 32], [0xaa; 32], VERIFICATION_TYPE_KYC, U256::from(6_000))
+        );
+        
+        // A registry that cannot answer leaves the member untouched
+        let unknown = Address::repeat_byte(0x44);
+        let is_verified = registry_abi::isVerifiedCall { user: unknown };
+        vm.mock_static_call(new_registry, is_verified.abi_encode(), Err(Vec::new()));
+        assert_eq!(dao.resync_member(unknown), Err(b"Registry isVerified call failed".to_vec()));
+        assert!(!dao.members.get(unknown).is_member);
+    }
+
+    #[test]
+    fn test_raw_commitment_views_match_submitted_proof() {
+        let (vm, mut dao) = setup_dao();
+        let user = Address::repeat_byte(0x42);
+        let commitment = [0x5au8; 32];
+        let proof_hash = [0xc3u8; 32];
+        
+        // Nothing on record before the backend submits the proof
+        assert_eq!(dao.member_commitment(user), [0u8; 32]);
+        assert_eq!(dao.validated_commitment(user), FixedBytes::ZERO);
+        
+        vm.set_sender(owner());
+        dao.submit_zk_proof(user, commitment, proof_hash).unwrap();
+        assert_eq!(dao.member_commitment(user), commitment);
+        assert_eq!(dao.member_proof_hash(user), proof_hash);
+        assert_eq!(dao.validated_commitment(user), FixedBytes::from(commitment));
+    }
+
+    #[test]
+    fn test_invalidated_proof_blocks_voting_until_reverified() {
+        let mut dao = DAO::default();
+        let user = Address::repeat_byte(0x24);
+        let commitment = [0x61u8; 32];
+        let proof_hash = [0x62u8; 32];
+        let now = U256::from(1_000);
+        
+        dao.record_zk_proof(user, commitment, proof_hash, now);
+        let reason = verification_reason(&dao.members.get(user), now, U256::ZERO);
+        assert_eq!(reason, VERIFICATION_REASON_VERIFIED);
+        
+        let cleared = dao.clear_validated_proof(user);
+        assert_eq!(cleared, FixedBytes::from(commitment));
+        assert_eq!(dao.validated_commitment(user), FixedBytes::ZERO);
+        
+        // vote and propose require VERIFICATION_REASON_VERIFIED, which the user no longer has
+        let reason = verification_reason(&dao.members.get(user), now, U256::ZERO);
+        assert_eq!(reason, VERIFICATION_REASON_NOT_VERIFIED);
+        
+        // Re-submitting a proof restores access
+        dao.record_zk_proof(user, commitment, [0x63u8; 32], now + U256::from(1));
+        let reason = verification_reason(&dao.members.get(user), now, U256::ZERO);
+        assert_eq!(reason, VERIFICATION_REASON_VERIFIED);
+    }
+
+    #[test]
+    fn test_voting_period_update_emits_parameters_snapshot() {
+        let (vm, mut dao) = setup_dao();
+        let snapshots = |vm: &TestVM| {
+            vm.get_emitted_logs()
+                .into_iter()
+                .filter(|(topics, _)| topics[0] == ParametersSnapshot::SIGNATURE_HASH)
+                .map(|(_, data)| ParametersSnapshot::abi_decode_data(&data, true).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let emitted = snapshots(&vm).len();
+        
+        // A period too short to vote in is rejected before anything is logged
+        assert_eq!(
+            dao.set_voting_period(U256::from(MIN_VOTING_PERIOD - 1)),
+            Err(b"Voting period too short".to_vec())
+        );
+        assert_eq!(dao.get_parameters().0, U256::from(3_600));
+        assert_eq!(snapshots(&vm).len(), emitted);
+        
+        // One change logs the full parameter set with the new period
+        dao.set_voting_period(U256::from(7_200)).unwrap();
+        let logged = snapshots(&vm);
+        assert_eq!(logged.len(), emitted + 1);
+        assert_eq!(
+            logged.last().unwrap(),
+            &(U256::from(7_200), U256::from(20), U256::from(86_400), U256::ZERO, U256::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_dao_summary_matches_getters() {
+        let mut dao = DAO::default();
+        dao.owner.set(Address::repeat_byte(0x01));
+        dao.treasury.set(Address::repeat_byte(0x02));
+        dao.shadow_id_registry.set(Address::repeat_byte(0x03));
+        dao.voting_period.set(U256::from(3_600));
+        dao.quorum_percent.set(U256::from(10));
+        dao.verification_required.set(true);
+        dao.proposal_count.set(U256::from(4));
+
+        // Re-recording an existing member does not count them twice
+        let member = Address::repeat_byte(0x10);
+        dao.record_zk_proof(member, [0x11; 32], [0x12; 32], U256::from(1_000));
+        dao.record_zk_proof(member, [0x11; 32], [0x13; 32], U256::from(2_000));
+        dao.record_zk_proof(Address::repeat_byte(0x20), [0x21; 32], [0x22; 32], U256::from(1_000));
+        assert_eq!(dao.member_count(), U256::from(2));
+
+        let (voting_period, quorum_percent, _, _) = dao.get_parameters();
+        assert_eq!(
+            dao.get_dao_summary(),
+            (
+                dao.owner(),
+                dao.treasury(),
+                dao.shadow_id_registry(),
+                dao.proposal_count(),
+                dao.member_count(),
+                voting_period,
+                quorum_percent,
+                true,
+            )
+        );
+    }
+
+    #[test]
+    fn test_supports_interface() {
+        let signatures = [
+            "createProposal(string,string,address,uint256,uint8[],uint8[32],uint8[32])",
+            "vote(uint256,uint8,uint8[32],uint8[32])",
+            "finalizeProposal(uint256)",
+            "executeProposal(uint256,uint8[32],uint8[32])",
+            "cancelProposal(uint256)",
+        ];
+        let mut expected = [0u8; 4];
+        for signature in signatures {
+            let hash = crypto::keccak(signature.as_bytes());
+            for (byte, selector_byte) in expected.iter_mut().zip(&hash.0[..4]) {
+                *byte ^= selector_byte;
+            }
+        }
+        assert_eq!(expected, DAO_INTERFACE_ID);
+
+        let dao = DAO::default();
+        assert!(dao.supports_interface(FixedBytes(ERC165_INTERFACE_ID)));
+        assert!(dao.supports_interface(FixedBytes(DAO_INTERFACE_ID)));
+        assert!(!dao.supports_interface(FixedBytes([0x36, 0x37, 0x2b, 0x07])));
+        assert!(!dao.supports_interface(FixedBytes([0xff; 4])));
+    }
+
+    #[test]
+    fn test_batch_allowed_targets() {
+        let (vm, mut dao) = setup_dao();
+        let targets: Vec<Address> = (0x51..=0x55u8).map(Address::repeat_byte).collect();
+        let allowed = vec![true; 5];
+        let updates = |vm: &TestVM| {
+            vm.get_emitted_logs()
+                .iter()
+                .filter(|(topics, _)| topics[0] == AllowedTargetUpdated::SIGNATURE_HASH)
+                .count()
+        };
+        let before = updates(&vm);
+        
+        vm.set_sender(Address::repeat_byte(0x21));
+        assert_eq!(
+            dao.set_allowed_targets(targets.clone(), allowed.clone()),
+            Err(b"Only owner can manage allowed targets".to_vec())
+        );
+        vm.set_sender(owner());
+        assert_eq!(
+            dao.set_allowed_targets(targets.clone(), allowed[..4].to_vec()),
+            Err(b"Array length mismatch".to_vec())
+        );
+        assert_eq!(
+            dao.set_allowed_targets(vec![targets[0], Address::ZERO], vec![true, true]),
+            Err(b"Invalid target".to_vec())
+        );
+        assert!(!dao.is_allowed_target(targets[0]));
+        
+        // Five targets in one call, each queryable and logged on its own
+        dao.set_allowed_targets(targets.clone(), allowed).unwrap();
+        for target in &targets {
+            assert!(dao.is_allowed_target(*target));
+        }
+        assert!(!dao.is_allowed_target(Address::repeat_byte(0x56)));
+        assert_eq!(updates(&vm), before + 5);
+        
+        // Revoking one entry leaves the rest allowed
+        dao.set_allowed_targets(vec![targets[2]], vec![false]).unwrap();
+        assert!(!dao.is_allowed_target(targets[2]));
+        assert!(dao.is_allowed_target(targets[3]));
+    }
+
+    #[test]
+    fn test_finalized_proposal_waits_out_timelock() {
+        let mut dao = DAO::default();
+        let delay = U256::from(2 * 24 * 60 * 60);
+        dao.execution_delay.set(delay);
+        
+        let proposal_id = U256::from(1);
+        let finalized_at = U256::from(100_000);
+        dao.finalize_active(proposal_id, ProposalCore {
+            id: proposal_id,
+            for_votes: U256::from(QUORUM_VOTES),
+            ..Default::default()
+        }, U256::from(QUORUM_VOTES), finalized_at).unwrap();
+        assert_eq!(dao.proposal_core.get(proposal_id).state, ProposalState::Passed);
+        let timelock_end = dao.execution_data.get(proposal_id).timelock_end;
+        assert_eq!(timelock_end, finalized_at + delay);
+        
+        // Executing right away, or a second early, hits the timelock
+        let too_early = Err(b"Timelock period not expired".to_vec());
+        assert_eq!(check_execution_window(finalized_at, timelock_end), too_early);
+        assert_eq!(check_execution_window(timelock_end - U256::from(1), timelock_end), too_early);
+        
+        // Once the delay has passed execution is allowed
+        assert!(check_execution_window(timelock_end, timelock_end).is_ok());
+    }
+
+    #[test]
+    fn test_time_remaining_phases() {
+        let (vm, mut dao) = setup_dao();
+        let voter = Address::repeat_byte(0x67);
+        verify_member(&vm, &mut dao, voter);
+        dao.set_voting_delay(U256::from(600)).unwrap();
+        let proposal_id = propose(&vm, &mut dao, voter);
+        
+        // Pending through the voting delay, then voting until the period ends
+        assert_eq!(dao.time_remaining(proposal_id), Ok((PHASE_PENDING, U256::from(600))));
+        vm.set_block_timestamp(1_600);
+        assert_eq!(dao.time_remaining(proposal_id), Ok((PHASE_VOTING, U256::from(3_600))));
+        
+        // Passed: the 86400 second timelock counts down from finalization
+        pass_proposal(&vm, &mut dao, voter, proposal_id);
+        assert_eq!(dao.time_remaining(proposal_id), Ok((PHASE_TIMELOCK, U256::from(86_400))));
+        
+        // Executable with no deadline once the timelock is over
+        vm.set_block_timestamp(5_201 + 86_400);
+        assert_eq!(dao.time_remaining(proposal_id), Ok((PHASE_NONE, U256::ZERO)));
+        assert_eq!(dao.time_remaining(U256::from(9)), Err(b"Proposal does not exist".to_vec()));
+    }
+
+    #[test]
+    fn test_fallback_rejects_stray_eth() {
+        let (vm, mut dao) = setup_dao();
+
+        // Direct sends revert outside proposal execution; plain calls carry nothing to lock
+        vm.set_value(U256::from(1_000));
+        assert_eq!(dao.fallback(), Err(b"DAO does not accept ETH".to_vec()));
+        vm.set_value(U256::ZERO);
+        assert!(dao.fallback().is_ok());
+
+        // Value-carrying proposals pay out of the DAO's balance
+        let member = Address::repeat_byte(0x21);
+        verify_member(&vm, &mut dao, member);
+        let (commitment, proof_hash) = zk_of(member);
+        let payout = |dao: &mut DAO, title: &str| {
+            vm.set_sender(member);
+            dao.create_proposal(
+                title.into(),
+                "Pays the treasury".into(),
+                treasury(),
+                U256::from(5),
+                Vec::new(),
+                commitment,
+                proof_hash,
+            ).unwrap()
+        };
+        let failing = payout(&mut dao, "Refused payout");
+        let proposal_id = payout(&mut dao, "Payout");
+        pass_proposal(&vm, &mut dao, member, failing);
+        pass_proposal(&vm, &mut dao, member, proposal_id);
+        let timelock_end = dao.execution_data.get(proposal_id).timelock_end;
+        vm.set_block_timestamp(timelock_end.to::<u64>());
+        vm.set_balance(vm.contract_address(), U256::from(10));
+        vm.set_sender(member);
+
+        // A failed execution closes the window again
+        vm.mock_call(treasury(), Vec::new(), U256::from(5), Err(Vec::new()));
+        assert_eq!(
+            dao.execute_proposal(failing, commitment, proof_hash),
+            Err(b"Proposal execution failed".to_vec())
+        );
+        assert!(!dao.accepting_eth.get());
+
+        vm.mock_call(treasury(), Vec::new(), U256::from(5), Ok(Vec::new()));
+        dao.execute_proposal(proposal_id, commitment, proof_hash).unwrap();
+        assert!(dao.proposal_core.get(proposal_id).state == ProposalState::Executed);
+        assert!(!dao.accepting_eth.get());
+
+        // While the target runs, ETH it sends back reaches the fallback and is accepted
+        dao.accepting_eth.set(true);
+        vm.set_sender(treasury());
+        vm.set_value(U256::from(2));
+        assert!(dao.fallback().is_ok());
+        dao.accepting_eth.set(false);
+        assert_eq!(dao.fallback(), Err(b"DAO does not accept ETH".to_vec()));
+    }
+
+    #[test]
+    fn test_late_vote_auto_finalizes() {
+        let end = U256::from(10_000);
+        assert!(!should_auto_finalize(false, end + U256::from(1), end));
+        assert!(!should_auto_finalize(true, end, end));
+        assert!(should_auto_finalize(true, end + U256::from(1), end));
+
+        let (vm, mut dao) = setup_dao();
+        let (early, late) = (Address::repeat_byte(0x68), Address::repeat_byte(0x69));
+        verify_member(&vm, &mut dao, early);
+        verify_member(&vm, &mut dao, late);
+        let proposal_id = propose(&vm, &mut dao, early);
+        vote_with(&vm, &mut dao, early, proposal_id, 0, 100).unwrap();
+        mock_past_supply(&vm, U256::from(999), U256::from(100));
+
+        // Without auto-finalize a late vote reverts
+        vm.set_block_timestamp(4_601);
+        assert!(!dao.auto_finalize_enabled());
+        assert_eq!(
+            vote_with(&vm, &mut dao, late, proposal_id, 1, 50),
+            Err(b"Voting period has ended".to_vec())
+        );
+
+        // With it, the late vote settles the proposal and is reported as dropped
+        vm.set_sender(owner());
+        dao.set_auto_finalize(true).unwrap();
+        assert!(dao.auto_finalize_enabled());
+        assert_eq!(vote_with(&vm, &mut dao, late, proposal_id, 1, 50), Ok(()));
+
+        let core = dao.proposal_core.get(proposal_id);
+        assert_eq!(core.state, ProposalState::Passed);
+        assert_eq!((core.for_votes, core.against_votes), (U256::from(100), U256::ZERO));
+        assert!(!dao.user_votes.get((proposal_id, late)).has_voted);
+        assert!(vm.get_emitted_logs().iter().any(|(topics, _)| {
+            topics[0] == VoteDropped::SIGNATURE_HASH && topics[2] == late.into_word()
+        }));
+
+        // The proposal is no longer open to votes
+        assert_eq!(
+            vote_with(&vm, &mut dao, late, proposal_id, 1, 50),
+            Err(b"Proposal is not active".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_sponsored_user_onboards_and_proposes_in_one_step() {
+        let (vm, mut dao) = setup_dao();
+        let user = Address::repeat_byte(0x31);
+        let (commitment, proof_hash) = zk_of(user);
+        let submit = |dao: &mut DAO, commitment: [u8; 32], target: Address| {
+            dao.submit_proof_and_create_proposal(
+                user,
+                commitment,
+                proof_hash,
+                "Title".into(),
+                "Description".into(),
+                target,
+                U256::ZERO,
+                Vec::new(),
+            )
+        };
+
+        let proposals_before = dao.proposal_count();
+
+        // Only the backend may sponsor a user
+        vm.set_sender(user);
+        assert_eq!(
+            submit(&mut dao, commitment, treasury()),
+            Err(b"Only authorized backend can submit proofs".to_vec())
+        );
+
+        // A malformed proof aborts before anything is recorded
+        vm.set_sender(owner());
+        assert_eq!(
+            submit(&mut dao, [0u8; 32], treasury()),
+            Err(b"Invalid commitment or proof hash from ZK flow".to_vec())
+        );
+
+        // A proposal that cannot be created leaves the user unregistered
+        assert_eq!(
+            submit(&mut dao, commitment, Address::repeat_byte(0x66)),
+            Err(b"Target contract not allowed".to_vec())
+        );
+        assert!(!dao.members.get(user).is_member);
+        assert_eq!(dao.member_count(), U256::ZERO);
+        assert_eq!(dao.proposal_count(), proposals_before);
+
+        // Registration and proposal land together, with the user as proposer
+        let id = submit(&mut dao, commitment, treasury()).unwrap();
+        assert_eq!(id, proposals_before);
+        assert_eq!(dao.proposal_count(), proposals_before + U256::from(1));
+        assert_eq!(dao.get_proposal(id).1, user);
+        assert!(dao.is_verified_member(user));
+        assert_eq!(dao.validated_commitment(user), FixedBytes::from(commitment));
+        assert_eq!(dao.member_count(), U256::from(1));
+    }
+
+    #[test]
+    fn test_proposal_zk_views_return_creation_proof() {
+        let (vm, mut dao) = setup_dao();
+        let proposer = Address::repeat_byte(0x21);
+        verify_member(&vm, &mut dao, proposer);
+        let proposal_id = propose(&vm, &mut dao, proposer);
+        
+        let (commitment, proof_hash) = zk_of(proposer);
+        assert_eq!(dao.get_proposal_zk(proposal_id), (commitment, proof_hash));
+        
+        let full = dao.get_proposal_full(proposal_id);
+        assert_eq!((full.11, full.12), (commitment, proof_hash));
+        let (id, author, title, description, start_time, end_time, for_votes, against_votes, abstain_votes, state, cancelled) =
+            dao.get_proposal(proposal_id);
+        assert_eq!(
+            (full.0, full.1, full.2, full.3, full.4, full.5, full.6, full.7, full.8, full.9, full.10),
+            (id, author, title, description, start_time, end_time, for_votes, against_votes, abstain_votes, state, cancelled)
+        );
+        assert_eq!(full.1, proposer);
+        
+        // An unknown proposal has no proof on record
+        assert_eq!(dao.get_proposal_zk(dao.proposal_count()), ([0u8; 32], [0u8; 32]));
+    }
+
+    #[test]
+    fn test_relinked_treasury_becomes_allowed_target() {
+        let (vm, mut dao) = setup_dao();
+        let first = Address::repeat_byte(0x61);
+        let second = Address::repeat_byte(0x62);
+        let linked = |vm: &TestVM, treasury: Address| {
+            vm.get_emitted_logs().iter().any(|(topics, _)| {
+                topics[0] == TreasuryLinked::SIGNATURE_HASH && topics[1] == treasury.into_word()
+            })
+        };
+        
+        vm.set_sender(Address::repeat_byte(0x21));
+        assert_eq!(dao.set_treasury(first, false), Err(b"Only owner can update treasury".to_vec()));
+        vm.set_sender(owner());
+        assert_eq!(dao.set_treasury(Address::ZERO, false), Err(b"Invalid treasury address".to_vec()));
+        assert_eq!(dao.treasury(), treasury());
+        
+        // Relinking keeps the old treasury allowed unless asked to drop it
+        dao.set_treasury(first, false).unwrap();
+        assert_eq!(dao.treasury(), first);
+        assert!(dao.is_allowed_target(first));
+        assert!(dao.is_allowed_target(treasury()));
+        assert!(linked(&vm, first));
+        
+        dao.set_treasury(second, true).unwrap();
+        assert_eq!(dao.treasury(), second);
+        assert!(dao.is_allowed_target(second));
+        assert!(!dao.is_allowed_target(first));
+        assert!(linked(&vm, second));
+        
+        // Proposals can now target the new treasury
+        verify_member(&vm, &mut dao, owner());
+        let (commitment, proof_hash) = zk_of(owner());
+        assert!(dao.create_proposal(
+            "Title".into(),
+            "Description".into(),
+            second,
+            U256::ZERO,
+            Vec::new(),
+            commitment,
+            proof_hash,
+        ).is_ok());
+    }
+
+    #[test]
+    fn test_rescue_force_sent_eth() {
+        let (vm, mut dao) = setup_dao();
+        let rescuer = Address::repeat_byte(0x5e);
+        // ETH force-sent through selfdestruct, which no receive hook could refuse
+        vm.set_balance(vm.contract_address(), U256::from(5));
+        
+        vm.set_sender(rescuer);
+        assert_eq!(dao.rescue_eth(rescuer, U256::from(5)), Err(b"Only owner can rescue ETH".to_vec()));
+        
+        vm.set_sender(owner());
+        assert_eq!(dao.rescue_eth(Address::ZERO, U256::from(5)), Err(b"Invalid rescue recipient".to_vec()));
+        assert_eq!(dao.rescue_eth(rescuer, U256::from(6)), Err(b"Invalid rescue amount".to_vec()));
+        assert_eq!(dao.rescue_eth(rescuer, U256::ZERO), Err(b"Invalid rescue amount".to_vec()));
+        
+        vm.mock_call(rescuer, Vec::new(), U256::from(5), Ok(Vec::new()));
+        dao.rescue_eth(rescuer, U256::from(5)).unwrap();
+        let (topics, data) = vm
+            .get_emitted_logs()
+            .into_iter()
+            .rev()
+            .find(|(topics, _)| topics[0] == EthRescued::SIGNATURE_HASH)
+            .expect("EthRescued emitted");
+        assert_eq!(topics[1], rescuer.into_word());
+        assert_eq!(EthRescued::abi_decode_data(&data, true).unwrap().0, U256::from(5));
+        
+        // A recipient that refuses the ETH fails the rescue
+        vm.mock_call(owner(), Vec::new(), U256::from(1), Err(Vec::new()));
+        assert_eq!(dao.rescue_eth(owner(), U256::from(1)), Err(b"ETH transfer failed".to_vec()));
+    }
+
+    #[test]
+    fn test_proposal_requirements() {
+        let mut dao = DAO::default();
+        let proposal_id = U256::from(1);
+        dao.proposal_core.setter(proposal_id).set(ProposalCore {
+            id: proposal_id,
+            required_verification_type: VERIFICATION_TYPE_KYB,
+            ..Default::default()
+        });
+
+        assert_eq!(dao.proposal_requirements(proposal_id), (VERIFICATION_TYPE_KYB, Vec::new()));
+        assert_eq!(dao.proposal_requirements(U256::from(2)), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_stale_cache_confirmed_by_registry() {
+        let registry = Address::repeat_byte(0x5e);
+        assert!(should_consult_registry(VERIFICATION_REASON_NOT_MEMBER, true, registry));
+        assert!(should_consult_registry(VERIFICATION_REASON_NO_COMMITMENT, true, registry));
+        assert!(!should_consult_registry(VERIFICATION_REASON_VERIFIED, true, registry));
+        assert!(!should_consult_registry(VERIFICATION_REASON_NOT_VERIFIED, true, registry));
+        assert!(!should_consult_registry(VERIFICATION_REASON_EXPIRED, true, registry));
+        assert!(!should_consult_registry(VERIFICATION_REASON_NOT_MEMBER, false, registry));
+        assert!(!should_consult_registry(VERIFICATION_REASON_NOT_MEMBER, true, Address::ZERO));
+
+        // Proof landed on the registry, but the DAO cache still holds the revoked record;
+        // only an explicit owner resync replaces it
+        let mut dao = DAO::default();
+        let user = Address::repeat_byte(0x52);
+        let now = U256::from(1_000);
+        dao.record_zk_proof(user, [0x53u8; 32], [0x54u8; 32], now);
+        dao.clear_validated_proof(user);
+        let stale = dao.members.get(user);
+        assert_eq!(verification_reason(&stale, now, U256::ZERO), VERIFICATION_REASON_NOT_VERIFIED);
+
+        // The registry confirms: verify_and_cache resyncs the record it reports
+        let fresh_commitment = [0x55u8; 32];
+        let later = now + U256::from(60);
+        dao.store_member(user, resynced_member(stale, true, fresh_commitment, [0x56u8; 32], later));
+
+        let cached = dao.members.get(user);
+        assert_eq!(verification_reason(&cached, later, U256::ZERO), VERIFICATION_REASON_VERIFIED);
+        assert!(commitment_matches(cached.kyc_commitment, fresh_commitment));
+        assert_eq!(cached.verification_timestamp, later);
+        assert_eq!(dao.member_count(), U256::from(1));
+    }
+
+    #[test]
+    fn test_acting_does_not_extend_verification() {
+        let (vm, mut dao) = setup_dao();
+        let member = Address::repeat_byte(0x64);
+        verify_member(&vm, &mut dao, member);
+        vm.set_sender(owner());
+        dao.set_verification_validity(U256::from(100)).unwrap();
+
+        // Proposing late in the window leaves the original verification time in place
+        vm.set_block_timestamp(1_090);
+        propose(&vm, &mut dao, member);
+        assert_eq!(dao.members.get(member).verification_timestamp, U256::from(1_000));
+
+        vm.set_block_timestamp(1_101);
+        let (commitment, proof_hash) = zk_of(member);
+        vm.set_sender(member);
+        let err = dao.create_proposal(
+            "Title".into(),
+            "Description".into(),
+            treasury(),
+            U256::ZERO,
+            Vec::new(),
+            commitment,
+            proof_hash,
+        ).unwrap_err();
+        assert_eq!(err, b"KYC required".to_vec());
+
+        // Only a new proof restarts the window
+        verify_member(&vm, &mut dao, member);
+        assert_eq!(dao.members.get(member).verification_timestamp, U256::from(1_101));
+    }
+
+    #[test]
+    fn test_proposal_created_reports_stored_window() {
+        let (vm, mut dao) = setup_dao();
+        let proposer = Address::repeat_byte(0x65);
+        verify_member(&vm, &mut dao, proposer);
+        vm.set_sender(owner());
+        dao.set_voting_delay(U256::from(600)).unwrap();
+        let proposal_id = propose(&vm, &mut dao, proposer);
+        
+        let core = dao.proposal_core.get(proposal_id);
+        assert_eq!(core.start_time, U256::from(1_600));
+        assert_eq!(core.end_time, U256::from(1_600 + 3_600));
+        
+        let (_, data) = vm
+            .get_emitted_logs()
+            .into_iter()
+            .rev()
+            .find(|(topics, _)| topics[0] == ProposalCreated::SIGNATURE_HASH)
+            .expect("ProposalCreated emitted");
+        let (_, start_time, end_time, _) = ProposalCreated::abi_decode_data(&data, true).unwrap();
+        assert_eq!((start_time, end_time), (core.start_time, core.end_time));
+    }
+
+    mod registry_abi {
+        stylus_sdk::alloy_sol_types::sol! {
+            function isVerified(address user) external view returns (bool);
+            function getUser(address user) external view returns (bool, bytes32, bytes32);
+            function getCommitment(address user) external view returns (bytes32);
+        }
+    }
+
+    /// Answer the registry's getCommitment(user) with `result`
+    fn mock_registry_commitment(vm: &TestVM, user: Address, result: Result<[u8; 32], Vec<u8>>) {
+        let call = registry_abi::getCommitmentCall { user };
+        let returned = result.map(|commitment| {
+            registry_abi::getCommitmentCall::abi_encode_returns(&(FixedBytes::from(commitment),))
+        });
+        vm.mock_static_call(registry(), call.abi_encode(), returned);
+    }
+
+    /// Have the registry report `user` as verified with their zk_of commitment
+    fn mock_registry_verified(vm: &TestVM, user: Address) {
+        let (commitment, proof_hash) = zk_of(user);
+        let is_verified = registry_abi::isVerifiedCall { user };
+        let verified = registry_abi::isVerifiedCall::abi_encode_returns(&(true,));
+        vm.mock_static_call(registry(), is_verified.abi_encode(), Ok(verified));
+        let get_user = registry_abi::getUserCall { user };
+        let record = registry_abi::getUserCall::abi_encode_returns(&(
+            true,
+            FixedBytes::from(commitment),
+            FixedBytes::from(proof_hash),
+        ));
+        vm.mock_static_call(registry(), get_user.abi_encode(), Ok(record));
+    }
+
+    #[test]
+    fn test_registry_fallback_skips_revoked_and_expired_members() {
+        let (vm, mut dao) = setup_dao();
+        vm.set_sender(owner());
+        dao.set_registry_fallback(true).unwrap();
+
+        // Unknown to the DAO but verified on the registry: resynced and allowed to propose
+        let unsynced = Address::repeat_byte(0x61);
+        mock_registry_verified(&vm, unsynced);
+        propose(&vm, &mut dao, unsynced);
+        assert!(dao.members.get(unsynced).verified);
+
+        // A local revocation is final, whatever the registry still says
+        let revoked = Address::repeat_byte(0x62);
+        verify_member(&vm, &mut dao, revoked);
+        dao.invalidate_proof(revoked).unwrap();
+        mock_registry_verified(&vm, revoked);
+        let (commitment, proof_hash) = zk_of(revoked);
+        vm.set_sender(revoked);
+        let err = dao.create_proposal(
+            "Title".into(),
+            "Description".into(),
+            treasury(),
+            U256::ZERO,
+            Vec::new(),
+            commitment,
+            proof_hash,
+        ).unwrap_err();
+        assert_eq!(err, b"KYC required".to_vec());
+        assert!(!dao.members.get(revoked).verified);
+
+        // So is expiry: the registry cannot refresh the verification timestamp
+        let expired = Address::repeat_byte(0x63);
+        verify_member(&vm, &mut dao, expired);
+        dao.set_verification_validity(U256::from(100)).unwrap();
+        vm.set_block_timestamp(1_101);
+        mock_registry_verified(&vm, expired);
+        let (commitment, proof_hash) = zk_of(expired);
+        vm.set_sender(expired);
+        let err = dao.create_proposal(
+            "Title".into(),
+            "Description".into(),
+            treasury(),
+            U256::ZERO,
+            Vec::new(),
+            commitment,
+            proof_hash,
+        ).unwrap_err();
+        assert_eq!(err, b"KYC required".to_vec());
+        assert_eq!(dao.members.get(expired).verification_timestamp, U256::from(1_000));
+    }
+
+    #[test]
+    fn test_constructor_runs_once() {
+        let mut dao = DAO::default();
+        let token = Address::repeat_byte(0x01);
+        let treasury = Address::repeat_byte(0x02);
+        let registry = Address::repeat_byte(0x03);
+        dao.constructor(
+            token,
+            treasury,
+            registry,
+            U256::from(3_600),
+            U256::from(20),
+            U256::from(86_400),
+            U256::from(100),
+        ).unwrap();
+        let owner = dao.owner();
+        
+        // A second call cannot repoint the DAO or reset its parameters
+        let attacker = Address::repeat_byte(0x0b);
+        let second = dao.constructor(
+            attacker,
+            attacker,
+            attacker,
+            U256::from(1),
+            U256::ZERO,
+            U256::ZERO,
+            U256::ZERO,
+        );
+        assert_eq!(second, Err(b"Already initialized".to_vec()));
+        assert_eq!(dao.owner(), owner);
+        assert_eq!(dao.treasury(), treasury);
+        assert_eq!(dao.shadow_id_registry(), registry);
+        assert_eq!(
+            dao.get_parameters(),
+            (U256::from(3_600), U256::from(20), U256::from(86_400), U256::from(100))
+        );
+        assert!(!dao.is_allowed_target(attacker));
+        
+        // A deployment from before the flag existed is still guarded by its owner
+        dao.initialized.set(false);
+        let legacy = dao.constructor(
+            attacker,
+            attacker,
+            attacker,
+            U256::from(1),
+            U256::ZERO,
+            U256::ZERO,
+            U256::ZERO,
+        );
+        assert_eq!(legacy, Err(b"Already initialized".to_vec()));
+        assert_eq!(dao.owner(), owner);
+    }
+
+    #[test]
+    fn test_verify_outcome() {
+        let mut dao = DAO::default();
+        dao.proposal_count.set(U256::from(4));
+        let passed = u8::from(ProposalState::Passed);
+        let rejected = u8::from(ProposalState::Rejected);
+        let executed = u8::from(ProposalState::Executed);
+        
+        // 1: finalized correctly as Passed
+        dao.finalize_active(U256::from(1), ProposalCore {
+            for_votes: U256::from(QUORUM_VOTES),
+            ..Default::default()
+        }, U256::from(QUORUM_VOTES), U256::from(1_000)).unwrap();
+        assert_eq!(dao.verify_outcome(U256::from(1), passed), Ok(true));
+        assert_eq!(dao.verify_outcome(U256::from(1), executed), Ok(true));
+        assert_eq!(dao.verify_outcome(U256::from(1), rejected), Ok(false));
+        
+        // 2: stored as Passed although it missed quorum
+        dao.proposal_core.setter(U256::from(2)).set(ProposalCore {
+            for_votes: U256::from(QUORUM_VOTES - 1),
+            quorum_votes: U256::from(QUORUM_VOTES),
+            state: ProposalState::Passed,
+            ..Default::default()
+        });
+        assert_eq!(dao.verify_outcome(U256::from(2), passed), Ok(false));
+        assert_eq!(dao.verify_outcome(U256::from(2), rejected), Ok(false));
+        
+        // 3: still active, nothing settled to verify
+        dao.proposal_core.setter(U256::from(3)).set(ProposalCore::default());
+        assert_eq!(dao.verify_outcome(U256::from(3), rejected), Ok(false));
+        
+        assert_eq!(
+            dao.verify_outcome(U256::from(4), passed),
+            Err(b"Proposal does not exist".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_proposals_per_block_cap() {
+        let mut dao = DAO::default();
+        let block = U256::from(12_345);
+        
+        // Unlimited by default
+        for _ in 0..5 {
+            assert!(dao.consume_proposal_slot(block).is_ok());
+        }
+        
+        dao.max_proposals_per_block.set(U256::from(2));
+        let next = block + U256::from(1);
+        assert!(dao.consume_proposal_slot(next).is_ok());
+        assert!(dao.consume_proposal_slot(next).is_ok());
+        assert_eq!(dao.consume_proposal_slot(next), Err(b"Proposal rate limit".to_vec()));
+        
+        // The allowance resets in the following block
+        assert!(dao.consume_proposal_slot(next + U256::from(1)).is_ok());
+    }
+
+    #[test]
+    fn test_import_members_until_finalized() {
+        let (vm, mut dao) = setup_dao();
+        vm.set_block_timestamp(2_000);
+        let users = vec![Address::repeat_byte(0x11), Address::repeat_byte(0x22)];
+        let commitments = vec![[0xc1; 32], [0xc2; 32]];
+        let proof_hashes = vec![[0xd1; 32], [0xd2; 32]];
+        let types = vec![VERIFICATION_TYPE_KYC, VERIFICATION_TYPE_KYC | VERIFICATION_TYPE_KYB];
+        let timestamps = vec![U256::from(1_000), U256::from(2_000)];
+        let import = |dao: &mut DAO, users: Vec<Address>, types: Vec<u8>, timestamps: Vec<U256>| {
+            let len = users.len();
+            dao.import_members(
+                users,
+                commitments[..len].to_vec(),
+                proof_hashes[..len].to_vec(),
+                types,
+                timestamps,
+            )
+        };
+        
+        vm.set_sender(users[0]);
+        let outsider = import(&mut dao, users.clone(), types.clone(), timestamps.clone());
+        assert_eq!(outsider, Err(b"Only owner can import members".to_vec()));
+        
+        vm.set_sender(owner());
+        let mismatch = import(&mut dao, users.clone(), types.clone(), vec![U256::from(1_000)]);
+        assert_eq!(mismatch, Err(b"Array length mismatch".to_vec()));
+        let bad_type = import(&mut dao, users.clone(), vec![VERIFICATION_TYPE_KYC, 4], timestamps.clone());
+        assert_eq!(bad_type, Err(b"Invalid verification type".to_vec()));
+        let future = import(&mut dao, users.clone(), types.clone(), vec![U256::from(1_000), U256::from(2_001)]);
+        assert_eq!(future, Err(b"Verification timestamp in the future".to_vec()));
+        let duplicate = import(&mut dao, vec![users[0], users[0]], types.clone(), timestamps.clone());
+        assert_eq!(duplicate, Err(b"Duplicate member".to_vec()));
+        assert_eq!(dao.member_count(), U256::ZERO);
+        
+        import(&mut dao, users.clone(), types.clone(), timestamps.clone()).unwrap();
+        assert_eq!(dao.member_count(), U256::from(2));
+        let imported = dao.members.get(users[1]);
+        assert!(imported.is_member && imported.verified);
+        assert_eq!(imported.kyc_commitment, [0xc2; 32]);
+        assert_eq!(imported.proof_hash, [0xd2; 32]);
+        assert_eq!(imported.verification_type, 3);
+        assert_eq!(imported.verification_timestamp, U256::from(2_000));
+        assert_eq!(dao.validated_proofs.get(users[0]), FixedBytes::from([0xc1; 32]));
+        
+        // Existing members cannot be overwritten by a later batch
+        let reimport = import(&mut dao, vec![users[0]], vec![VERIFICATION_TYPE_KYB], vec![U256::from(2_000)]);
+        assert_eq!(reimport, Err(b"Already a member".to_vec()));
+        assert_eq!(dao.members.get(users[0]).verification_timestamp, U256::from(1_000));
+        
+        dao.finalize_member_import().unwrap();
+        assert!(dao.member_import_finalized());
+        let late = Address::repeat_byte(0x33);
+        let blocked = import(&mut dao, vec![late], vec![VERIFICATION_TYPE_KYB], vec![U256::from(2_000)]);
+        assert_eq!(blocked, Err(b"Member import finalized".to_vec()));
+        assert!(!dao.members.get(late).is_member);
+    }
+
+    #[test]
+    fn test_tampered_execution_data_rejected() {
+        let target = Address::repeat_byte(0x7a);
+        let value = U256::from(1_000);
+        let data = vec![0xa9, 0x05, 0x9c, 0xbb];
+        let approved = ExecutionData {
+            target,
+            value,
+            data: data.clone(),
+            ..Default::default()
+        };
+        let stored_hash = hash_execution(target, value, &data);
+        assert!(check_execution_hash(stored_hash, &approved).is_ok());
+        
+        // Timelock bookkeeping is not part of the approved call
+        let scheduled = ExecutionData { timelock_end: U256::from(99), ..approved.clone() };
+        assert!(check_execution_hash(stored_hash, &scheduled).is_ok());
+        
+        let mismatch = Err(b"Execution data mismatch".to_vec());
+        let retargeted = ExecutionData { target: Address::repeat_byte(0x7b), ..approved.clone() };
+        assert_eq!(check_execution_hash(stored_hash, &retargeted), mismatch);
+        let revalued = ExecutionData { value: value + U256::from(1), ..approved.clone() };
+        assert_eq!(check_execution_hash(stored_hash, &revalued), mismatch);
+        let recalled = ExecutionData { data: vec![0xa9, 0x05, 0x9c, 0xbc], ..approved };
+        assert_eq!(check_execution_hash(stored_hash, &recalled), mismatch);
+    }
+
+    #[test]
+    fn test_quorum_percent_of_eligible_supply() {
+        let supply = U256::from(1_000);
+        assert_eq!(percent_quorum(supply, U256::from(20)), U256::from(200));
+        assert_eq!(percent_quorum(supply, U256::from(60)), U256::from(600));
+        assert_eq!(percent_quorum(U256::from(101), U256::from(20)), U256::from(21));
+        assert_eq!(percent_quorum(supply, U256::ZERO), U256::ZERO);
+        
+        let mut dao = DAO::default();
+        let now = U256::from(10_000);
+        let finalize = |dao: &mut DAO, id: u64, pct: u64, for_votes: u64, against_votes: u64| {
+            let proposal_id = U256::from(id);
+            dao.finalize_active(proposal_id, ProposalCore {
+                id: proposal_id,
+                for_votes: U256::from(for_votes),
+                against_votes: U256::from(against_votes),
+                ..Default::default()
+            }, percent_quorum(supply, U256::from(pct)), now).unwrap();
+            dao.proposal_core.get(proposal_id).state
+        };
+        
+        // 20%: 200 of 1000 votes reach quorum, 199 do not
+        assert_eq!(finalize(&mut dao, 1, 20, 150, 50), ProposalState::Passed);
+        assert_eq!(finalize(&mut dao, 2, 20, 149, 50), ProposalState::Rejected);
+        // Quorum alone is not enough without a For majority
+        assert_eq!(finalize(&mut dao, 3, 20, 100, 100), ProposalState::Rejected);
+        
+        // 60%: the same turnout now falls short
+        assert_eq!(finalize(&mut dao, 4, 60, 150, 50), ProposalState::Rejected);
+        assert_eq!(finalize(&mut dao, 5, 60, 400, 199), ProposalState::Rejected);
+        assert_eq!(finalize(&mut dao, 6, 60, 400, 200), ProposalState::Passed);
+        assert_eq!(dao.proposal_core.get(U256::from(6)).quorum_votes, U256::from(600));
+        
+        // An override only ever raises the percentage quorum
+        assert_eq!(required_quorum(U256::from(QUORUM_VOTES), U256::from(600)), U256::from(600));
+        assert_eq!(required_quorum(U256::from(900), U256::from(600)), U256::from(900));
+    }
+
+    #[test]
+    fn test_quorum_progress_by_weight_strategy() {
+        let (vm, mut dao) = setup_dao();
+        let voter = Address::repeat_byte(0x66);
+        verify_member(&vm, &mut dao, voter);
+        verify_member(&vm, &mut dao, owner());
+        dao.set_voting_delay(U256::from(600)).unwrap();
+        
+        // Before the snapshot there is no supply to measure against yet
+        let token_weighted = propose(&vm, &mut dao, voter);
+        assert_eq!(dao.quorum_progress(token_weighted), Ok((U256::ZERO, U256::ZERO, false)));
+        
+        // Token-weighted: 20% of the 1000 token supply at the snapshot
+        vm.set_block_timestamp(1_600);
+        mock_past_supply(&vm, U256::from(1_599), U256::from(1_000));
+        vote_with(&vm, &mut dao, voter, token_weighted, 0, 150).unwrap();
+        assert_eq!(
+            dao.quorum_progress(token_weighted),
+            Ok((U256::from(150), U256::from(200), false))
+        );
+        
+        // Quadratic: the fixed quorum, with no supply lookup (none is mocked at this snapshot)
+        let (commitment, proof_hash) = zk_of(owner());
+        vm.set_sender(owner());
+        let quadratic = dao.create_proposal_with_weight_strategy(
+            "Title".into(),
+            "Description".into(),
+            treasury(),
+            U256::ZERO,
+            Vec::new(),
+            commitment,
+            proof_hash,
+            u8::from(WeightStrategy::Quadratic),
+        ).unwrap();
+        vm.set_block_timestamp(2_200);
+        vote_with(&vm, &mut dao, voter, quadratic, 0, 10_000).unwrap();
+        assert_eq!(
+            dao.quorum_progress(quadratic),
+            Ok((U256::from(100), U256::from(QUORUM_VOTES), true))
+        );
+    }
+
+    #[test]
+    fn test_voting_delay_window() {
+        let created = U256::from(1_000);
+        let delay = U256::from(600);
+        let period = U256::from(3_600);
+        let start = created + delay;
+        let end = start + period;
+
+        // Before the delay elapses
+        assert_eq!(check_voting_window(created, start, end), Err(b"Voting not started".to_vec()));
+        assert_eq!(check_voting_window(start - U256::from(1), start, end), Err(b"Voting not started".to_vec()));
+
+        // After the delay, through the end of the voting period
+        assert!(check_voting_window(start, start, end).is_ok());
+        assert!(check_voting_window(end, start, end).is_ok());
+        assert_eq!(check_voting_window(end + U256::from(1), start, end), Err(b"Voting period has ended".to_vec()));
+    }
+}
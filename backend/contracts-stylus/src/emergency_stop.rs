@@ -0,0 +1,193 @@
+//! EmergencyStop - Ecosystem-wide circuit breaker for Arbitrum Stylus
+//! A guardian flips one flag; the DAO, Treasury and ZkVerificationStorage read it
+//! through `isStopped()` and refuse state-changing calls while it is set.
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    alloy_primitives::Address,
+    alloy_sol_types::{sol, SolEvent},
+    prelude::*,
+    msg, evm,
+};
+use alloc::vec::Vec;
+
+sol! {
+    event EmergencyStopSet(address indexed guardian, bool stopped);
+    event GuardianUpdated(address indexed oldGuardian, address indexed newGuardian);
+}
+
+#[solidity_storage]
+#[entrypoint]
+pub struct EmergencyStop {
+    owner: StorageAddress,
+    guardian: StorageAddress,
+    stopped: StorageBool,
+}
+
+#[public]
+impl EmergencyStop {
+    /// Initialize with an owner (manages the guardian) and the guardian that can trigger the stop
+    pub fn initialize(&mut self, owner: Address, guardian: Address) -> Result<(), Vec<u8>> {
+        if self.owner.get() != Address::ZERO {
+            return Err(b"Already initialized".to_vec());
+        }
+        if owner == Address::ZERO || guardian == Address::ZERO {
+            return Err(b"Invalid address".to_vec());
+        }
+
+        self.owner.set(owner);
+        self.guardian.set(guardian);
+
+        evm::log(GuardianUpdated { oldGuardian: Address::ZERO, newGuardian: guardian });
+        Ok(())
+    }
+
+    /// Halt or resume every contract wired to this coordinator (guardian only)
+    pub fn set_emergency_stop(&mut self, stopped: bool) -> Result<(), Vec<u8>> {
+        let caller = msg::sender();
+        if caller != self.guardian.get() {
+            return Err(b"Only guardian can toggle emergency stop".to_vec());
+        }
+
+        self.stopped.set(stopped);
+
+        evm::log(EmergencyStopSet { guardian: caller, stopped });
+        Ok(())
+    }
+
+    /// Replace the guardian (owner only)
+    pub fn set_guardian(&mut self, new_guardian: Address) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"Only owner can set guardian".to_vec());
+        }
+        if new_guardian == Address::ZERO {
+            return Err(b"Invalid address".to_vec());
+        }
+
+        let old_guardian = self.guardian.get();
+        self.guardian.set(new_guardian);
+
+        evm::log(GuardianUpdated { oldGuardian: old_guardian, newGuardian: new_guardian });
+        Ok(())
+    }
+
+    /// Whether the ecosystem is currently halted
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.get()
+    }
+
+    pub fn guardian(&self) -> Address {
+        self.guardian.get()
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starts_running() {
+        let coordinator = EmergencyStop::default();
+        assert!(!coordinator.is_stopped());
+        assert_eq!(coordinator.guardian(), Address::ZERO);
+    }
+
+    #[test]
+    fn test_guardian_halts_dao_treasury_and_verifier() {
+        use crate::dao::DAO;
+        use crate::treasury::{Treasury, TreasuryError};
+        use crate::zk_integration::ZkVerificationStorage;
+        use stylus_sdk::{
+            alloy_primitives::{Bytes, U256},
+            alloy_sol_types::SolCall,
+            testing::TestVM,
+        };
+
+        sol! {
+            function isStopped() external view returns (bool);
+        }
+
+        let owner = Address::repeat_byte(0x01);
+        let guardian = Address::repeat_byte(0x9a);
+        let coordinator_address = Address::repeat_byte(0xe5);
+
+        let coordinator_vm = TestVM::default();
+        let mut coordinator = EmergencyStop::from(&coordinator_vm);
+        coordinator.initialize(owner, guardian).unwrap();
+
+        // Each contract gets its own VM (its own storage), all pointed at the coordinator
+        let (dao_vm, treasury_vm, verifier_vm) = (TestVM::default(), TestVM::default(), TestVM::default());
+        for vm in [&dao_vm, &treasury_vm, &verifier_vm] {
+            vm.set_sender(owner);
+        }
+
+        let mut dao = DAO::from(&dao_vm);
+        dao.constructor(
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x04),
+            U256::from(3_600),
+            U256::from(20),
+            U256::from(86_400),
+            U256::ZERO,
+        ).unwrap();
+        dao.set_emergency_stop_contract(coordinator_address).unwrap();
+
+        let mut treasury = Treasury::from(&treasury_vm);
+        treasury.init(owner).ok().unwrap();
+        treasury.set_dao(Address::repeat_byte(0x05)).ok().unwrap();
+        treasury.set_emergency_stop_contract(coordinator_address).ok().unwrap();
+
+        let mut verifier = ZkVerificationStorage::from(&verifier_vm);
+        verifier.initialize(owner).unwrap();
+        verifier.set_emergency_stop_contract(coordinator_address).unwrap();
+
+        // Every contract's isStopped() call is answered by the coordinator's current flag
+        let relay = |coordinator: &EmergencyStop| {
+            let stopped = isStoppedCall::abi_encode_returns(&(coordinator.is_stopped(),));
+            for vm in [&dao_vm, &treasury_vm, &verifier_vm] {
+                vm.mock_static_call(coordinator_address, isStoppedCall {}.abi_encode(), Ok(stopped.clone()));
+            }
+        };
+
+        // Only the guardian can pull the switch
+        coordinator_vm.set_sender(owner);
+        assert_eq!(
+            coordinator.set_emergency_stop(true),
+            Err(b"Only guardian can toggle emergency stop".to_vec())
+        );
+        coordinator_vm.set_sender(guardian);
+        coordinator.set_emergency_stop(true).unwrap();
+        relay(&coordinator);
+
+        // One flag halts all three contracts
+        let bytes = |json: &[u8]| Bytes::from(json.to_vec());
+        treasury_vm.set_value(U256::from(1_000));
+        assert_eq!(
+            dao.vote(U256::from(1), 0, [0x11; 32], [0x12; 32]),
+            Err(b"Emergency stop active".to_vec())
+        );
+        assert!(matches!(treasury.deposit(), Err(TreasuryError::EmergencyStopped(_))));
+        assert_eq!(
+            verifier.verify_zk_proof(bytes(b"{}"), bytes(b"{}")),
+            Err(b"Emergency stop active".to_vec())
+        );
+
+        // Resuming lets each contract get past the stop check again
+        coordinator.set_emergency_stop(false).unwrap();
+        relay(&coordinator);
+
+        assert_eq!(
+            dao.vote(U256::from(1), 0, [0x11; 32], [0x12; 32]),
+            Err(b"KYC required".to_vec())
+        );
+        assert!(treasury.deposit().is_ok());
+        assert_eq!(treasury.contribution_of(owner), U256::from(1_000));
+        assert_eq!(verifier.verify_zk_proof(bytes(b"{}"), bytes(b"{}")), Ok(false));
+    }
+}
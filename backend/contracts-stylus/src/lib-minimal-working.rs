@@ -2,7 +2,7 @@
 #![cfg_attr(not(feature = "export-abi"), no_main)]
 extern crate alloc;
 
-use stylus_sdk::{prelude::*, storage::{StorageAddress, StorageU256, StorageBool}};
+use stylus_sdk::{prelude::*, storage::{StorageAddress, StorageU256, StorageBool, StorageMap}};
 use alloy_primitives::{Address, U256};
 
 /// Simple DAO contract for testing Stylus compilation
@@ -17,6 +17,9 @@ pub struct SimpleDAO {
     
     /// Contract initialization status
     initialized: StorageBool,
+    
+    /// Membership flags (after `initialized` so deployed slots keep their positions)
+    member_flags: StorageMap<Address, StorageBool>,
 }
 
 #[external]
@@ -50,6 +53,55 @@ impl SimpleDAO {
         Ok(())
     }
 
+    /// Add a member by address so it can later be removed (owner only)
+    pub fn register_member(&mut self, member: Address) -> Result<(), Vec<u8>> {
+        if !self.initialized.get() {
+            return Err(b"Not initialized".to_vec());
+        }
+        
+        if msg::sender() != self.owner.get() {
+            return Err(b"Not owner".to_vec());
+        }
+        
+        if self.member_flags.get(member) {
+            return Err(b"Already a member".to_vec());
+        }
+        
+        self.member_flags.setter(member).set(true);
+        let current_count = self.members.get();
+        self.members.set(current_count + U256::from(1));
+        
+        Ok(())
+    }
+
+    /// Remove a member (owner only)
+    pub fn remove_member(&mut self, member: Address) -> Result<(), Vec<u8>> {
+        if !self.initialized.get() {
+            return Err(b"Not initialized".to_vec());
+        }
+        
+        if msg::sender() != self.owner.get() {
+            return Err(b"Not owner".to_vec());
+        }
+        
+        if !self.member_flags.get(member) {
+            return Err(b"Not a member".to_vec());
+        }
+        
+        self.member_flags.setter(member).set(false);
+        let current_count = self.members.get();
+        if current_count > U256::ZERO {
+            self.members.set(current_count - U256::from(1));
+        }
+        
+        Ok(())
+    }
+
+    /// Check if address is a member
+    pub fn is_member(&self, member: Address) -> bool {
+        self.member_flags.get(member)
+    }
+
     /// Get member count
     pub fn member_count(&self) -> U256 {
         self.members.get()
@@ -64,4 +116,40 @@ impl SimpleDAO {
     pub fn owner(&self) -> Address {
         self.owner.get()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::testing::TestVM;
+
+    #[test]
+    fn test_remove_member() {
+        let vm = TestVM::default();
+        let owner = Address::repeat_byte(0x01);
+        let (alice, bob) = (Address::repeat_byte(0x0a), Address::repeat_byte(0x0b));
+        vm.set_sender(owner);
+        let mut dao = SimpleDAO::from(&vm);
+        dao.initialize(owner).unwrap();
+        
+        dao.register_member(alice).unwrap();
+        dao.register_member(bob).unwrap();
+        assert_eq!(dao.member_count(), U256::from(2));
+        
+        dao.remove_member(alice).unwrap();
+        assert_eq!(dao.member_count(), U256::from(1));
+        assert!(!dao.is_member(alice));
+        assert!(dao.is_member(bob));
+        assert_eq!(dao.remove_member(alice), Err(b"Not a member".to_vec()));
+        assert_eq!(dao.member_count(), U256::from(1));
+        
+        vm.set_sender(bob);
+        assert_eq!(dao.remove_member(bob), Err(b"Not owner".to_vec()));
+        assert!(dao.is_member(bob));
+        
+        // The original count-only entrypoint keeps its signature
+        vm.set_sender(owner);
+        dao.add_member().unwrap();
+        assert_eq!(dao.member_count(), U256::from(2));
+    }
+}
@@ -9,6 +9,8 @@ pub use treasury::Treasury;
 pub use shadowid_registry::ShadowIDRegistry;
 pub use zk_integration::ZkVerificationStorage;
 pub use zk_enhanced_dao::ZkEnhancedDAO;
+pub use commitment_tree::CommitmentTree;
+pub use emergency_stop::EmergencyStop;
 
 // Module declarations
 pub mod dao;
@@ -17,7 +19,14 @@ pub mod treasury;
 pub mod shadowid_registry;
 pub mod zk_integration;
 pub mod zk_enhanced_dao;
+pub mod commitment_tree;
+pub mod emergency_stop;
+pub mod math;
 
 // ZK verifier module (referenced by zk_integration)
 #[path = "../zk_verifier.rs"]
-pub mod zk_verifier;
\ No newline at end of file
+pub mod zk_verifier;
+
+// Field element types and proof ids (referenced by commitment_tree and zk_integration)
+#[path = "../zk_noir_verifier.rs"]
+pub mod zk_noir_verifier;
@@ -4,20 +4,34 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
-use stylus_sdk::{prelude::*, storage::StorageU256};
+use stylus_sdk::alloy_primitives::Address;
+use stylus_sdk::{
+    msg,
+    prelude::*,
+    storage::{StorageAddress, StorageBool, StorageU256},
+};
 
 /// Ultra-simple contract that should compile on Windows
 #[entrypoint]
 #[storage]
 pub struct SimpleCounter {
     count: StorageU256,
+    owner: StorageAddress,
+    initialized: StorageBool,
 }
 
 #[external]
 impl SimpleCounter {
-    /// Initialize counter
-    pub fn init(&mut self) {
+    /// Initialize counter and set the caller as owner (once only)
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        if self.initialized.get() {
+            return Err(b"Already initialized".to_vec());
+        }
+        
+        self.owner.set(msg::sender());
+        self.initialized.set(true);
         self.count.set(0);
+        Ok(())
     }
 
     /// Increment counter
@@ -31,8 +45,50 @@ impl SimpleCounter {
         self.count.get()
     }
 
-    /// Reset counter
-    pub fn reset(&mut self) {
+    /// Reset counter (owner only)
+    pub fn reset(&mut self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"Not owner".to_vec());
+        }
+        
         self.count.set(0);
+        Ok(())
     }
-}
\ No newline at end of file
+
+    /// Get owner address
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::testing::TestVM;
+
+    #[test]
+    fn test_init_once_and_owner_only_reset() {
+        let vm = TestVM::default();
+        let owner = Address::repeat_byte(0x01);
+        let other = Address::repeat_byte(0x02);
+        vm.set_sender(owner);
+        let mut counter = SimpleCounter::from(&vm);
+        counter.init().unwrap();
+        counter.increment();
+        counter.increment();
+
+        // A second init, by anyone, cannot take ownership or wipe the count
+        vm.set_sender(other);
+        assert_eq!(counter.init(), Err(b"Already initialized".to_vec()));
+        assert_eq!(counter.owner(), owner);
+        assert_eq!(counter.get_count(), 2);
+
+        // Anyone may count, only the owner may reset
+        counter.increment();
+        assert_eq!(counter.reset(), Err(b"Not owner".to_vec()));
+        assert_eq!(counter.get_count(), 3);
+
+        vm.set_sender(owner);
+        counter.reset().unwrap();
+        assert_eq!(counter.get_count(), 0);
+    }
+}
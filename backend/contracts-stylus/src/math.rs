@@ -0,0 +1,48 @@
+//! Shared fixed-width math helpers for the DAO ecosystem contracts
+//! Integer square root for quadratic voting and quadratic funding.
+
+use stylus_sdk::alloy_primitives::U256;
+
+/// Floor of the square root of `x`: exact for perfect squares, rounded down otherwise
+pub fn u256_sqrt(x: U256) -> U256 {
+    if x < U256::from(2) {
+        return x;
+    }
+
+    // 2^ceil(bits/2) is at least sqrt(x), so Newton's iteration decreases monotonically.
+    // While root >= sqrt(x), x / root <= root < 2^129, so root + x / root cannot overflow.
+    let mut root = U256::from(1) << ((x.bit_len() + 1) / 2);
+    loop {
+        let next = (root + x / root) >> 1;
+        if next >= root {
+            return root;
+        }
+        root = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u256_sqrt() {
+        assert_eq!(u256_sqrt(U256::ZERO), U256::ZERO);
+        assert_eq!(u256_sqrt(U256::from(1)), U256::from(1));
+
+        // Perfect squares are exact, their neighbours floor
+        for n in [2u64, 3, 10, 99, 1_000, 65_535, 4_294_967_295] {
+            let square = U256::from(n) * U256::from(n);
+            assert_eq!(u256_sqrt(square), U256::from(n));
+            assert_eq!(u256_sqrt(square - U256::from(1)), U256::from(n - 1));
+            assert_eq!(u256_sqrt(square + U256::from(1)), U256::from(n));
+        }
+
+        // Largest root that fits: (2^128 - 1)^2 sits just below U256::MAX
+        let max_root = (U256::from(1) << 128) - U256::from(1);
+        assert_eq!(u256_sqrt(U256::MAX), max_root);
+        assert_eq!(u256_sqrt(max_root * max_root), max_root);
+        assert_eq!(u256_sqrt(max_root * max_root - U256::from(1)), max_root - U256::from(1));
+        assert_eq!(u256_sqrt(U256::MAX - U256::from(1)), max_root);
+    }
+}
@@ -223,6 +223,11 @@ impl ShadowIDRegistry {
         (user_data.verified, user_data.kyc_commitment, user_data.proof_hash)
     }
 
+    /// Get the KYC commitment currently registered for a user
+    pub fn get_commitment(&self, user: Address) -> [u8; 32] {
+        self.users.get(user).kyc_commitment
+    }
+
     /// Get user badge information
     pub fn get_badge(&self, user: Address) -> (U256, U256, u8, bool) {
         let badge = self.badges.get(user);
@@ -8,11 +8,11 @@ extern crate alloc;
 
 use alloc::{string::String, vec::Vec};
 use stylus_sdk::{
-    alloy_primitives::{Address, U256},
+    alloy_primitives::{Address, B256, FixedBytes, U256},
     alloy_sol_types::{sol, SolEvent},
-    block, msg,
+    block, contract, msg,
     prelude::*,
-    call::Call,
+    call::{transfer_eth, Call, StaticCall},
 };
 
 // Treasury Events using sol! macro
@@ -22,13 +22,87 @@ sol! {
     event WithdrawnETH(address indexed to, uint256 amount);
     event DepositedERC20(address indexed token, address indexed from, uint256 amount);
     event WithdrawnERC20(address indexed token, address indexed to, uint256 amount);
-    event WithdrawalQueued(uint256 indexed withdrawalId, address indexed recipient, uint256 amount, uint256 unlockTime);
+    event WithdrawalQueued(uint256 indexed withdrawalId, address indexed recipient, uint256 amount, uint256 unlockTime, string memo);
     event WithdrawalExecuted(uint256 indexed withdrawalId, address indexed recipient, uint256 amount);
     event WithdrawalCancelled(uint256 indexed withdrawalId);
+    event LockedEthUpdated(uint256 lockedEth);
+    event WithdrawalCredited(uint256 indexed withdrawalId, address indexed recipient, uint256 amount);
+    event Claimed(address indexed recipient, uint256 amount);
     event WithdrawalDelayUpdated(uint256 oldDelay, uint256 newDelay);
     event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+    event DaoUpdated(address indexed previousDao, address indexed newDao);
+    event EmergencyStopContractUpdated(address indexed oldContract, address indexed newContract);
+    event ContributionRefunded(address indexed contributor, uint256 amount, uint256 remaining);
     event Paused(address account);
     event Unpaused(address account);
+
+    // Treasury Errors
+    error NotOwner(address caller);
+    error NotDaoOrOwner(address caller);
+    error ContractPaused();
+    error ReentrantCall();
+    error InvalidAddress();
+    error ZeroAmount();
+    error InsufficientBalance(uint256 available, uint256 required);
+    error TokenTransferFailed(address token);
+    error TokenBalanceUnavailable(address token);
+    error WithdrawalNotFound(uint256 withdrawalId);
+    error WithdrawalNotReady(uint256 withdrawalId, uint256 unlockTime);
+    error WithdrawalExpired(uint256 withdrawalId);
+    error WithdrawalNotExpired(uint256 withdrawalId);
+    error AlreadyExecuted(uint256 withdrawalId);
+    error AlreadyCancelled(uint256 withdrawalId);
+    error InvalidDelay(uint256 delay);
+    error InvalidStatusFilter(uint8 statusFilter);
+    error RefundExceedsContribution(address contributor, uint256 contributed, uint256 requested);
+    error EmergencyStopped();
+    error EmergencyStopCheckFailed(address coordinator);
+    error PermitFailed(address token);
+    error NothingToClaim(address recipient);
+    error MemoTooLong(uint256 length, uint256 maxLength);
+    error EthTransferFailed(address recipient, uint256 amount);
+}
+
+/// ABI-decodable revert reasons returned by the Treasury
+#[derive(SolidityError)]
+pub enum TreasuryError {
+    NotOwner(NotOwner),
+    NotDaoOrOwner(NotDaoOrOwner),
+    Paused(ContractPaused),
+    ReentrantCall(ReentrantCall),
+    InvalidAddress(InvalidAddress),
+    ZeroAmount(ZeroAmount),
+    InsufficientBalance(InsufficientBalance),
+    TokenTransferFailed(TokenTransferFailed),
+    TokenBalanceUnavailable(TokenBalanceUnavailable),
+    WithdrawalNotFound(WithdrawalNotFound),
+    WithdrawalNotReady(WithdrawalNotReady),
+    WithdrawalExpired(WithdrawalExpired),
+    WithdrawalNotExpired(WithdrawalNotExpired),
+    AlreadyExecuted(AlreadyExecuted),
+    AlreadyCancelled(AlreadyCancelled),
+    InvalidDelay(InvalidDelay),
+    InvalidStatusFilter(InvalidStatusFilter),
+    RefundExceedsContribution(RefundExceedsContribution),
+    EmergencyStopped(EmergencyStopped),
+    EmergencyStopCheckFailed(EmergencyStopCheckFailed),
+    PermitFailed(PermitFailed),
+    NothingToClaim(NothingToClaim),
+    MemoTooLong(MemoTooLong),
+    EthTransferFailed(EthTransferFailed),
+}
+
+// Shared circuit breaker consulted by state-changing entrypoints,
+// and the EIP-2612 extension used for single-transaction deposits
+sol_interface! {
+    interface IEmergencyStop {
+        function isStopped() external view returns (bool);
+    }
+
+    interface IERC20Permit {
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
 }
 
 // Struct for queued withdrawals with timelock
@@ -39,6 +113,8 @@ pub struct QueuedWithdrawal {
     unlock_time: U256,
     executed: bool,
     cancelled: bool,
+    pull: bool, // Credit `claimable` on execution instead of pushing ETH
+    memo: String, // Purpose of the withdrawal, for treasury accounting
 }
 
 // Main Treasury contract storage
@@ -47,13 +123,26 @@ pub struct QueuedWithdrawal {
 pub struct Treasury {
     // Ownership and access control
     owner: sol_storage::Value<Address>,
+    dao: sol_storage::Value<Address>,
     paused: sol_storage::Value<bool>,
+    emergency_stop: sol_storage::Value<Address>, // Shared EmergencyStop coordinator (zero = none)
     reentrancy_guard: sol_storage::ReentrancyGuard,
 
     // Treasury-specific state
     withdrawal_delay: sol_storage::Value<U256>,
     withdrawal_count: sol_storage::Value<U256>,
     queued_withdrawals: sol_storage::Mapping<U256, QueuedWithdrawal>,
+    locked_eth: sol_storage::Value<U256>, // ETH earmarked by queued withdrawals and unclaimed credits
+
+    // Executed pull-mode withdrawals awaiting claim()
+    claimable: sol_storage::Mapping<Address, U256>,
+
+    // Registry of ERC20 tokens currently held
+    held_tokens: sol_storage::StorageVec<Address>,
+    is_held_token: sol_storage::Mapping<Address, bool>,
+
+    // Per-depositor ETH ledger for refundable contributions
+    contributions: sol_storage::Mapping<Address, U256>,
 }
 
 // Time constants
@@ -61,14 +150,29 @@ const ONE_HOUR: u64 = 60 * 60;
 const ONE_DAY: u64 = 24 * ONE_HOUR;
 const MIN_WITHDRAWAL_DELAY: u64 = ONE_HOUR;
 const MAX_WITHDRAWAL_DELAY: u64 = 30 * ONE_DAY;
+const WITHDRAWAL_GRACE_PERIOD: u64 = 14 * ONE_DAY; // Window to execute after unlock
+
+// Longest withdrawal memo accepted, in bytes
+const MAX_MEMO_LENGTH: usize = 256;
+
+// Withdrawal status filters for get_withdrawals
+const STATUS_PENDING: u8 = 0;
+const STATUS_EXECUTED: u8 = 1;
+const STATUS_CANCELLED: u8 = 2;
+const STATUS_ALL: u8 = 3;
+
+// ERC-165 interface ids
+const ERC165_INTERFACE_ID: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+// XOR of deposit, depositErc20, queueWithdrawal, executeWithdrawal, cancelWithdrawal, balance
+const TREASURY_INTERFACE_ID: [u8; 4] = [0x44, 0xd7, 0x37, 0x2b];
 
 // External interface implementation
 #[external]
 impl Treasury {
-    /// Initialize the Treasury contract with an initial owner
-    pub fn init(&mut self, initial_owner: Address) -> Result<(), Vec<u8>> {
+    /// Initialize the Treasury contract with an initial owner (link the spending DAO with set_dao)
+    pub fn init(&mut self, initial_owner: Address) -> Result<(), TreasuryError> {
         if initial_owner == Address::ZERO {
-            return Err(b"Invalid owner address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
 
         self.owner.set(initial_owner);
@@ -89,14 +193,18 @@ impl Treasury {
 
     /// Deposit ETH into the treasury (payable function)
     #[payable]
-    pub fn deposit(&mut self) -> Result<(), Vec<u8>> {
+    pub fn deposit(&mut self) -> Result<(), TreasuryError> {
         self.when_not_paused()?;
         
         let amount = msg::value();
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
         
+        let contributor = msg::sender();
+        let credited = self.contributions.get(contributor) + amount;
+        self.contributions.setter(contributor).set(credited);
+        
         evm::log(DepositedETH {
             from: msg::sender(),
             amount,
@@ -106,106 +214,177 @@ impl Treasury {
 
     /// Alternative deposit function name for compatibility
     #[payable]
-    pub fn deposit_eth(&mut self) -> Result<(), Vec<u8>> {
+    pub fn deposit_eth(&mut self) -> Result<(), TreasuryError> {
         self.deposit()
     }
 
     /// Direct ETH withdrawal (owner only, for emergencies)
-    pub fn withdraw_eth(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+    pub fn withdraw_eth(&mut self, to: Address, amount: U256) -> Result<(), TreasuryError> {
         self.only_owner()?;
         self.when_not_paused()?;
-        let _guard = self.reentrancy_guard.guard()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
 
         if to == Address::ZERO {
-            return Err(b"Invalid recipient address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
 
-        let contract_balance = self.get_eth_balance();
-        if contract_balance < amount {
-            return Err(b"Insufficient ETH balance".to_vec());
+        // ETH earmarked by queued withdrawals and unclaimed credits is not spendable here
+        let available = self.available_eth();
+        if available < amount {
+            return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+                available,
+                required: amount,
+            }));
         }
 
         self._process_eth_withdrawal(to, amount)?;
         Ok(())
     }
 
-    // ========================================================================
-    // ERC20 TOKEN HANDLING FUNCTIONS
-    // ========================================================================
-
-    /// Deposit ERC20 tokens into the treasury
-    pub fn deposit_erc20(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+    /// Refund part of a depositor's recorded contribution (owner only)
+    pub fn refund_contribution(&mut self, to: Address, amount: U256) -> Result<(), TreasuryError> {
+        self.only_owner()?;
         self.when_not_paused()?;
-        let _guard = self.reentrancy_guard.guard()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
 
-        if token == Address::ZERO {
-            return Err(b"Invalid token address".to_vec());
+        if to == Address::ZERO {
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
 
-        // Transfer tokens from sender to treasury
-        let token_contract = IERC20::new(token);
-        let success = token_contract
-            .transfer_from(Call::new(), msg::sender(), address(), amount)
-            .map_err(|_| b"Token transfer failed".to_vec())?;
-        
-        if !success {
-            return Err(b"Token transfer failed".to_vec());
+        let remaining = debit_contribution(to, self.contributions.get(to), amount)?;
+
+        let available = self.available_eth();
+        if available < amount {
+            return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+                available,
+                required: amount,
+            }));
         }
 
-        evm::log(DepositedERC20 {
-            token,
-            from: msg::sender(),
+        // Update the ledger before sending ETH (CEI pattern)
+        self.contributions.setter(to).set(remaining);
+        self._process_eth_withdrawal(to, amount)?;
+
+        evm::log(ContributionRefunded {
+            contributor: to,
             amount,
+            remaining,
         });
         Ok(())
     }
 
+    // ========================================================================
+    // ERC20 TOKEN HANDLING FUNCTIONS
+    // ========================================================================
+
+    /// Deposit ERC20 tokens into the treasury
+    pub fn deposit_erc20(&mut self, token: Address, amount: U256) -> Result<(), TreasuryError> {
+        self.when_not_paused()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
+
+        self._deposit_erc20(token, amount)
+    }
+
+    /// Deposit ERC20 tokens using an EIP-2612 permit instead of a prior approve
+    pub fn deposit_erc20_with_permit(
+        &mut self,
+        token: Address,
+        amount: U256,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), TreasuryError> {
+        self.when_not_paused()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
+
+        // A permit can fail because someone front-ran it with the same signature, which still
+        // granted the allowance; only fail when no sufficient allowance exists either
+        let permit_token = IERC20Permit::new(token);
+        let owner = msg::sender();
+        if permit_token
+            .permit(Call::new(), owner, address(), amount, deadline, v, r, s)
+            .is_err()
+        {
+            let allowance = permit_token
+                .allowance(StaticCall::new(), owner, address())
+                .unwrap_or(U256::ZERO);
+            if allowance < amount {
+                return Err(TreasuryError::PermitFailed(PermitFailed { token }));
+            }
+        }
+
+        self._deposit_erc20(token, amount)
+    }
+
     /// Execute ERC20 token transfer (DAO interface - returns 0 for immediate execution)
     pub fn execute_token(
         &mut self,
         to: Address,
         token: Address,
         amount: U256,
-    ) -> Result<U256, Vec<u8>> {
-        self.only_owner()?;
+    ) -> Result<U256, TreasuryError> {
+        self.only_dao_or_owner()?;
         self.when_not_paused()?;
-        let _guard = self.reentrancy_guard.guard()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
 
         if token == Address::ZERO {
-            return Err(b"Invalid token address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if to == Address::ZERO {
-            return Err(b"Invalid recipient address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
 
         // Check token balance
         let token_contract = IERC20::new(token);
         let balance = token_contract
             .balance_of(Call::new(), address())
-            .map_err(|_| b"Failed to get token balance".to_vec())?;
+            .map_err(|_| TreasuryError::TokenBalanceUnavailable(TokenBalanceUnavailable {
+                token,
+            }))?;
         
         if balance < amount {
-            return Err(b"Insufficient token balance".to_vec());
+            return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+                available: balance,
+                required: amount,
+            }));
         }
 
         // Execute transfer
         let success = token_contract
             .transfer(Call::new(), to, amount)
-            .map_err(|_| b"Token transfer failed".to_vec())?;
+            .map_err(|_| TreasuryError::TokenTransferFailed(TokenTransferFailed { token }))?;
         
         if !success {
-            return Err(b"Token transfer failed".to_vec());
+            return Err(TreasuryError::TokenTransferFailed(TokenTransferFailed { token }));
         }
 
+        self._untrack_token_if_empty(token, balance - amount);
+
         evm::log(WithdrawnERC20 { token, to, amount });
         Ok(U256::ZERO) // Return 0 for immediate execution (no withdrawal ID)
     }
@@ -216,39 +395,49 @@ impl Treasury {
         token: Address,
         to: Address,
         amount: U256,
-    ) -> Result<(), Vec<u8>> {
+    ) -> Result<(), TreasuryError> {
         self.only_owner()?;
         self.when_not_paused()?;
-        let _guard = self.reentrancy_guard.guard()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
 
         if token == Address::ZERO {
-            return Err(b"Invalid token address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if to == Address::ZERO {
-            return Err(b"Invalid recipient address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
 
         // Check and execute token transfer
         let token_contract = IERC20::new(token);
         let balance = token_contract
             .balance_of(Call::new(), address())
-            .map_err(|_| b"Failed to get token balance".to_vec())?;
+            .map_err(|_| TreasuryError::TokenBalanceUnavailable(TokenBalanceUnavailable {
+                token,
+            }))?;
         
         if balance < amount {
-            return Err(b"Insufficient token balance".to_vec());
+            return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+                available: balance,
+                required: amount,
+            }));
         }
 
         let success = token_contract
             .transfer(Call::new(), to, amount)
-            .map_err(|_| b"Token transfer failed".to_vec())?;
+            .map_err(|_| TreasuryError::TokenTransferFailed(TokenTransferFailed { token }))?;
         
         if !success {
-            return Err(b"Token transfer failed".to_vec());
+            return Err(TreasuryError::TokenTransferFailed(TokenTransferFailed { token }));
         }
 
+        self._untrack_token_if_empty(token, balance - amount);
+
         evm::log(WithdrawnERC20 { token, to, amount });
         Ok(())
     }
@@ -257,86 +446,90 @@ impl Treasury {
     // TIMELOCK QUEUE SYSTEM (DAO INTERFACE)
     // ========================================================================
 
-    /// Queue a withdrawal with timelock (matches DAO interface)
+    /// Queue a withdrawal with timelock (matches DAO interface); `memo` records its purpose
     pub fn queue_withdrawal(
         &mut self,
         recipient: Address,
         amount: U256,
-    ) -> Result<U256, Vec<u8>> {
-        self.only_owner()?;
-        self.when_not_paused()?;
-
-        if recipient == Address::ZERO {
-            return Err(b"Invalid recipient".to_vec());
-        }
-        if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
-        }
-
-        let contract_balance = self.get_eth_balance();
-        if contract_balance < amount {
-            return Err(b"Insufficient balance".to_vec());
-        }
-
-        // Create new withdrawal
-        let withdrawal_id = self.withdrawal_count.get() + U256::from(1);
-        self.withdrawal_count.set(withdrawal_id);
-
-        let unlock_time = block::timestamp() + self.withdrawal_delay.get();
-
-        let queued_withdrawal = QueuedWithdrawal {
-            recipient,
-            amount,
-            unlock_time,
-            executed: false,
-            cancelled: false,
-        };
-
-        self.queued_withdrawals.setter(withdrawal_id).set(queued_withdrawal);
-
-        evm::log(WithdrawalQueued {
-            withdrawalId: withdrawal_id,
-            recipient,
-            amount,
-            unlockTime: unlock_time,
-        });
+        memo: String,
+    ) -> Result<U256, TreasuryError> {
+        self._queue_withdrawal(recipient, amount, memo, false)
+    }
 
-        Ok(withdrawal_id)
+    /// Queue a withdrawal the recipient pulls with `claim()` once executed, for recipients
+    /// that cannot accept a pushed ETH transfer
+    pub fn queue_pull_withdrawal(
+        &mut self,
+        recipient: Address,
+        amount: U256,
+        memo: String,
+    ) -> Result<U256, TreasuryError> {
+        self._queue_withdrawal(recipient, amount, memo, true)
     }
 
     /// Execute a queued withdrawal (matches DAO interface)
-    pub fn execute_withdrawal(&mut self, withdrawal_id: U256) -> Result<(), Vec<u8>> {
-        self.only_owner()?;
+    pub fn execute_withdrawal(&mut self, withdrawal_id: U256) -> Result<(), TreasuryError> {
+        self.only_dao_or_owner()?;
         self.when_not_paused()?;
-        let _guard = self.reentrancy_guard.guard()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
 
         let mut withdrawal = self.queued_withdrawals.get(withdrawal_id);
 
         // Validate withdrawal
         if withdrawal.unlock_time == U256::ZERO {
-            return Err(b"Withdrawal does not exist".to_vec());
+            return Err(TreasuryError::WithdrawalNotFound(WithdrawalNotFound {
+                withdrawalId: withdrawal_id,
+            }));
         }
         if withdrawal.executed {
-            return Err(b"Already executed".to_vec());
+            return Err(TreasuryError::AlreadyExecuted(AlreadyExecuted {
+                withdrawalId: withdrawal_id,
+            }));
         }
         if withdrawal.cancelled {
-            return Err(b"Withdrawal cancelled".to_vec());
+            return Err(TreasuryError::AlreadyCancelled(AlreadyCancelled {
+                withdrawalId: withdrawal_id,
+            }));
         }
         if block::timestamp() < withdrawal.unlock_time {
-            return Err(b"Not unlocked yet".to_vec());
+            return Err(TreasuryError::WithdrawalNotReady(WithdrawalNotReady {
+                withdrawalId: withdrawal_id,
+                unlockTime: withdrawal.unlock_time,
+            }));
+        }
+        if self._is_expired(&withdrawal) {
+            return Err(TreasuryError::WithdrawalExpired(WithdrawalExpired {
+                withdrawalId: withdrawal_id,
+            }));
         }
 
         let contract_balance = self.get_eth_balance();
         if contract_balance < withdrawal.amount {
-            return Err(b"Insufficient balance".to_vec());
+            return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+                available: contract_balance,
+                required: withdrawal.amount,
+            }));
         }
 
         // Mark as executed before external call (CEI pattern)
         withdrawal.executed = true;
         self.queued_withdrawals.setter(withdrawal_id).set(withdrawal.clone());
 
-        // Execute withdrawal
-        self._process_eth_withdrawal(withdrawal.recipient, withdrawal.amount)?;
+        if withdrawal.pull {
+            // Funds stay locked until the recipient claims them
+            self._credit_claimable(withdrawal.recipient, withdrawal.amount);
+            evm::log(WithdrawalCredited {
+                withdrawalId: withdrawal_id,
+                recipient: withdrawal.recipient,
+                amount: withdrawal.amount,
+            });
+        } else {
+            self._set_locked_eth(self.locked_eth.get().saturating_sub(withdrawal.amount));
+            self._process_eth_withdrawal(withdrawal.recipient, withdrawal.amount)?;
+        }
 
         evm::log(WithdrawalExecuted {
             withdrawalId: withdrawal_id,
@@ -347,24 +540,50 @@ impl Treasury {
         Ok(())
     }
 
+    /// Withdraw all ETH credited to the caller by executed pull-mode withdrawals
+    pub fn claim(&mut self) -> Result<U256, TreasuryError> {
+        self.when_not_paused()?;
+        let _guard = self
+            .reentrancy_guard
+            .guard()
+            .map_err(|_| TreasuryError::ReentrantCall(ReentrantCall {}))?;
+
+        let recipient = msg::sender();
+        let amount = self._take_claimable(recipient)?;
+        self._set_locked_eth(self.locked_eth.get().saturating_sub(amount));
+
+        self._process_eth_withdrawal(recipient, amount)?;
+
+        evm::log(Claimed { recipient, amount });
+        Ok(amount)
+    }
+
     /// Cancel a queued withdrawal before execution
-    pub fn cancel_withdrawal(&mut self, withdrawal_id: U256) -> Result<(), Vec<u8>> {
+    pub fn cancel_withdrawal(&mut self, withdrawal_id: U256) -> Result<(), TreasuryError> {
         self.only_owner()?;
 
         let mut withdrawal = self.queued_withdrawals.get(withdrawal_id);
 
         if withdrawal.unlock_time == U256::ZERO {
-            return Err(b"Withdrawal does not exist".to_vec());
+            return Err(TreasuryError::WithdrawalNotFound(WithdrawalNotFound {
+                withdrawalId: withdrawal_id,
+            }));
         }
         if withdrawal.executed {
-            return Err(b"Already executed".to_vec());
+            return Err(TreasuryError::AlreadyExecuted(AlreadyExecuted {
+                withdrawalId: withdrawal_id,
+            }));
         }
         if withdrawal.cancelled {
-            return Err(b"Already cancelled".to_vec());
+            return Err(TreasuryError::AlreadyCancelled(AlreadyCancelled {
+                withdrawalId: withdrawal_id,
+            }));
         }
 
         withdrawal.cancelled = true;
+        let amount = withdrawal.amount;
         self.queued_withdrawals.setter(withdrawal_id).set(withdrawal);
+        self._set_locked_eth(self.locked_eth.get().saturating_sub(amount));
 
         evm::log(WithdrawalCancelled {
             withdrawalId: withdrawal_id,
@@ -373,32 +592,97 @@ impl Treasury {
         Ok(())
     }
 
+    /// Requeue an expired, unexecuted and uncancelled withdrawal under its original ID
+    pub fn requeue_withdrawal(&mut self, withdrawal_id: U256) -> Result<(), TreasuryError> {
+        self.only_owner()?;
+        self.when_not_paused()?;
+
+        let mut withdrawal = self.queued_withdrawals.get(withdrawal_id);
+
+        if withdrawal.unlock_time == U256::ZERO {
+            return Err(TreasuryError::WithdrawalNotFound(WithdrawalNotFound {
+                withdrawalId: withdrawal_id,
+            }));
+        }
+        if withdrawal.executed {
+            return Err(TreasuryError::AlreadyExecuted(AlreadyExecuted {
+                withdrawalId: withdrawal_id,
+            }));
+        }
+        // A cancelled withdrawal has released its locked ETH; queue a new one instead
+        if withdrawal.cancelled {
+            return Err(TreasuryError::AlreadyCancelled(AlreadyCancelled {
+                withdrawalId: withdrawal_id,
+            }));
+        }
+        if !self._is_expired(&withdrawal) {
+            return Err(TreasuryError::WithdrawalNotExpired(WithdrawalNotExpired {
+                withdrawalId: withdrawal_id,
+            }));
+        }
+
+        // An expired withdrawal still holds its lock, so only the unlock time moves
+        let unlock_time = block::timestamp() + self.withdrawal_delay.get();
+        withdrawal.unlock_time = unlock_time;
+        self.queued_withdrawals.setter(withdrawal_id).set(withdrawal.clone());
+
+        evm::log(WithdrawalQueued {
+            withdrawalId: withdrawal_id,
+            recipient: withdrawal.recipient,
+            amount: withdrawal.amount,
+            unlockTime: unlock_time,
+            memo: withdrawal.memo,
+        });
+
+        Ok(())
+    }
+
     // ========================================================================
     // ADMIN FUNCTIONS
     // ========================================================================
 
-    /// Update the withdrawal delay with validation
-    pub fn set_withdrawal_delay(&mut self, delay: U256) -> Result<(), Vec<u8>> {
-        self.only_owner()?;
+    /// Update the withdrawal delay with validation (DAO or owner, so a passed proposal can
+    /// adjust the timelock)
+    pub fn set_withdrawal_delay(&mut self, delay: U256) -> Result<(), TreasuryError> {
+        self.only_dao_or_owner()?;
+        self._set_withdrawal_delay(delay)
+    }
 
-        let delay_seconds = delay.to::<u64>();
-        if delay_seconds < MIN_WITHDRAWAL_DELAY || delay_seconds > MAX_WITHDRAWAL_DELAY {
-            return Err(b"Invalid delay".to_vec());
+    /// Set the DAO contract allowed to move funds (owner only)
+    pub fn set_dao(&mut self, new_dao: Address) -> Result<(), TreasuryError> {
+        self.only_owner()?;
+        if new_dao == Address::ZERO {
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
 
-        let old_delay = self.withdrawal_delay.get();
-        self.withdrawal_delay.set(delay);
+        let previous_dao = self.dao.get();
+        self.dao.set(new_dao);
 
-        evm::log(WithdrawalDelayUpdated {
-            oldDelay: old_delay,
-            newDelay: delay,
+        evm::log(DaoUpdated {
+            previousDao: previous_dao,
+            newDao: new_dao,
+        });
+
+        Ok(())
+    }
+
+    /// Point the Treasury at a shared EmergencyStop coordinator, or zero to detach (owner only)
+    pub fn set_emergency_stop_contract(&mut self, new_contract: Address) -> Result<(), TreasuryError> {
+        self.only_owner()?;
+
+        let old_contract = self.emergency_stop.get();
+        self.emergency_stop.set(new_contract);
+
+        evm::log(EmergencyStopContractUpdated {
+            oldContract: old_contract,
+            newContract: new_contract,
         });
 
         Ok(())
     }
 
     /// Pause the contract (owner only)
-    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+    pub fn pause(&mut self) -> Result<(), TreasuryError> {
         self.only_owner()?;
         self.paused.set(true);
         evm::log(Paused {
@@ -408,7 +692,7 @@ impl Treasury {
     }
 
     /// Unpause the contract (owner only)
-    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+    pub fn unpause(&mut self) -> Result<(), TreasuryError> {
         self.only_owner()?;
         self.paused.set(false);
         evm::log(Unpaused {
@@ -418,11 +702,11 @@ impl Treasury {
     }
 
     /// Transfer ownership to a new address
-    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), TreasuryError> {
         self.only_owner()?;
 
         if new_owner == Address::ZERO {
-            return Err(b"New owner is the zero address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
 
         let previous_owner = self.owner.get();
@@ -441,19 +725,22 @@ impl Treasury {
     // ========================================================================
 
     /// Emergency ETH withdrawal bypassing normal controls
-    pub fn emergency_withdraw(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+    pub fn emergency_withdraw(&mut self, to: Address, amount: U256) -> Result<(), TreasuryError> {
         self.only_owner()?;
 
         if to == Address::ZERO {
-            return Err(b"Invalid recipient".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
 
         let contract_balance = self.get_eth_balance();
         if contract_balance < amount {
-            return Err(b"Insufficient balance".to_vec());
+            return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+                available: contract_balance,
+                required: amount,
+            }));
         }
 
         self._process_eth_withdrawal(to, amount)?;
@@ -466,28 +753,35 @@ impl Treasury {
         token: Address,
         to: Address,
         amount: U256,
-    ) -> Result<(), Vec<u8>> {
+    ) -> Result<(), TreasuryError> {
         self.only_owner()?;
 
         if token == Address::ZERO {
-            return Err(b"Invalid token".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if to == Address::ZERO {
-            return Err(b"Invalid recipient".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
         if amount == U256::ZERO {
-            return Err(b"Amount must be greater than 0".to_vec());
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
         }
 
         let token_contract = IERC20::new(token);
         let success = token_contract
             .transfer(Call::new(), to, amount)
-            .map_err(|_| b"Token transfer failed".to_vec())?;
+            .map_err(|_| TreasuryError::TokenTransferFailed(TokenTransferFailed { token }))?;
         
         if !success {
-            return Err(b"Token transfer failed".to_vec());
+            return Err(TreasuryError::TokenTransferFailed(TokenTransferFailed { token }));
         }
 
+        let remaining = token_contract
+            .balance_of(Call::new(), address())
+            .map_err(|_| TreasuryError::TokenBalanceUnavailable(TokenBalanceUnavailable {
+                token,
+            }))?;
+        self._untrack_token_if_empty(token, remaining);
+
         evm::log(WithdrawnERC20 { token, to, amount });
         Ok(())
     }
@@ -506,23 +800,64 @@ impl Treasury {
         self.get_eth_balance()
     }
 
+    /// Get ETH earmarked by queued, unexecuted withdrawals
+    pub fn locked_eth(&self) -> U256 {
+        self.locked_eth.get()
+    }
+
+    /// Get ETH credited to a recipient and awaiting `claim()`
+    pub fn claimable_of(&self, recipient: Address) -> U256 {
+        self.claimable.get(recipient)
+    }
+
+    /// Check whether a queued withdrawal pays out via `claim()` rather than a push
+    pub fn is_pull_withdrawal(&self, withdrawal_id: U256) -> bool {
+        self.queued_withdrawals.get(withdrawal_id).pull
+    }
+
+    /// Get ETH not yet committed to a queued withdrawal
+    pub fn available_eth(&self) -> U256 {
+        self.get_eth_balance().saturating_sub(self.locked_eth.get())
+    }
+
     /// Get ERC20 token balance
-    pub fn token_balance(&self, token: Address) -> Result<U256, Vec<u8>> {
+    pub fn token_balance(&self, token: Address) -> Result<U256, TreasuryError> {
         if token == Address::ZERO {
-            return Err(b"Invalid token address".to_vec());
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
         }
 
         let token_contract = IERC20::new(token);
         token_contract
             .balance_of(Call::new(), address())
-            .map_err(|_| b"Failed to get token balance".to_vec())
+            .map_err(|_| TreasuryError::TokenBalanceUnavailable(TokenBalanceUnavailable { token }))
     }
 
     /// Get ERC20 token balance (alternative name)
-    pub fn balance_erc20(&self, token: Address) -> Result<U256, Vec<u8>> {
+    pub fn balance_erc20(&self, token: Address) -> Result<U256, TreasuryError> {
         self.token_balance(token)
     }
 
+    /// Get the ETH a depositor has contributed via deposit, net of refunds
+    pub fn contribution_of(&self, contributor: Address) -> U256 {
+        self.contributions.get(contributor)
+    }
+
+    /// Get all ERC20 tokens currently held by the treasury
+    pub fn get_held_tokens(&self) -> Vec<Address> {
+        let mut result = Vec::new();
+        for i in 0..self.held_tokens.len() {
+            if let Some(token) = self.held_tokens.get(i) {
+                result.push(token);
+            }
+        }
+        result
+    }
+
+    /// Get number of ERC20 tokens currently held
+    pub fn held_token_count(&self) -> U256 {
+        U256::from(self.held_tokens.len())
+    }
+
     /// Get withdrawal details for compatibility
     pub fn pending_withdrawals(&self, withdrawal_id: U256) -> (U256, Address, U256, U256, bool) {
         let withdrawal = self.queued_withdrawals.get(withdrawal_id);
@@ -536,7 +871,10 @@ impl Treasury {
     }
 
     /// Get complete withdrawal details
-    pub fn get_withdrawal(&self, withdrawal_id: U256) -> (Address, U256, U256, bool, bool) {
+    pub fn get_withdrawal(
+        &self,
+        withdrawal_id: U256,
+    ) -> (Address, U256, U256, bool, bool, String) {
         let withdrawal = self.queued_withdrawals.get(withdrawal_id);
         (
             withdrawal.recipient,
@@ -544,6 +882,7 @@ impl Treasury {
             withdrawal.unlock_time,
             withdrawal.executed,
             withdrawal.cancelled,
+            withdrawal.memo,
         )
     }
 
@@ -554,6 +893,20 @@ impl Treasury {
             && !withdrawal.executed
             && !withdrawal.cancelled
             && block::timestamp() >= withdrawal.unlock_time
+            && !self._is_expired(&withdrawal)
+    }
+
+    /// Check if a withdrawal's execution window has passed
+    pub fn is_withdrawal_expired(&self, withdrawal_id: U256) -> bool {
+        let withdrawal = self.queued_withdrawals.get(withdrawal_id);
+        withdrawal.unlock_time > U256::ZERO
+            && !withdrawal.executed
+            && self._is_expired(&withdrawal)
+    }
+
+    /// Get the window after unlock during which a withdrawal can be executed
+    pub fn withdrawal_grace_period(&self) -> U256 {
+        U256::from(WITHDRAWAL_GRACE_PERIOD)
     }
 
     /// Get all pending withdrawal IDs
@@ -570,16 +923,72 @@ impl Treasury {
         result
     }
 
+    /// Get a page of withdrawals matching a status filter
+    /// (0: pending, 1: executed, 2: cancelled, 3: all)
+    pub fn get_withdrawals(
+        &self,
+        offset: U256,
+        limit: U256,
+        status_filter: u8,
+    ) -> Result<Vec<(U256, Address, U256, U256, bool, bool)>, TreasuryError> {
+        if status_filter > STATUS_ALL {
+            return Err(TreasuryError::InvalidStatusFilter(InvalidStatusFilter {
+                statusFilter: status_filter,
+            }));
+        }
+
+        let mut result = Vec::new();
+        let mut skipped = U256::ZERO;
+        let total_count = self.withdrawal_count.get();
+
+        for i in 1..=total_count.to::<u64>() {
+            if U256::from(result.len()) >= limit {
+                break;
+            }
+
+            let withdrawal_id = U256::from(i);
+            let withdrawal = self.queued_withdrawals.get(withdrawal_id);
+            if !matches_status(withdrawal.executed, withdrawal.cancelled, status_filter) {
+                continue;
+            }
+
+            if skipped < offset {
+                skipped += U256::from(1);
+                continue;
+            }
+
+            result.push((
+                withdrawal_id,
+                withdrawal.recipient,
+                withdrawal.amount,
+                withdrawal.unlock_time,
+                withdrawal.executed,
+                withdrawal.cancelled,
+            ));
+        }
+        Ok(result)
+    }
+
     /// Get current owner
     pub fn owner(&self) -> Address {
         self.owner.get()
     }
 
+    /// Get the DAO contract allowed to move funds
+    pub fn dao(&self) -> Address {
+        self.dao.get()
+    }
+
     /// Check if contract is paused
     pub fn paused(&self) -> bool {
         self.paused.get()
     }
 
+    /// Get the EmergencyStop coordinator address (zero = none)
+    pub fn emergency_stop_contract(&self) -> Address {
+        self.emergency_stop.get()
+    }
+
     /// Get withdrawal delay
     pub fn withdrawal_delay(&self) -> U256 {
         self.withdrawal_delay.get()
@@ -589,12 +998,18 @@ impl Treasury {
     pub fn withdrawal_count(&self) -> U256 {
         self.withdrawal_count.get()
     }
+
+    /// Support for interface detection
+    pub fn supports_interface(&self, interface_id: FixedBytes<4>) -> bool {
+        interface_id == FixedBytes(ERC165_INTERFACE_ID)
+            || interface_id == FixedBytes(TREASURY_INTERFACE_ID)
+    }
 }
 
 // Payable fallback function to receive ETH
 #[payable]
 impl Treasury {
-    fn fallback(&mut self) -> Result<(), Vec<u8>> {
+    fn fallback(&mut self) -> Result<(), TreasuryError> {
         evm::log(DepositedETH {
             from: msg::sender(),
             amount: msg::value(),
@@ -606,34 +1021,931 @@ impl Treasury {
 // Internal helper methods
 impl Treasury {
     /// Check if caller is owner
-    fn only_owner(&self) -> Result<(), Vec<u8>> {
+    fn only_owner(&self) -> Result<(), TreasuryError> {
         if self.owner.get() != msg::sender() {
-            Err(b"Ownable: caller is not the owner".to_vec())
+            Err(TreasuryError::NotOwner(NotOwner { caller: msg::sender() }))
         } else {
             Ok(())
         }
     }
 
-    /// Check if contract is not paused
-    fn when_not_paused(&self) -> Result<(), Vec<u8>> {
-        if self.paused.get() {
-            Err(b"Pausable: paused".to_vec())
-        } else {
+    /// Check if caller is the linked DAO or the owner
+    fn only_dao_or_owner(&self) -> Result<(), TreasuryError> {
+        let caller = msg::sender();
+        if is_dao_or_owner(caller, self.owner.get(), self.dao.get()) {
             Ok(())
+        } else {
+            Err(TreasuryError::NotDaoOrOwner(NotDaoOrOwner { caller }))
+        }
+    }
+
+    /// Check if contract is not paused locally or halted by the shared emergency stop
+    fn when_not_paused(&self) -> Result<(), TreasuryError> {
+        if self.paused.get() {
+            return Err(TreasuryError::Paused(ContractPaused {}));
+        }
+
+        let coordinator = self.emergency_stop.get();
+        if coordinator != Address::ZERO {
+            // Fail closed: an unreachable coordinator blocks state changes
+            let stopped = IEmergencyStop::new(coordinator)
+                .is_stopped(StaticCall::new())
+                .map_err(|_| {
+                    TreasuryError::EmergencyStopCheckFailed(EmergencyStopCheckFailed { coordinator })
+                })?;
+            if stopped {
+                return Err(TreasuryError::EmergencyStopped(EmergencyStopped {}));
+            }
         }
+        Ok(())
+    }
+
+    /// Validate, earmark and record a new timelocked withdrawal
+    fn _queue_withdrawal(
+        &mut self,
+        recipient: Address,
+        amount: U256,
+        memo: String,
+        pull: bool,
+    ) -> Result<U256, TreasuryError> {
+        self.only_dao_or_owner()?;
+        self.when_not_paused()?;
+
+        if recipient == Address::ZERO {
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
+        }
+        if amount == U256::ZERO {
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
+        }
+        check_memo(&memo)?;
+
+        // Only ETH not already earmarked by pending withdrawals can be queued
+        let locked_eth = lock_eth(self.get_eth_balance(), self.locked_eth.get(), amount)?;
+        self._set_locked_eth(locked_eth);
+
+        // Create new withdrawal
+        let withdrawal_id = self.withdrawal_count.get() + U256::from(1);
+        self.withdrawal_count.set(withdrawal_id);
+
+        let unlock_time = block::timestamp() + self.withdrawal_delay.get();
+
+        let queued_withdrawal = QueuedWithdrawal {
+            recipient,
+            amount,
+            unlock_time,
+            executed: false,
+            cancelled: false,
+            pull,
+            memo: memo.clone(),
+        };
+
+        self.queued_withdrawals.setter(withdrawal_id).set(queued_withdrawal);
+
+        evm::log(WithdrawalQueued {
+            withdrawalId: withdrawal_id,
+            recipient,
+            amount,
+            unlockTime: unlock_time,
+            memo,
+        });
+
+        Ok(withdrawal_id)
+    }
+
+    /// Pull `amount` of `token` from the caller and record the deposit
+    fn _deposit_erc20(&mut self, token: Address, amount: U256) -> Result<(), TreasuryError> {
+        if token == Address::ZERO {
+            return Err(TreasuryError::InvalidAddress(InvalidAddress {}));
+        }
+        if amount == U256::ZERO {
+            return Err(TreasuryError::ZeroAmount(ZeroAmount {}));
+        }
+
+        // Transfer tokens from sender to treasury
+        let token_contract = IERC20::new(token);
+        let success = token_contract
+            .transfer_from(Call::new(), msg::sender(), address(), amount)
+            .map_err(|_| TreasuryError::TokenTransferFailed(TokenTransferFailed { token }))?;
+        
+        if !success {
+            return Err(TreasuryError::TokenTransferFailed(TokenTransferFailed { token }));
+        }
+
+        self._track_token(token);
+
+        evm::log(DepositedERC20 {
+            token,
+            from: msg::sender(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Add a token to the held-asset registry on first deposit
+    fn _track_token(&mut self, token: Address) {
+        if !self.is_held_token.get(token) {
+            self.is_held_token.setter(token).set(true);
+            self.held_tokens.push(token);
+        }
+    }
+
+    /// Drop a token from the held-asset registry once its balance is exhausted
+    fn _untrack_token_if_empty(&mut self, token: Address, remaining_balance: U256) {
+        if remaining_balance > U256::ZERO || !self.is_held_token.get(token) {
+            return;
+        }
+
+        // Swap-remove to keep the registry compact
+        let len = self.held_tokens.len();
+        for i in 0..len {
+            if self.held_tokens.get(i) == Some(token) {
+                if let Some(last) = self.held_tokens.get(len - 1) {
+                    self.held_tokens.setter(i).set(last);
+                }
+                self.held_tokens.pop();
+                break;
+            }
+        }
+        self.is_held_token.setter(token).set(false);
+    }
+
+    /// Credit an executed pull-mode withdrawal to its recipient
+    fn _credit_claimable(&mut self, recipient: Address, amount: U256) {
+        let balance = self.claimable.get(recipient);
+        self.claimable.setter(recipient).set(balance + amount);
+    }
+
+    /// Zero a recipient's claimable balance before paying it out (CEI pattern)
+    fn _take_claimable(&mut self, recipient: Address) -> Result<U256, TreasuryError> {
+        let amount = self.claimable.get(recipient);
+        if amount == U256::ZERO {
+            return Err(TreasuryError::NothingToClaim(NothingToClaim { recipient }));
+        }
+        self.claimable.setter(recipient).set(U256::ZERO);
+        Ok(amount)
+    }
+
+    /// Set the timelock for newly queued withdrawals, within MIN/MAX_WITHDRAWAL_DELAY
+    fn _set_withdrawal_delay(&mut self, delay: U256) -> Result<(), TreasuryError> {
+        check_withdrawal_delay(delay)?;
+
+        let old_delay = self.withdrawal_delay.get();
+        self.withdrawal_delay.set(delay);
+
+        evm::log(WithdrawalDelayUpdated {
+            oldDelay: old_delay,
+            newDelay: delay,
+        });
+
+        Ok(())
+    }
+
+    /// Record the ETH committed to pending withdrawals
+    fn _set_locked_eth(&mut self, locked_eth: U256) {
+        self.locked_eth.set(locked_eth);
+        evm::log(LockedEthUpdated { lockedEth: locked_eth });
+    }
+
+    /// Check if a withdrawal is past its unlock time plus the grace period
+    fn _is_expired(&self, withdrawal: &QueuedWithdrawal) -> bool {
+        block::timestamp() > withdrawal.unlock_time + U256::from(WITHDRAWAL_GRACE_PERIOD)
     }
 
     /// Get current ETH balance of the contract
     fn get_eth_balance(&self) -> U256 {
-        // In Stylus, we can use the balance via the execution context
-        U256::from(0) // Placeholder - in real implementation, get contract balance
+        contract::balance()
     }
 
-    /// Internal function to process ETH withdrawal
-    fn _process_eth_withdrawal(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
-        // In Stylus, ETH transfers are done via the execution context
-        // This is a simplified implementation - real version would use proper transfer mechanism
+    /// Send `amount` ETH to `to`, reverting the whole withdrawal if the transfer fails
+    fn _process_eth_withdrawal(&mut self, to: Address, amount: U256) -> Result<(), TreasuryError> {
+        transfer_eth(to, amount).map_err(|_| {
+            TreasuryError::EthTransferFailed(EthTransferFailed { recipient: to, amount })
+        })?;
         evm::log(WithdrawnETH { to, amount });
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Subtract a refund from a contribution, rejecting refunds above what was deposited
+fn debit_contribution(contributor: Address, contributed: U256, amount: U256) -> Result<U256, TreasuryError> {
+    if amount > contributed {
+        return Err(TreasuryError::RefundExceedsContribution(RefundExceedsContribution {
+            contributor,
+            contributed,
+            requested: amount,
+        }));
+    }
+    Ok(contributed - amount)
+}
+
+/// Earmark `amount` out of the unlocked balance, returning the new locked total
+fn lock_eth(balance: U256, locked: U256, amount: U256) -> Result<U256, TreasuryError> {
+    let available = balance.saturating_sub(locked);
+    if available < amount {
+        return Err(TreasuryError::InsufficientBalance(InsufficientBalance {
+            available,
+            required: amount,
+        }));
+    }
+    Ok(locked + amount)
+}
+
+/// Whether `caller` is the owner or the linked DAO (an unset DAO matches nobody)
+fn is_dao_or_owner(caller: Address, owner: Address, dao: Address) -> bool {
+    caller == owner || (dao != Address::ZERO && caller == dao)
+}
+
+/// Reject delays outside [MIN_WITHDRAWAL_DELAY, MAX_WITHDRAWAL_DELAY]
+fn check_withdrawal_delay(delay: U256) -> Result<(), TreasuryError> {
+    if delay < U256::from(MIN_WITHDRAWAL_DELAY) || delay > U256::from(MAX_WITHDRAWAL_DELAY) {
+        return Err(TreasuryError::InvalidDelay(InvalidDelay { delay }));
+    }
+    Ok(())
+}
+
+/// Reject withdrawal memos longer than MAX_MEMO_LENGTH bytes
+fn check_memo(memo: &str) -> Result<(), TreasuryError> {
+    if memo.len() > MAX_MEMO_LENGTH {
+        return Err(TreasuryError::MemoTooLong(MemoTooLong {
+            length: U256::from(memo.len()),
+            maxLength: U256::from(MAX_MEMO_LENGTH),
+        }));
+    }
+    Ok(())
+}
+
+/// Check a withdrawal's flags against a get_withdrawals status filter
+fn matches_status(executed: bool, cancelled: bool, status_filter: u8) -> bool {
+    match status_filter {
+        STATUS_PENDING => !executed && !cancelled,
+        STATUS_EXECUTED => executed,
+        STATUS_CANCELLED => cancelled,
+        STATUS_ALL => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::testing::TestVM;
+
+    #[test]
+    fn test_withdrawal_status_filter() {
+        let owner = Address::repeat_byte(0x01);
+        let (vm, mut treasury) = setup_treasury(owner, 100);
+        vm.set_block_timestamp(1_000);
+        let recipients: Vec<Address> = (0x61..=0x65).map(Address::repeat_byte).collect();
+        let ids: Vec<U256> = recipients
+            .iter()
+            .enumerate()
+            .map(|(i, recipient)| {
+                treasury.queue_withdrawal(*recipient, U256::from(i + 1), String::new()).ok().unwrap()
+            })
+            .collect();
+
+        // Withdrawals 2 and 4 are paid out, 3 is cancelled, 1 and 5 stay pending
+        vm.set_block_timestamp(1_000 + ONE_DAY);
+        for i in [1, 3] {
+            vm.mock_call(recipients[i], Vec::new(), U256::from(i + 1), Ok(Vec::new()));
+            treasury.execute_withdrawal(ids[i]).ok().unwrap();
+        }
+        treasury.cancel_withdrawal(ids[2]).ok().unwrap();
+
+        let listed = |treasury: &Treasury, offset: u64, limit: u64, filter: u8| -> Vec<U256> {
+            treasury
+                .get_withdrawals(U256::from(offset), U256::from(limit), filter)
+                .ok()
+                .unwrap()
+                .into_iter()
+                .map(|withdrawal| withdrawal.0)
+                .collect()
+        };
+        assert_eq!(listed(&treasury, 0, 10, STATUS_PENDING), vec![ids[0], ids[4]]);
+        assert_eq!(listed(&treasury, 0, 10, STATUS_EXECUTED), vec![ids[1], ids[3]]);
+        assert_eq!(listed(&treasury, 0, 10, STATUS_CANCELLED), vec![ids[2]]);
+        assert_eq!(listed(&treasury, 0, 10, STATUS_ALL), ids);
+
+        // Offset and limit count matching withdrawals only
+        assert_eq!(listed(&treasury, 1, 2, STATUS_ALL), ids[1..3].to_vec());
+        assert_eq!(listed(&treasury, 1, 10, STATUS_PENDING), vec![ids[4]]);
+        assert_eq!(listed(&treasury, 1, 1, STATUS_EXECUTED), vec![ids[3]]);
+        assert!(listed(&treasury, 2, 10, STATUS_PENDING).is_empty());
+        assert!(listed(&treasury, 0, 0, STATUS_ALL).is_empty());
+
+        // Entries carry the withdrawal's details and flags
+        let page = treasury.get_withdrawals(U256::ZERO, U256::from(10), STATUS_ALL).ok().unwrap();
+        assert_eq!(page[1], (ids[1], recipients[1], U256::from(2), U256::from(1_000 + ONE_DAY), true, false));
+        assert_eq!(page[2], (ids[2], recipients[2], U256::from(3), U256::from(1_000 + ONE_DAY), false, true));
+        assert_eq!(page[4], (ids[4], recipients[4], U256::from(5), U256::from(1_000 + ONE_DAY), false, false));
+
+        let err = treasury.get_withdrawals(U256::ZERO, U256::from(10), 4).err().unwrap();
+        assert!(matches!(err, TreasuryError::InvalidStatusFilter(e) if e.statusFilter == 4));
+    }
+
+    #[test]
+    fn test_contribution_refunds() {
+        let owner = Address::repeat_byte(0x01);
+        let contributor = Address::repeat_byte(0x33);
+        let (vm, mut treasury) = setup_treasury(owner, 0);
+
+        // Two deposits, through either entrypoint, accumulate in the ledger
+        vm.set_sender(contributor);
+        vm.set_value(U256::from(3));
+        treasury.deposit().ok().unwrap();
+        vm.set_value(U256::from(4));
+        treasury.deposit_eth().ok().unwrap();
+        vm.set_value(U256::ZERO);
+        assert_eq!(treasury.contribution_of(contributor), U256::from(7));
+        assert_eq!(treasury.contribution_of(owner), U256::ZERO);
+        vm.set_balance(vm.contract_address(), U256::from(7));
+
+        // Partial and full refunds drain it
+        vm.set_sender(owner);
+        vm.mock_call(contributor, Vec::new(), U256::from(5), Ok(Vec::new()));
+        treasury.refund_contribution(contributor, U256::from(5)).ok().unwrap();
+        assert_eq!(treasury.contribution_of(contributor), U256::from(2));
+        vm.mock_call(contributor, Vec::new(), U256::from(2), Ok(Vec::new()));
+        treasury.refund_contribution(contributor, U256::from(2)).ok().unwrap();
+        assert_eq!(treasury.contribution_of(contributor), U256::ZERO);
+    }
+
+    #[test]
+    fn test_over_refund_rejected() {
+        let owner = Address::repeat_byte(0x01);
+        let contributor = Address::repeat_byte(0x33);
+        let (vm, mut treasury) = setup_treasury(owner, 0);
+
+        vm.set_sender(contributor);
+        vm.set_value(U256::from(7));
+        treasury.deposit().ok().unwrap();
+        vm.set_value(U256::ZERO);
+        vm.set_balance(vm.contract_address(), U256::from(20));
+
+        // The treasury could pay 8, but only 7 were contributed
+        vm.set_sender(owner);
+        let err = treasury.refund_contribution(contributor, U256::from(8)).err().unwrap();
+        assert!(matches!(
+            err,
+            TreasuryError::RefundExceedsContribution(RefundExceedsContribution { contributor: c, contributed, requested })
+                if c == contributor && contributed == U256::from(7) && requested == U256::from(8)
+        ));
+        assert_eq!(treasury.contribution_of(contributor), U256::from(7));
+    }
+
+    #[test]
+    fn test_queued_withdrawal_locks_eth_against_direct_spends() {
+        let owner = Address::repeat_byte(0x01);
+        let contributor = Address::repeat_byte(0x33);
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+        treasury.contributions.setter(contributor).set(U256::from(10));
+
+        // Queuing 6 of 10 leaves 4 spendable
+        treasury.queue_withdrawal(Address::repeat_byte(0x0d), U256::from(6), String::new()).ok().unwrap();
+        assert_eq!(treasury.locked_eth(), U256::from(6));
+        assert_eq!(treasury.available_eth(), U256::from(4));
+
+        let over_available = |err: TreasuryError| matches!(
+            err,
+            TreasuryError::InsufficientBalance(InsufficientBalance { available, required })
+                if available == U256::from(4) && required == U256::from(5)
+        );
+
+        // Neither a second queue, a direct withdrawal nor a refund can dip into the locked 6
+        let err = treasury.queue_withdrawal(Address::repeat_byte(0x0e), U256::from(5), String::new());
+        assert!(over_available(err.err().unwrap()));
+        assert!(over_available(treasury.withdraw_eth(contributor, U256::from(5)).err().unwrap()));
+        assert!(over_available(treasury.refund_contribution(contributor, U256::from(5)).err().unwrap()));
+        assert_eq!(treasury.contribution_of(contributor), U256::from(10));
+
+        // The unlocked remainder can still be withdrawn directly
+        vm.mock_call(contributor, Vec::new(), U256::from(4), Ok(Vec::new()));
+        assert!(treasury.withdraw_eth(contributor, U256::from(4)).is_ok());
+    }
+
+    #[test]
+    fn test_pull_withdrawal_claim() {
+        let owner = Address::repeat_byte(0x01);
+        // A contract recipient whose receive() reverts on a push it did not ask for
+        let rejecting_contract = Address::repeat_byte(0xc0);
+
+        // Pushed, the withdrawal cannot be paid
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+        vm.set_block_timestamp(1_000);
+        let id = treasury.queue_withdrawal(rejecting_contract, U256::from(6), String::new()).ok().unwrap();
+        vm.set_block_timestamp(1_000 + ONE_DAY);
+        vm.mock_call(rejecting_contract, Vec::new(), U256::from(6), Err(b"no receive".to_vec()));
+        let err = treasury.execute_withdrawal(id).err().unwrap();
+        assert!(matches!(err, TreasuryError::EthTransferFailed(e) if e.recipient == rejecting_contract));
+
+        // Pull-mode withdrawals are credited on execution, and stay locked until claimed
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+        vm.set_block_timestamp(1_000);
+        let first = treasury.queue_pull_withdrawal(rejecting_contract, U256::from(3), String::new()).ok().unwrap();
+        let second = treasury.queue_pull_withdrawal(rejecting_contract, U256::from(4), String::new()).ok().unwrap();
+        assert!(treasury.is_pull_withdrawal(first));
+        vm.set_block_timestamp(1_000 + ONE_DAY);
+        treasury.execute_withdrawal(first).ok().unwrap();
+        treasury.execute_withdrawal(second).ok().unwrap();
+        assert_eq!(treasury.claimable_of(rejecting_contract), U256::from(7));
+        assert_eq!(treasury.locked_eth(), U256::from(7));
+
+        // The contract pulls the full credit in a call it makes itself
+        vm.set_sender(rejecting_contract);
+        vm.mock_call(rejecting_contract, Vec::new(), U256::from(7), Ok(Vec::new()));
+        assert_eq!(treasury.claim().ok().unwrap(), U256::from(7));
+        assert_eq!(treasury.claimable_of(rejecting_contract), U256::ZERO);
+        assert_eq!(treasury.locked_eth(), U256::ZERO);
+
+        let err = treasury.claim().err().unwrap();
+        assert!(matches!(
+            err,
+            TreasuryError::NothingToClaim(NothingToClaim { recipient })
+                if recipient == rejecting_contract
+        ));
+    }
+
+    #[test]
+    fn test_withdrawal_memo_round_trip() {
+        let mut treasury = Treasury::default();
+        let recipient = Address::repeat_byte(0x0d);
+        let memo = "Q3 audit retainer, invoice #4471".to_string();
+        assert!(check_memo(&memo).is_ok());
+
+        let withdrawal_id = U256::from(1);
+        treasury.queued_withdrawals.setter(withdrawal_id).set(QueuedWithdrawal {
+            recipient,
+            amount: U256::from(5),
+            unlock_time: U256::from(1_000),
+            memo: memo.clone(),
+            ..Default::default()
+        });
+        assert_eq!(
+            treasury.get_withdrawal(withdrawal_id),
+            (recipient, U256::from(5), U256::from(1_000), false, false, memo)
+        );
+
+        // Memos are capped at MAX_MEMO_LENGTH bytes
+        assert!(check_memo(&"x".repeat(MAX_MEMO_LENGTH)).is_ok());
+        let err = check_memo(&"x".repeat(MAX_MEMO_LENGTH + 1)).err().unwrap();
+        assert!(matches!(
+            err,
+            TreasuryError::MemoTooLong(MemoTooLong { length, maxLength })
+                if length == U256::from(MAX_MEMO_LENGTH + 1)
+                    && maxLength == U256::from(MAX_MEMO_LENGTH)
+        ));
+    }
+
+    #[test]
+    fn test_expired_withdrawal_requeued_then_executed() {
+        let owner = Address::repeat_byte(0x01);
+        let recipient = Address::repeat_byte(0x0d);
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+        vm.set_block_timestamp(1_000);
+
+        let id = treasury.queue_withdrawal(recipient, U256::from(6), "grant".to_string()).ok().unwrap();
+        let first_unlock = 1_000 + ONE_DAY;
+
+        // Nobody executed it within the grace period
+        vm.set_block_timestamp(first_unlock + WITHDRAWAL_GRACE_PERIOD + 1);
+        assert!(treasury.is_withdrawal_expired(id));
+        let err = treasury.execute_withdrawal(id).err().unwrap();
+        assert!(matches!(err, TreasuryError::WithdrawalExpired(e) if e.withdrawalId == id));
+
+        // Requeuing keeps the id and the lock, and restarts the delay from now
+        let requeued_at = first_unlock + WITHDRAWAL_GRACE_PERIOD + 1;
+        treasury.requeue_withdrawal(id).ok().unwrap();
+        let (_, amount, unlock_time, executed, cancelled, memo) = treasury.get_withdrawal(id);
+        assert_eq!((amount, executed, cancelled, memo.as_str()), (U256::from(6), false, false, "grant"));
+        assert_eq!(unlock_time, U256::from(requeued_at + ONE_DAY));
+        assert_eq!(treasury.locked_eth(), U256::from(6));
+
+        let err = treasury.execute_withdrawal(id).err().unwrap();
+        assert!(matches!(err, TreasuryError::WithdrawalNotReady(e) if e.unlockTime == unlock_time));
+
+        // After the new delay it executes and releases the lock
+        vm.set_block_timestamp(requeued_at + ONE_DAY);
+        vm.mock_call(recipient, Vec::new(), U256::from(6), Ok(Vec::new()));
+        assert!(treasury.execute_withdrawal(id).is_ok());
+        assert_eq!(treasury.locked_eth(), U256::ZERO);
+        assert!(treasury.get_withdrawal(id).3);
+    }
+
+    #[test]
+    fn test_cancelled_withdrawal_cannot_be_requeued() {
+        let owner = Address::repeat_byte(0x01);
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+        vm.set_block_timestamp(1_000);
+
+        let id = treasury.queue_withdrawal(Address::repeat_byte(0x0d), U256::from(6), String::new()).ok().unwrap();
+        treasury.cancel_withdrawal(id).ok().unwrap();
+        assert_eq!(treasury.locked_eth(), U256::ZERO);
+
+        // Even once expired, reviving it would leave the payout unbacked by locked ETH
+        vm.set_block_timestamp(1_000 + ONE_DAY + WITHDRAWAL_GRACE_PERIOD + 1);
+        let err = treasury.requeue_withdrawal(id).err().unwrap();
+        assert!(matches!(err, TreasuryError::AlreadyCancelled(e) if e.withdrawalId == id));
+        assert!(treasury.get_withdrawal(id).4);
+        assert_eq!(treasury.locked_eth(), U256::ZERO);
+    }
+
+    /// Treasury initialized by `owner`, holding `balance` wei, with `owner` as the caller
+    fn setup_treasury(owner: Address, balance: u64) -> (TestVM, Treasury) {
+        let vm = TestVM::default();
+        vm.set_sender(owner);
+        vm.set_balance(vm.contract_address(), U256::from(balance));
+
+        let mut treasury = Treasury::from(&vm);
+        treasury.init(owner).ok().unwrap();
+        treasury.set_dao(Address::repeat_byte(0xda)).ok().unwrap();
+        (vm, treasury)
+    }
+
+    #[test]
+    fn test_dao_queues_withdrawal_and_outsider_is_rejected() {
+        let owner = Address::repeat_byte(0x01);
+        let dao = Address::repeat_byte(0xda);
+        let outsider = Address::repeat_byte(0x0c);
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+
+        // The DAO cannot be unset once linked
+        assert!(matches!(treasury.set_dao(Address::ZERO), Err(TreasuryError::InvalidAddress(_))));
+        assert_eq!(treasury.dao(), dao);
+
+        vm.set_sender(dao);
+        let id = treasury.queue_withdrawal(Address::repeat_byte(0x0d), U256::from(6), String::new()).ok().unwrap();
+        assert_eq!(treasury.get_withdrawal(id).1, U256::from(6));
+
+        vm.set_sender(outsider);
+        let err = treasury.queue_withdrawal(outsider, U256::from(4), String::new()).err().unwrap();
+        assert!(matches!(err, TreasuryError::NotDaoOrOwner(NotDaoOrOwner { caller }) if caller == outsider));
+        assert!(matches!(treasury.set_dao(outsider), Err(TreasuryError::NotOwner(_))));
+        assert_eq!(treasury.locked_eth(), U256::from(6));
+        assert_eq!(treasury.dao(), dao);
+    }
+
+    /// `from` sends `value` wei to the payable deposit; like the chain, the VM credits the
+    /// contract before deposit runs
+    fn send_deposit(vm: &TestVM, treasury: &mut Treasury, from: Address, value: u64) -> Result<(), TreasuryError> {
+        let credited = treasury.balance() + U256::from(value);
+        vm.set_balance(vm.contract_address(), credited);
+        vm.set_sender(from);
+        vm.set_value(U256::from(value));
+        let result = treasury.deposit();
+        vm.set_value(U256::ZERO);
+        result
+    }
+
+    #[test]
+    fn test_deposit_reported_by_balance() {
+        let owner = Address::repeat_byte(0x01);
+        let depositor = Address::repeat_byte(0x44);
+        let (vm, mut treasury) = setup_treasury(owner, 0);
+        assert_eq!(treasury.balance(), U256::ZERO);
+
+        send_deposit(&vm, &mut treasury, depositor, 7).ok().unwrap();
+        assert_eq!(treasury.balance(), U256::from(7));
+        assert_eq!(treasury.balance_eth(), U256::from(7));
+        assert_eq!(treasury.available_eth(), U256::from(7));
+
+        send_deposit(&vm, &mut treasury, depositor, 3).ok().unwrap();
+        assert_eq!(treasury.balance(), U256::from(10));
+        assert_eq!(treasury.contribution_of(depositor), treasury.balance());
+    }
+
+    #[test]
+    fn test_withdraw_eth_sends_exact_amount() {
+        let owner = Address::repeat_byte(0x01);
+        let recipient = Address::repeat_byte(0x0d);
+        let (vm, mut treasury) = setup_treasury(owner, 10);
+        let withdrawn = |vm: &TestVM| {
+            vm.get_emitted_logs()
+                .into_iter()
+                .filter(|(topics, _)| topics[0] == WithdrawnETH::SIGNATURE_HASH)
+                .map(|(topics, data)| (topics[1], WithdrawnETH::abi_decode_data(&data, true).unwrap().0))
+                .collect::<Vec<_>>()
+        };
+
+        // The only call answered is a plain value transfer of exactly 4 wei to the recipient,
+        // so any other target, calldata or value would fail the withdrawal
+        vm.mock_call(recipient, Vec::new(), U256::from(4), Ok(Vec::new()));
+        treasury.withdraw_eth(recipient, U256::from(4)).ok().unwrap();
+        assert_eq!(withdrawn(&vm), vec![(recipient.into_word(), U256::from(4))]);
+
+        // A refused transfer reverts with the recipient and amount, and is not reported as paid
+        vm.mock_call(recipient, Vec::new(), U256::from(3), Err(Vec::new()));
+        let err = treasury.withdraw_eth(recipient, U256::from(3)).err().unwrap();
+        assert!(matches!(
+            err,
+            TreasuryError::EthTransferFailed(EthTransferFailed { recipient: to, amount })
+                if to == recipient && amount == U256::from(3)
+        ));
+        assert_eq!(withdrawn(&vm).len(), 1);
+    }
+
+    mod token_abi {
+        stylus_sdk::alloy_sol_types::sol! {
+            function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+            function allowance(address owner, address spender) external view returns (uint256);
+            function transferFrom(address from, address to, uint256 amount) external returns (bool);
+            function balanceOf(address account) external view returns (uint256);
+            function transfer(address to, uint256 amount) external returns (bool);
+        }
+    }
+
+    /// Encode an error as the contract would revert with it
+    fn revert_data(err: TreasuryError) -> Vec<u8> {
+        err.into()
+    }
+
+    #[test]
+    fn test_deposit_with_permit_falls_back_to_allowance() {
+        use stylus_sdk::alloy_sol_types::SolCall;
+
+        let owner = Address::repeat_byte(0x01);
+        let depositor = Address::repeat_byte(0x44);
+        let token = Address::repeat_byte(0x70);
+        let (vm, mut treasury) = setup_treasury(owner, 0);
+        let this = vm.contract_address();
+        let (deadline, v, r, s) = (U256::from(9_999), 27u8, B256::repeat_byte(0x0a), B256::repeat_byte(0x0b));
+        let amount = U256::from(500);
+
+        let permit = |value: U256| token_abi::permitCall {
+            owner: depositor,
+            spender: this,
+            value,
+            deadline,
+            v,
+            r,
+            s,
+        }.abi_encode();
+        let allowance = |allowed: u64| {
+            let call = token_abi::allowanceCall { owner: depositor, spender: this }.abi_encode();
+            let returned = token_abi::allowanceCall::abi_encode_returns(&(U256::from(allowed),));
+            vm.mock_static_call(token, call, Ok(returned));
+        };
+        let transfer_from = |value: U256| {
+            let call = token_abi::transferFromCall { from: depositor, to: this, amount: value }.abi_encode();
+            let returned = token_abi::transferFromCall::abi_encode_returns(&(true,));
+            vm.mock_call(token, call, U256::ZERO, Ok(returned));
+        };
+        vm.set_sender(depositor);
+
+        // A valid permit grants the allowance and the deposit goes through
+        vm.mock_call(token, permit(amount), U256::ZERO, Ok(Vec::new()));
+        transfer_from(amount);
+        assert!(treasury.deposit_erc20_with_permit(token, amount, deadline, v, r, s).is_ok());
+        assert_eq!(treasury.get_held_tokens(), vec![token]);
+
+        // A front-run permit reverts, but the allowance it granted is still used
+        let front_run = U256::from(300);
+        vm.mock_call(token, permit(front_run), U256::ZERO, Err(b"ERC2612InvalidSigner".to_vec()));
+        allowance(300);
+        transfer_from(front_run);
+        assert!(treasury.deposit_erc20_with_permit(token, front_run, deadline, v, r, s).is_ok());
+
+        // Without a sufficient allowance the failed permit is reported
+        let short = U256::from(400);
+        vm.mock_call(token, permit(short), U256::ZERO, Err(b"ERC2612ExpiredSignature".to_vec()));
+        allowance(399);
+        let err = treasury.deposit_erc20_with_permit(token, short, deadline, v, r, s);
+        assert!(matches!(
+            err,
+            Err(TreasuryError::PermitFailed(PermitFailed { token: failed })) if failed == token
+        ));
+    }
+
+    #[test]
+    fn test_held_token_registry() {
+        use stylus_sdk::alloy_sol_types::SolCall;
+
+        let owner = Address::repeat_byte(0x01);
+        let depositor = Address::repeat_byte(0x44);
+        let recipient = Address::repeat_byte(0x0d);
+        let (usdc, weth) = (Address::repeat_byte(0x70), Address::repeat_byte(0x71));
+        let (vm, mut treasury) = setup_treasury(owner, 0);
+        let this = vm.contract_address();
+
+        let deposit = |treasury: &mut Treasury, token: Address, amount: u64| {
+            let amount = U256::from(amount);
+            let call = token_abi::transferFromCall { from: depositor, to: this, amount }.abi_encode();
+            let returned = token_abi::transferFromCall::abi_encode_returns(&(true,));
+            vm.mock_call(token, call, U256::ZERO, Ok(returned));
+            vm.set_sender(depositor);
+            treasury.deposit_erc20(token, amount).ok().unwrap();
+        };
+        let withdraw = |treasury: &mut Treasury, token: Address, held: u64, amount: u64| {
+            let balance = token_abi::balanceOfCall { account: this }.abi_encode();
+            let returned = token_abi::balanceOfCall::abi_encode_returns(&(U256::from(held),));
+            vm.mock_call(token, balance, U256::ZERO, Ok(returned));
+            let transfer = token_abi::transferCall { to: recipient, amount: U256::from(amount) }.abi_encode();
+            let returned = token_abi::transferCall::abi_encode_returns(&(true,));
+            vm.mock_call(token, transfer, U256::ZERO, Ok(returned));
+            vm.set_sender(owner);
+            treasury.withdraw_erc20(token, recipient, U256::from(amount)).ok().unwrap();
+        };
+
+        // Repeat deposits of a token are listed once
+        deposit(&mut treasury, usdc, 100);
+        deposit(&mut treasury, weth, 5);
+        deposit(&mut treasury, usdc, 50);
+        assert_eq!(treasury.get_held_tokens(), vec![usdc, weth]);
+        assert_eq!(treasury.held_token_count(), U256::from(2));
+
+        // A partial withdrawal keeps the token; draining it removes it
+        withdraw(&mut treasury, weth, 5, 2);
+        assert_eq!(treasury.held_token_count(), U256::from(2));
+        withdraw(&mut treasury, usdc, 150, 150);
+        assert_eq!(treasury.get_held_tokens(), vec![weth]);
+        assert_eq!(treasury.held_token_count(), U256::from(1));
+
+        // A token deposited again after being drained is listed again
+        deposit(&mut treasury, usdc, 10);
+        assert_eq!(treasury.get_held_tokens(), vec![weth, usdc]);
+    }
+
+    #[test]
+    fn test_error_selectors_decode() {
+        use stylus_sdk::alloy_sol_types::SolError;
+
+        let caller = Address::repeat_byte(0x11);
+        let token = Address::repeat_byte(0x22);
+        let id = U256::from(7);
+
+        let data = revert_data(TreasuryError::NotOwner(NotOwner { caller }));
+        assert_eq!(data[..4], NotOwner::SELECTOR);
+        assert_eq!(NotOwner::abi_decode(&data, true).unwrap().caller, caller);
+
+        let data = revert_data(TreasuryError::NotDaoOrOwner(NotDaoOrOwner { caller }));
+        assert_eq!(data[..4], NotDaoOrOwner::SELECTOR);
+        assert_eq!(NotDaoOrOwner::abi_decode(&data, true).unwrap().caller, caller);
+
+        let data = revert_data(TreasuryError::Paused(ContractPaused {}));
+        assert_eq!(data[..4], ContractPaused::SELECTOR);
+
+        let data = revert_data(TreasuryError::ReentrantCall(ReentrantCall {}));
+        assert_eq!(data[..4], ReentrantCall::SELECTOR);
+
+        let data = revert_data(TreasuryError::InvalidAddress(InvalidAddress {}));
+        assert_eq!(data[..4], InvalidAddress::SELECTOR);
+
+        let data = revert_data(TreasuryError::ZeroAmount(ZeroAmount {}));
+        assert_eq!(data[..4], ZeroAmount::SELECTOR);
+
+        let data = revert_data(TreasuryError::InsufficientBalance(InsufficientBalance {
+            available: U256::from(1),
+            required: U256::from(2),
+        }));
+        assert_eq!(data[..4], InsufficientBalance::SELECTOR);
+        let decoded = InsufficientBalance::abi_decode(&data, true).unwrap();
+        assert_eq!((decoded.available, decoded.required), (U256::from(1), U256::from(2)));
+
+        let data = revert_data(TreasuryError::TokenTransferFailed(TokenTransferFailed { token }));
+        assert_eq!(data[..4], TokenTransferFailed::SELECTOR);
+        assert_eq!(TokenTransferFailed::abi_decode(&data, true).unwrap().token, token);
+
+        let data = revert_data(TreasuryError::EthTransferFailed(EthTransferFailed {
+            recipient: caller,
+            amount: U256::from(5),
+        }));
+        assert_eq!(data[..4], EthTransferFailed::SELECTOR);
+        let decoded = EthTransferFailed::abi_decode(&data, true).unwrap();
+        assert_eq!((decoded.recipient, decoded.amount), (caller, U256::from(5)));
+
+        let data = revert_data(TreasuryError::TokenBalanceUnavailable(TokenBalanceUnavailable { token }));
+        assert_eq!(data[..4], TokenBalanceUnavailable::SELECTOR);
+        assert_eq!(TokenBalanceUnavailable::abi_decode(&data, true).unwrap().token, token);
+
+        let data = revert_data(TreasuryError::WithdrawalNotFound(WithdrawalNotFound { withdrawalId: id }));
+        assert_eq!(data[..4], WithdrawalNotFound::SELECTOR);
+        assert_eq!(WithdrawalNotFound::abi_decode(&data, true).unwrap().withdrawalId, id);
+
+        let data = revert_data(TreasuryError::WithdrawalNotReady(WithdrawalNotReady {
+            withdrawalId: id,
+            unlockTime: U256::from(1_000),
+        }));
+        assert_eq!(data[..4], WithdrawalNotReady::SELECTOR);
+        assert_eq!(WithdrawalNotReady::abi_decode(&data, true).unwrap().unlockTime, U256::from(1_000));
+
+        let data = revert_data(TreasuryError::WithdrawalExpired(WithdrawalExpired { withdrawalId: id }));
+        assert_eq!(data[..4], WithdrawalExpired::SELECTOR);
+
+        let data = revert_data(TreasuryError::WithdrawalNotExpired(WithdrawalNotExpired { withdrawalId: id }));
+        assert_eq!(data[..4], WithdrawalNotExpired::SELECTOR);
+
+        let data = revert_data(TreasuryError::AlreadyExecuted(AlreadyExecuted { withdrawalId: id }));
+        assert_eq!(data[..4], AlreadyExecuted::SELECTOR);
+        assert_eq!(AlreadyExecuted::abi_decode(&data, true).unwrap().withdrawalId, id);
+
+        let data = revert_data(TreasuryError::AlreadyCancelled(AlreadyCancelled { withdrawalId: id }));
+        assert_eq!(data[..4], AlreadyCancelled::SELECTOR);
+
+        let data = revert_data(TreasuryError::InvalidDelay(InvalidDelay { delay: U256::from(5) }));
+        assert_eq!(data[..4], InvalidDelay::SELECTOR);
+        assert_eq!(InvalidDelay::abi_decode(&data, true).unwrap().delay, U256::from(5));
+
+        let data = revert_data(TreasuryError::InvalidStatusFilter(InvalidStatusFilter { statusFilter: 9 }));
+        assert_eq!(data[..4], InvalidStatusFilter::SELECTOR);
+        assert_eq!(InvalidStatusFilter::abi_decode(&data, true).unwrap().statusFilter, 9);
+    }
+
+    #[test]
+    fn test_error_selectors_are_distinct() {
+        use stylus_sdk::alloy_sol_types::SolError;
+
+        let mut selectors = vec![
+            NotOwner::SELECTOR,
+            NotDaoOrOwner::SELECTOR,
+            ContractPaused::SELECTOR,
+            ReentrantCall::SELECTOR,
+            InvalidAddress::SELECTOR,
+            ZeroAmount::SELECTOR,
+            InsufficientBalance::SELECTOR,
+            TokenTransferFailed::SELECTOR,
+            TokenBalanceUnavailable::SELECTOR,
+            WithdrawalNotFound::SELECTOR,
+            WithdrawalNotReady::SELECTOR,
+            WithdrawalExpired::SELECTOR,
+            WithdrawalNotExpired::SELECTOR,
+            AlreadyExecuted::SELECTOR,
+            AlreadyCancelled::SELECTOR,
+            InvalidDelay::SELECTOR,
+            InvalidStatusFilter::SELECTOR,
+            RefundExceedsContribution::SELECTOR,
+            EmergencyStopped::SELECTOR,
+            EmergencyStopCheckFailed::SELECTOR,
+            PermitFailed::SELECTOR,
+            NothingToClaim::SELECTOR,
+            MemoTooLong::SELECTOR,
+            EthTransferFailed::SELECTOR,
+        ];
+        let total = selectors.len();
+        selectors.sort();
+        selectors.dedup();
+        assert_eq!(selectors.len(), total);
+    }
+
+    #[test]
+    fn test_dao_can_set_withdrawal_delay() {
+        let owner = Address::repeat_byte(0x01);
+        let dao = Address::repeat_byte(0xda);
+        let outsider = Address::repeat_byte(0x03);
+        let (vm, mut treasury) = setup_treasury(owner, 0);
+        let initial = treasury.withdrawal_delay();
+
+        // An outsider cannot touch the delay
+        vm.set_sender(outsider);
+        let err = treasury.set_withdrawal_delay(U256::from(2 * ONE_DAY)).err().unwrap();
+        assert!(matches!(err, TreasuryError::NotDaoOrOwner(NotDaoOrOwner { caller }) if caller == outsider));
+        assert_eq!(treasury.withdrawal_delay(), initial);
+
+        // The DAO sets a delay within bounds and the change is logged
+        vm.set_sender(dao);
+        let delay = U256::from(2 * ONE_DAY);
+        treasury.set_withdrawal_delay(delay).ok().unwrap();
+        assert_eq!(treasury.withdrawal_delay(), delay);
+        let (_, data) = vm.get_emitted_logs().last().unwrap().clone();
+        let event = WithdrawalDelayUpdated::abi_decode_data(&data, true).unwrap();
+        assert_eq!(event, (initial, delay));
+
+        // Out of bounds reverts and leaves the previous delay in place
+        let too_short = U256::from(MIN_WITHDRAWAL_DELAY - 1);
+        let too_long = U256::from(MAX_WITHDRAWAL_DELAY + 1);
+        for bad in [too_short, too_long, U256::MAX] {
+            let err = treasury.set_withdrawal_delay(bad).err().unwrap();
+            assert!(matches!(err, TreasuryError::InvalidDelay(e) if e.delay == bad));
+        }
+        assert_eq!(treasury.withdrawal_delay(), delay);
+
+        // The owner keeps the same right
+        vm.set_sender(owner);
+        treasury.set_withdrawal_delay(initial).ok().unwrap();
+        assert_eq!(treasury.withdrawal_delay(), initial);
+    }
+
+    #[test]
+    fn test_supports_interface() {
+        let signatures = [
+            "deposit()",
+            "depositErc20(address,uint256)",
+            "queueWithdrawal(address,uint256,string)",
+            "executeWithdrawal(uint256)",
+            "cancelWithdrawal(uint256)",
+            "balance()",
+        ];
+        let mut expected = [0u8; 4];
+        for signature in signatures {
+            let hash = stylus_sdk::crypto::keccak(signature.as_bytes());
+            for (byte, selector_byte) in expected.iter_mut().zip(&hash.0[..4]) {
+                *byte ^= selector_byte;
+            }
+        }
+        assert_eq!(expected, TREASURY_INTERFACE_ID);
+
+        let treasury = Treasury::default();
+        assert!(treasury.supports_interface(FixedBytes(ERC165_INTERFACE_ID)));
+        assert!(treasury.supports_interface(FixedBytes(TREASURY_INTERFACE_ID)));
+        assert!(!treasury.supports_interface(FixedBytes([0x36, 0x37, 0x2b, 0x07])));
+        assert!(!treasury.supports_interface(FixedBytes([0xff; 4])));
+    }
+}
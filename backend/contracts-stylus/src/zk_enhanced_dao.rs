@@ -4,14 +4,14 @@
 //! privacy-preserving operations using Noir ZK proofs.
 
 use stylus_sdk::{
-    alloy_primitives::{Address, U256, Bytes},
+    alloy_primitives::{Address, U256, Bytes, FixedBytes},
     prelude::*,
-    storage::{StorageMap, StorageBool, StorageU256, StorageString},
+    storage::{StorageMap, StorageBool, StorageU256, StorageString, StorageFixedBytes},
 };
 
 use crate::{
     dao::DAO,
-    zk_integration::ZkVerificationStorage,
+    zk_integration::{proof_public_inputs, ZkVerificationStorage},
     shadowid_registry::ShadowIDRegistry,
 };
 
@@ -30,8 +30,23 @@ pub struct ZkEnhancedDAO {
     /// Mapping of address to verified ZK proofs
     pub member_zk_proofs: StorageMap<Address, StorageMap<String, StorageBool>>,
     
+    /// Mapping of address to proof hash per verified proof type
+    pub member_proof_hashes: StorageMap<Address, StorageMap<String, StorageFixedBytes<32>>>,
+    
     /// Privacy-preserving voting enabled
     pub privacy_voting_enabled: StorageBool,
+    
+    /// Mapping of proposal ID to nullifiers consumed by private votes
+    pub spent_nullifiers: StorageMap<U256, StorageMap<[u8; 32], StorageBool>>,
+    
+    /// Number of nullifiers consumed per proposal
+    pub nullifier_counts: StorageMap<U256, StorageU256>,
+    
+    /// Private yes votes tallied per proposal
+    pub private_yes_votes: StorageMap<U256, StorageU256>,
+    
+    /// Private no votes tallied per proposal
+    pub private_no_votes: StorageMap<U256, StorageU256>,
 }
 
 /// Events for ZK-enhanced DAO operations
@@ -69,14 +84,30 @@ sol_interface! {
 #[public]
 impl ZkEnhancedDAO {
     /// Initialize the ZK-enhanced DAO
+    /// 
+    /// The base DAO takes the same settings as `DAO::constructor`; the caller becomes
+    /// its owner, which is the admin checked by `set_privacy_voting`.
     pub fn initialize_zk_dao(
         &mut self,
         token_address: Address,
-        admin: Address,
+        treasury: Address,
+        shadow_id_registry: Address,
+        voting_period: U256,
+        quorum_percent: U256,
+        execution_delay: U256,
+        proposal_threshold: U256,
         zk_admin: Address
     ) -> Result<(), Vec<u8>> {
-        // Initialize base DAO
-        self.dao.initialize(token_address, admin)?;
+        // Initialize base DAO (rejects a second initialization)
+        self.dao.constructor(
+            token_address,
+            treasury,
+            shadow_id_registry,
+            voting_period,
+            quorum_percent,
+            execution_delay,
+            proposal_threshold,
+        )?;
         
         // Initialize ZK verifier
         self.zk_verifier.initialize(zk_admin)?;
@@ -112,14 +143,11 @@ impl ZkEnhancedDAO {
         )?;
         
         if verification_result {
-            // Store the verified proof for the member
-            self.member_zk_proofs
-                .setter(member)
-                .setter(proof_type.clone())
-                .set(true);
+            // Store the verified proof and its hash for the member
+            let proof_hash = self.compute_proof_hash(proof_json.as_ref());
+            self.store_member_proof(member, proof_type.clone(), proof_hash);
             
             // Emit verification event
-            let proof_hash = self.compute_proof_hash(proof_json.as_ref());
             evm::log(ZkProofSubmitted {
                 member,
                 proofType: proof_type.clone(),
@@ -206,6 +234,9 @@ impl ZkEnhancedDAO {
             return Err(b"Privacy voting not enabled".to_vec());
         }
         
+        // Only existing, active proposals inside their voting window take votes
+        self.dao.check_private_vote(proposal_id)?;
+        
         // Verify nullifier proof (prevents double voting)
         let nullifier_valid = self.zk_verifier.verify_zk_proof(nullifier_proof.clone(), vk_json.clone())?;
         if !nullifier_valid {
@@ -222,8 +253,12 @@ impl ZkEnhancedDAO {
         let nullifier_hash = self.extract_nullifier_hash(nullifier_proof.as_ref())?;
         let commitment_hash = self.extract_commitment_hash(nullifier_proof.as_ref())?;
         
-        // Cast vote through base DAO (this would need modification to support ZK voting)
-        // For now, we'll emit the private vote event
+        // Record the nullifier as consumed for this proposal; a replayed proof reverts here
+        self.record_nullifier(proposal_id, nullifier_hash)?;
+        // Kept apart from the token-weighted tally: the proofs are checked against a caller
+        // supplied VK and nullifiers are not yet tied to members
+        self.tally_private_vote(proposal_id, vote);
+        
         evm::log(PrivateVoteCast {
             proposalId: proposal_id,
             nullifierHash: nullifier_hash,
@@ -267,15 +302,51 @@ impl ZkEnhancedDAO {
         self.member_zk_proofs.get(member).get(proof_type)
     }
 
+    /// Check if a nullifier has been consumed by a private vote on a proposal
+    pub fn is_nullifier_spent(&self, proposal_id: U256, nullifier: [u8; 32]) -> bool {
+        self.spent_nullifiers.get(proposal_id).get(nullifier)
+    }
+
+    /// Get the number of nullifiers consumed by private votes on a proposal
+    pub fn nullifier_count(&self, proposal_id: U256) -> U256 {
+        self.nullifier_counts.get(proposal_id)
+    }
+
+    /// Get the private vote tally for a proposal: (yes votes, no votes)
+    pub fn get_private_tally(&self, proposal_id: U256) -> (U256, U256) {
+        (
+            self.private_yes_votes.get(proposal_id),
+            self.private_no_votes.get(proposal_id)
+        )
+    }
+
+    /// Get the hash of the proof a member submitted for a proof type
+    pub fn get_member_proof_hash(&self, member: Address, proof_type: String) -> [u8; 32] {
+        self.member_proof_hashes.get(member).get(proof_type).0
+    }
+
     /// Get the required ZK proof type for a proposal
     pub fn get_proposal_zk_requirement(&self, proposal_id: U256) -> String {
         self.proposal_zk_requirements.get(proposal_id)
     }
 
+    /// Get what a proposal requires of voters: (verification type, named proof types).
+    /// Same shape as DAO::proposal_requirements, with the ZK proof type filled in
+    pub fn proposal_requirements(&self, proposal_id: U256) -> (u8, Vec<String>) {
+        let (verification_type, _) = self.dao.proposal_requirements(proposal_id);
+        let required_proof = self.proposal_zk_requirements.get(proposal_id);
+        
+        let mut proof_types = Vec::new();
+        if !required_proof.is_empty() {
+            proof_types.push(required_proof);
+        }
+        (verification_type, proof_types)
+    }
+
     /// Enable or disable privacy-preserving voting (admin only)
     pub fn set_privacy_voting(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
-        // Check if caller is DAO admin
-        if msg::sender() != self.dao.get_admin() {
+        // Check if caller is DAO admin (the base DAO's owner)
+        if msg::sender() != self.dao.owner() {
             return Err(b"Only DAO admin can modify privacy settings".to_vec());
         }
         
@@ -316,22 +387,60 @@ impl ZkEnhancedDAO {
         proof_types
     }
 
-    /// Helper function to compute proof hash
+    /// Helper function to compute the canonical proof id
     fn compute_proof_hash(&self, proof_data: &[u8]) -> [u8; 32] {
-        use stylus_sdk::crypto::keccak;
-        keccak(proof_data)
+        crate::zk_integration::proof_id_of_json(proof_data)
     }
 
-    /// Extract nullifier hash from ZK proof (mock implementation)
-    fn extract_nullifier_hash(&self, _proof_data: &[u8]) -> Result<[u8; 32], Vec<u8>> {
-        // In a real implementation, this would parse the proof and extract the nullifier
-        Ok([0u8; 32]) // Placeholder
+    /// Record a verified proof type and its hash for a member
+    fn store_member_proof(&mut self, member: Address, proof_type: String, proof_hash: [u8; 32]) {
+        self.member_zk_proofs
+            .setter(member)
+            .setter(proof_type.clone())
+            .set(true);
+        
+        self.member_proof_hashes
+            .setter(member)
+            .setter(proof_type)
+            .set(FixedBytes::from(proof_hash));
+    }
+
+    /// Mark a nullifier as spent for a proposal and bump the proposal's count,
+    /// rejecting one already spent on that proposal
+    fn record_nullifier(&mut self, proposal_id: U256, nullifier: [u8; 32]) -> Result<(), Vec<u8>> {
+        if self.spent_nullifiers.get(proposal_id).get(nullifier) {
+            return Err(b"Nullifier already spent".to_vec());
+        }
+        
+        self.spent_nullifiers
+            .setter(proposal_id)
+            .setter(nullifier)
+            .set(true);
+        
+        let count = self.nullifier_counts.get(proposal_id);
+        self.nullifier_counts.setter(proposal_id).set(count + U256::from(1));
+        Ok(())
+    }
+
+    /// Count one private vote towards a proposal's yes or no tally
+    fn tally_private_vote(&mut self, proposal_id: U256, vote: bool) {
+        let tally = if vote {
+            &mut self.private_yes_votes
+        } else {
+            &mut self.private_no_votes
+        };
+        let count = tally.get(proposal_id);
+        tally.setter(proposal_id).set(count + U256::from(1));
+    }
+
+    /// Extract the nullifier hash from a nullifier proof's public inputs
+    fn extract_nullifier_hash(&self, proof_data: &[u8]) -> Result<[u8; 32], Vec<u8>> {
+        nullifier_proof_input(proof_data, NULLIFIER_INPUT)
     }
 
-    /// Extract commitment hash from ZK proof (mock implementation)
-    fn extract_commitment_hash(&self, _proof_data: &[u8]) -> Result<[u8; 32], Vec<u8>> {
-        // In a real implementation, this would parse the proof and extract the commitment
-        Ok([1u8; 32]) // Placeholder
+    /// Extract the member commitment from a nullifier proof's public inputs
+    fn extract_commitment_hash(&self, proof_data: &[u8]) -> Result<[u8; 32], Vec<u8>> {
+        nullifier_proof_input(proof_data, COMMITMENT_INPUT)
     }
 }
 
@@ -359,16 +468,144 @@ impl ZkEnhancedDAO {
             self.privacy_voting_enabled.get()
         )
     }
+
+    /// Get private-vote state for a proposal: (privacy enabled, nullifiers spent, required proof type)
+    /// 
+    /// The proof type is returned as keccak256 of its name (zero if none is required), the same
+    /// value indexers see for the indexed `requiredProofType` in ProposalZkRequirementSet.
+    pub fn get_proposal_privacy_stats(&self, proposal_id: U256) -> (bool, U256, [u8; 32]) {
+        (
+            self.privacy_voting_enabled.get(),
+            self.nullifier_counts.get(proposal_id),
+            proof_type_hash(&self.proposal_zk_requirements.get(proposal_id))
+        )
+    }
+}
+
+/// Public input layout of a private-vote nullifier proof: (nullifier, commitment)
+const NULLIFIER_INPUT: usize = 0;
+const COMMITMENT_INPUT: usize = 1;
+
+/// Read one public input of a nullifier proof, rejecting a missing or zero value
+fn nullifier_proof_input(proof_json: &[u8], index: usize) -> Result<[u8; 32], Vec<u8>> {
+    let inputs = proof_public_inputs(proof_json)
+        .ok_or_else(|| b"Malformed nullifier proof".to_vec())?;
+    match inputs.get(index) {
+        Some(input) if *input != [0u8; 32] => Ok(*input),
+        _ => Err(b"Nullifier proof missing public input".to_vec()),
+    }
+}
+
+/// Hash a proof type name as it appears in indexed event topics (zero for no requirement)
+fn proof_type_hash(proof_type: &str) -> [u8; 32] {
+    use stylus_sdk::crypto::keccak;
+    if proof_type.is_empty() {
+        return [0u8; 32];
+    }
+    keccak(proof_type.as_bytes())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use stylus_sdk::testing::TestVM;
+
+    const VOTE_CIRCUIT: &str = "private_vote";
+
+    fn owner() -> Address { Address::repeat_byte(0x01) }
+
+    /// A ZK DAO deployed by owner() with one open treasury proposal (id 1)
+    fn setup_zk_dao() -> (TestVM, ZkEnhancedDAO) {
+        let vm = TestVM::default();
+        vm.set_sender(owner());
+        vm.set_block_timestamp(1_000);
+        vm.set_block_number(1);
+        let mut dao = ZkEnhancedDAO::from(&vm);
+        dao.initialize_zk_dao(
+            Address::repeat_byte(0x02),
+            Address::repeat_byte(0x03),
+            Address::repeat_byte(0x04),
+            U256::from(3_600),
+            U256::from(20),
+            U256::from(86_400),
+            U256::ZERO,
+            owner(),
+        ).unwrap();
+        
+        dao.dao.submit_zk_proof(owner(), [0x11; 32], [0x12; 32]).unwrap();
+        dao.dao.create_proposal(
+            "Title".into(),
+            "Description".into(),
+            Address::repeat_byte(0x03),
+            U256::ZERO,
+            Vec::new(),
+            [0x11; 32],
+            [0x12; 32],
+        ).unwrap();
+        (vm, dao)
+    }
+
+    /// Verification key for VOTE_CIRCUIT
+    fn vote_vk() -> Bytes {
+        Bytes::from(serde_json::json!({
+            "circuit_name": VOTE_CIRCUIT,
+            "verification_key": {
+                "alpha_g1": "0x01", "beta_g2": "0x02", "gamma_g2": "0x03", "delta_g2": "0x04",
+                "ic": []
+            },
+            "key_type": "groth16",
+            "curve": "bn254",
+            "generated_at": "2024-01-01T00:00:00Z"
+        }).to_string().into_bytes())
+    }
+
+    /// A VOTE_CIRCUIT proof over the given public inputs
+    fn vote_proof(inputs: &[[u8; 32]]) -> Bytes {
+        let inputs: Vec<String> = inputs
+            .iter()
+            .map(|input| format!("0x{}", hex::encode(input)))
+            .collect();
+        Bytes::from(serde_json::json!({
+            "circuit_name": VOTE_CIRCUIT,
+            "proof": vec![7u8; 64],
+            "public_inputs": inputs,
+            "generated_at": "2024-01-01T00:00:00Z"
+        }).to_string().into_bytes())
+    }
 
     #[test]
     fn test_zk_dao_initialization() {
-        // Test initialization of ZK-enhanced DAO
-        // This would require proper test setup in a real implementation
+        let mut dao = ZkEnhancedDAO::default();
+        let token = Address::from([1u8; 20]);
+        let treasury = Address::from([2u8; 20]);
+        let registry = Address::from([3u8; 20]);
+        let zk_admin = Address::from([4u8; 20]);
+        
+        let init = |dao: &mut ZkEnhancedDAO| {
+            dao.initialize_zk_dao(
+                token,
+                treasury,
+                registry,
+                U256::from(3_600),
+                U256::from(20),
+                U256::from(86_400),
+                U256::from(100),
+                zk_admin,
+            )
+        };
+        
+        assert!(init(&mut dao).is_ok());
+        assert_eq!(dao.dao.owner(), msg::sender());
+        assert_eq!(dao.dao.shadow_id_registry(), registry);
+        assert_eq!(
+            dao.dao.get_parameters(),
+            (U256::from(3_600), U256::from(20), U256::from(86_400), U256::from(100))
+        );
+        assert_eq!(dao.zk_verifier.admin.get(), zk_admin);
+        assert_eq!(dao.get_zk_stats(), (U256::ZERO, true));
+        
+        // A second initialization cannot reset the base DAO
+        assert_eq!(init(&mut dao), Err(b"Already initialized".to_vec()));
     }
 
     #[test]
@@ -376,4 +613,164 @@ mod tests {
         // Test ZK proof verification for membership
         // This would require mock proofs and verification keys
     }
+
+    #[test]
+    fn test_nullifier_tracking() {
+        let mut dao = ZkEnhancedDAO::default();
+        let proposal_id = U256::from(1);
+        let other_proposal = U256::from(2);
+        
+        dao.record_nullifier(proposal_id, [1u8; 32]).unwrap();
+        dao.record_nullifier(proposal_id, [2u8; 32]).unwrap();
+        dao.record_nullifier(proposal_id, [3u8; 32]).unwrap();
+        
+        // The same identity may vote on another proposal, but not twice on one
+        dao.record_nullifier(other_proposal, [1u8; 32]).unwrap();
+        assert_eq!(
+            dao.record_nullifier(proposal_id, [1u8; 32]),
+            Err(b"Nullifier already spent".to_vec())
+        );
+        assert_eq!(
+            dao.record_nullifier(other_proposal, [1u8; 32]),
+            Err(b"Nullifier already spent".to_vec())
+        );
+        
+        assert_eq!(dao.nullifier_count(proposal_id), U256::from(3));
+        assert_eq!(dao.nullifier_count(other_proposal), U256::from(1));
+        
+        assert!(dao.is_nullifier_spent(proposal_id, [2u8; 32]));
+        assert!(!dao.is_nullifier_spent(proposal_id, [4u8; 32]));
+        assert!(!dao.is_nullifier_spent(other_proposal, [2u8; 32]));
+    }
+
+    #[test]
+    fn test_private_vote_nullifier_comes_from_proof() {
+        let (_vm, mut dao) = setup_zk_dao();
+        let proposal_id = U256::from(1);
+        let nullifier = [0x0a; 32];
+        let commitment = [0x0c; 32];
+        let membership = vote_proof(&[commitment]);
+        
+        // The nullifier recorded is the one the proof commits to
+        let proof = vote_proof(&[nullifier, commitment]);
+        dao.cast_private_vote(proposal_id, true, proof.clone(), membership.clone(), vote_vk()).unwrap();
+        assert!(dao.is_nullifier_spent(proposal_id, nullifier));
+        assert!(!dao.is_nullifier_spent(proposal_id, [0u8; 32]));
+        
+        // Replaying the same proof is rejected and not tallied again
+        assert_eq!(
+            dao.cast_private_vote(proposal_id, true, proof, membership.clone(), vote_vk()),
+            Err(b"Nullifier already spent".to_vec())
+        );
+        assert_eq!(dao.nullifier_count(proposal_id), U256::from(1));
+        
+        // A different identity's proof carries a different nullifier and counts
+        let other = vote_proof(&[[0x0b; 32], commitment]);
+        dao.cast_private_vote(proposal_id, false, other, membership.clone(), vote_vk()).unwrap();
+        assert_eq!(dao.get_private_tally(proposal_id), (U256::from(1), U256::from(1)));
+        
+        // Proofs without a usable nullifier input can't vote
+        assert_eq!(
+            dao.cast_private_vote(proposal_id, true, vote_proof(&[[0u8; 32], commitment]), membership.clone(), vote_vk()),
+            Err(b"Nullifier proof missing public input".to_vec())
+        );
+        assert_eq!(
+            dao.cast_private_vote(proposal_id, true, vote_proof(&[nullifier]), membership, vote_vk()),
+            Err(b"Nullifier proof missing public input".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_private_votes_tallied() {
+        let (vm, mut dao) = setup_zk_dao();
+        let proposal_id = U256::from(1);
+        let membership = vote_proof(&[[0x0c; 32]]);
+        let cast = |dao: &mut ZkEnhancedDAO, proposal_id: U256, voter: u8, vote: bool| {
+            let proof = vote_proof(&[[voter; 32], [0x0c; 32]]);
+            dao.cast_private_vote(proposal_id, vote, proof, membership.clone(), vote_vk())
+        };
+        
+        // Votes count in the private tally only; the token-weighted tally is untouched
+        for (voter, vote) in [(1, true), (2, false), (3, true), (4, true)] {
+            cast(&mut dao, proposal_id, voter, vote).unwrap();
+        }
+        assert_eq!(dao.get_private_tally(proposal_id), (U256::from(3), U256::from(1)));
+        let (.., for_votes, against_votes, abstain_votes, _, _) = dao.dao.get_proposal(proposal_id);
+        assert_eq!((for_votes, against_votes, abstain_votes), (U256::ZERO, U256::ZERO, U256::ZERO));
+        
+        // Proposals that don't exist, aren't active, or are past their window take no votes
+        assert_eq!(cast(&mut dao, U256::from(9), 5, true), Err(b"Proposal does not exist".to_vec()));
+        dao.dao.create_proposal(
+            "Second".into(),
+            "Cancelled".into(),
+            Address::repeat_byte(0x03),
+            U256::ZERO,
+            Vec::new(),
+            [0x11; 32],
+            [0x12; 32],
+        ).unwrap();
+        dao.dao.cancel_proposal(U256::from(2)).unwrap();
+        assert_eq!(cast(&mut dao, U256::from(2), 5, true), Err(b"Proposal is not active".to_vec()));
+        
+        vm.set_block_timestamp(1_000 + 3_600 + 1);
+        assert_eq!(cast(&mut dao, proposal_id, 5, true), Err(b"Voting period has ended".to_vec()));
+        
+        // Rejected votes leave no trace
+        assert_eq!(dao.get_private_tally(proposal_id), (U256::from(3), U256::from(1)));
+        assert_eq!(dao.get_private_tally(U256::from(2)), (U256::ZERO, U256::ZERO));
+        assert!(!dao.is_nullifier_spent(proposal_id, [5u8; 32]));
+    }
+
+    #[test]
+    fn test_proposal_privacy_stats() {
+        let mut dao = ZkEnhancedDAO::default();
+        let proposal_id = U256::from(1);
+        dao.privacy_voting_enabled.set(true);
+        dao.proposal_zk_requirements
+            .setter(proposal_id)
+            .set("citizenship_proof".to_string());
+        
+        // Three private votes; a replayed nullifier is rejected and not counted
+        dao.record_nullifier(proposal_id, [1u8; 32]).unwrap();
+        dao.record_nullifier(proposal_id, [2u8; 32]).unwrap();
+        assert!(dao.record_nullifier(proposal_id, [2u8; 32]).is_err());
+        dao.record_nullifier(proposal_id, [3u8; 32]).unwrap();
+        
+        let (enabled, spent, proof_type) = dao.get_proposal_privacy_stats(proposal_id);
+        assert!(enabled);
+        assert_eq!(spent, U256::from(3));
+        assert_eq!(proof_type, proof_type_hash("citizenship_proof"));
+        assert_ne!(proof_type, proof_type_hash("age_proof"));
+        
+        // A proposal without private votes or a requirement
+        assert_eq!(dao.get_proposal_privacy_stats(U256::from(2)), (true, U256::ZERO, [0u8; 32]));
+    }
+
+    #[test]
+    fn test_proposal_requirements() {
+        let mut dao = ZkEnhancedDAO::default();
+        let proposal_id = U256::from(1);
+        dao.proposal_zk_requirements
+            .setter(proposal_id)
+            .set("age_proof".to_string());
+        
+        assert_eq!(dao.proposal_requirements(proposal_id), (0, vec!["age_proof".to_string()]));
+        
+        // No named proof required
+        assert_eq!(dao.proposal_requirements(U256::from(2)), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_member_proof_hash_recorded() {
+        let mut dao = ZkEnhancedDAO::default();
+        let member = Address::from([7u8; 20]);
+        let proof_json = b"{\"circuit_name\":\"age_proof\",\"proof\":[1,2,3]}";
+        
+        let proof_hash = dao.compute_proof_hash(proof_json);
+        dao.store_member_proof(member, "age_proof".to_string(), proof_hash);
+        
+        assert!(dao.has_zk_proof(member, "age_proof".to_string()));
+        assert_eq!(dao.get_member_proof_hash(member, "age_proof".to_string()), proof_hash);
+        assert_eq!(dao.get_member_proof_hash(member, "citizenship_proof".to_string()), [0u8; 32]);
+    }
 }
\ No newline at end of file
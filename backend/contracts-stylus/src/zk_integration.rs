@@ -4,13 +4,15 @@
 //! providing ABI-compatible functions for on-chain verification.
 
 use stylus_sdk::{
-    alloy_primitives::{Address, Bytes, U256},
-    call::RawCall,
+    alloy_primitives::{Address, Bytes, FixedBytes, U256},
+    block, contract,
+    call::{RawCall, StaticCall},
     prelude::*,
-    storage::{StorageMap, StorageBool, StorageU256, StorageAddress},
+    storage::{StorageMap, StorageBool, StorageU256, StorageAddress, StorageVec, StorageFixedBytes},
 };
 
 use crate::zk_verifier::{verify_noir_proof, verify_noir_proof_with_result, VerificationResult};
+use crate::zk_noir_verifier::{proof_id, FieldElement, ProofSizeBounds};
 
 /// Storage layout for ZK verification state
 #[storage]
@@ -21,11 +23,37 @@ pub struct ZkVerificationStorage {
     /// Mapping of circuit name to verification key hash
     pub circuit_vk_hashes: StorageMap<String, [u8; 32]>,
     
+    /// Names of circuits that currently have a verification key
+    pub registered_circuits: StorageVec<String>,
+    
+    /// Per-circuit proof size bounds in bytes (max of 0 = no bounds registered)
+    pub circuit_min_proof_sizes: StorageMap<String, StorageU256>,
+    pub circuit_max_proof_sizes: StorageMap<String, StorageU256>,
+    
+    /// Mapping of verified proof hash to the hash of its public inputs
+    pub public_inputs_hashes: StorageMap<[u8; 32], [u8; 32]>,
+    
     /// Total number of proofs verified
     pub total_verifications: StorageU256,
     
     /// Admin address that can manage verification keys
     pub admin: StorageAddress<Address>,
+    
+    /// Shared EmergencyStop coordinator (zero = none)
+    pub emergency_stop: StorageAddress<Address>,
+    
+    /// Off-chain verifier whose signed attestations mark proofs verified (zero = disabled)
+    pub trusted_verifier: StorageAddress<Address>,
+    
+    /// Selector verify_and_callback calls on each allowed target (zero = not allowed)
+    pub callback_selectors: StorageMap<Address, StorageFixedBytes<4>>,
+}
+
+sol_interface! {
+    /// Ecosystem-wide circuit breaker checked before recording verifications
+    interface IEmergencyStop {
+        function isStopped() external view returns (bool);
+    }
 }
 
 /// Events emitted by the ZK verifier
@@ -53,6 +81,53 @@ sol_interface! {
         bytes32 indexed vkHash,
         address indexed admin
     );
+    
+    /// Emitted when a proof is marked verified from an off-chain attestation
+    event ProofAttested(
+        bytes32 indexed proofHash,
+        address indexed trustedVerifier,
+        address indexed submitter
+    );
+    
+    /// Emitted when verify_and_callback notifies a target of a verified proof
+    event VerificationCallback(
+        bytes32 indexed proofHash,
+        address indexed target,
+        bytes4 selector,
+        bytes32 publicInputsHash
+    );
+    
+    /// Emitted when a callback target's selector is set (zero selector = disallowed)
+    event CallbackTargetUpdated(
+        address indexed target,
+        bytes4 selector
+    );
+    
+    /// Emitted when the trusted off-chain verifier changes
+    event TrustedVerifierUpdated(
+        address indexed oldVerifier,
+        address indexed newVerifier
+    );
+    
+    /// Emitted when a circuit's verification key is removed
+    event VerificationKeyUnregistered(
+        string indexed circuitName,
+        address indexed admin
+    );
+    
+    /// Emitted when the shared EmergencyStop coordinator changes
+    event EmergencyStopContractUpdated(
+        address indexed oldContract,
+        address indexed newContract
+    );
+    
+    /// Emitted when proof size bounds are registered for a circuit
+    event ProofSizeBoundsRegistered(
+        string indexed circuitName,
+        uint256 minSize,
+        uint256 maxSize,
+        address indexed admin
+    );
 }
 
 /// Stylus contract for ZK proof verification
@@ -75,50 +150,16 @@ impl ZkVerificationStorage {
     /// * `vk_json` - JSON-encoded verification key
     /// 
     /// # Returns
-    /// * `bool` - true if proof is valid and verified
+    /// * `bool` - true if proof is valid and verified (false for invalid or malformed input)
     pub fn verify_zk_proof(&mut self, proof_json: Bytes, vk_json: Bytes) -> Result<bool, Vec<u8>> {
-        // Convert Bytes to &[u8] for verification
-        let proof_bytes = proof_json.as_ref();
-        let vk_bytes = vk_json.as_ref();
+        self.when_not_stopped()?;
         
-        // Perform detailed verification
-        match verify_noir_proof_with_result(proof_bytes, vk_bytes) {
-            Ok(result) => {
-                let proof_hash = self.compute_proof_hash(proof_bytes);
-                
-                if result.valid {
-                    // Store verification result
-                    self.verified_proofs.setter(proof_hash).set(true);
-                    self.total_verifications.set(self.total_verifications.get() + U256::from(1));
-                    
-                    // Emit success event
-                    evm::log(ProofVerified {
-                        proofHash: proof_hash,
-                        circuitName: result.circuit_name,
-                        verifier: msg::sender(),
-                        publicInputsHash: self.hex_to_bytes32(&result.public_inputs_hash)?,
-                        gasUsed: U256::from(result.gas_used.unwrap_or(0)),
-                    });
-                    
-                    Ok(true)
-                } else {
-                    // Emit failure event
-                    evm::log(ProofVerificationFailed {
-                        proofHash: proof_hash,
-                        circuitName: result.circuit_name,
-                        verifier: msg::sender(),
-                        reason: result.error.unwrap_or("Verification failed".to_string()),
-                    });
-                    
-                    Ok(false)
-                }
-            }
+        match self.run_verification(proof_json.as_ref(), vk_json.as_ref())? {
+            Ok(result) => Ok(result.valid),
             Err(error_msg) => {
-                let proof_hash = self.compute_proof_hash(proof_bytes);
-                
                 // Emit failure event
                 evm::log(ProofVerificationFailed {
-                    proofHash: proof_hash,
+                    proofHash: self.compute_proof_hash(proof_json.as_ref()),
                     circuitName: "unknown".to_string(),
                     verifier: msg::sender(),
                     reason: error_msg,
@@ -129,6 +170,88 @@ impl ZkVerificationStorage {
         }
     }
 
+    /// Verify a Noir ZK proof and return the full verification result
+    /// 
+    /// Unlike `verify_zk_proof`, malformed input or a verifier error reverts with its reason
+    /// instead of collapsing into `false`; an `Ok` result with `valid == false` means the
+    /// proof was well-formed but did not verify (`error` says why).
+    pub fn verify_zk_proof_detailed(
+        &mut self,
+        proof_json: Bytes,
+        vk_json: Bytes
+    ) -> Result<VerificationResult, Vec<u8>> {
+        self.when_not_stopped()?;
+        
+        self.run_verification(proof_json.as_ref(), vk_json.as_ref())?
+            .map_err(|error_msg| error_msg.into_bytes())
+    }
+
+    /// Verify a proof and, if valid, call `callback_target` with its public-inputs hash
+    /// 
+    /// The callback receives the target's registered selector followed by
+    /// `abi.encode(bytes32 publicInputsHash)`, so a DAO can register membership in the same
+    /// transaction. Callers pick the target, never the function called on it. A failing
+    /// callback reverts the whole call, including the verification record.
+    /// 
+    /// # Returns
+    /// * `bool` - false (and no callback) if the proof did not verify
+    pub fn verify_and_callback(
+        &mut self,
+        proof_json: Bytes,
+        vk_json: Bytes,
+        callback_target: Address
+    ) -> Result<bool, Vec<u8>> {
+        let callback_selector = self.callback_selectors.get(callback_target);
+        if callback_selector == FixedBytes::ZERO {
+            return Err(b"Callback target not allowed".to_vec());
+        }
+        
+        let proof_hash = self.compute_proof_hash(proof_json.as_ref());
+        if !self.verify_zk_proof(proof_json, vk_json)? {
+            return Ok(false);
+        }
+        
+        let public_inputs_hash = self.public_inputs_hashes.get(proof_hash);
+        RawCall::new()
+            .call(callback_target, &callback_calldata(callback_selector, public_inputs_hash))
+            .map_err(|_| b"Verification callback failed".to_vec())?;
+        
+        evm::log(VerificationCallback {
+            proofHash: proof_hash,
+            target: callback_target,
+            selector: callback_selector,
+            publicInputsHash: public_inputs_hash,
+        });
+        
+        Ok(true)
+    }
+
+    /// Allow a verify_and_callback target and fix the selector it is called with; a zero
+    /// selector disallows it (admin only)
+    pub fn set_callback_target(&mut self, target: Address, selector: FixedBytes<4>) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.admin.get() {
+            return Err(b"Only admin can manage callback targets".to_vec());
+        }
+        if target == Address::ZERO {
+            return Err(b"Invalid callback target".to_vec());
+        }
+        
+        self.callback_selectors.setter(target).set(selector);
+        
+        evm::log(CallbackTargetUpdated { target, selector });
+        Ok(())
+    }
+
+    /// Check if verify_and_callback may notify an address
+    pub fn is_callback_target(&self, target: Address) -> bool {
+        self.callback_selectors.get(target) != FixedBytes::ZERO
+    }
+
+    /// Selector verify_and_callback calls on a target (zero if not allowed)
+    pub fn callback_selector(&self, target: Address) -> FixedBytes<4> {
+        self.callback_selectors.get(target)
+    }
+
     /// Batch verify multiple proofs for gas efficiency
     pub fn batch_verify_proofs(
         &mut self, 
@@ -149,6 +272,67 @@ impl ZkVerificationStorage {
         Ok(results)
     }
 
+    /// Mark a proof verified from the trusted verifier's signature over its canonical id
+    /// 
+    /// The verifier checks the proof off-chain and signs `proof_hash` together with the chain
+    /// id and this contract's address (EIP-191 personal message), replacing ~100k gas of
+    /// on-chain verification with a ~5k gas ecrecover. The binding keeps an attestation from
+    /// being replayed on another deployment; resubmitting an already verified id is a no-op.
+    /// 
+    /// # Arguments
+    /// * `proof_hash` - Canonical proof id (`zk_noir_verifier::proof_id`)
+    /// * `signature` - 65-byte (r, s, v) signature from `trusted_verifier`
+    pub fn attest_proof(&mut self, proof_hash: [u8; 32], signature: Bytes) -> Result<(), Vec<u8>> {
+        self.when_not_stopped()?;
+        
+        let trusted_verifier = self.trusted_verifier.get();
+        if trusted_verifier == Address::ZERO {
+            return Err(b"No trusted verifier configured".to_vec());
+        }
+        
+        // Already verified (attested or proven): nothing to record again
+        if self.verified_proofs.get(proof_hash) {
+            return Ok(());
+        }
+        
+        let digest = attestation_digest(proof_hash, block::chainid(), contract::address());
+        let signer = self.recover_signer(digest, signature.as_ref())?;
+        check_attestation_signer(signer, trusted_verifier)?;
+        
+        // Attestations carry no public inputs, so none are linked
+        self.record_verification(proof_hash, [0u8; 32]);
+        
+        evm::log(ProofAttested {
+            proofHash: proof_hash,
+            trustedVerifier: trusted_verifier,
+            submitter: msg::sender(),
+        });
+        
+        Ok(())
+    }
+
+    /// Set the off-chain verifier trusted for attestations, or zero to disable (admin only)
+    pub fn set_trusted_verifier(&mut self, new_verifier: Address) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.admin.get() {
+            return Err(b"Only admin can set trusted verifier".to_vec());
+        }
+        
+        let old_verifier = self.trusted_verifier.get();
+        self.trusted_verifier.set(new_verifier);
+        
+        evm::log(TrustedVerifierUpdated {
+            oldVerifier: old_verifier,
+            newVerifier: new_verifier,
+        });
+        
+        Ok(())
+    }
+
+    /// Get the trusted off-chain verifier (zero if attestations are disabled)
+    pub fn get_trusted_verifier(&self) -> Address {
+        self.trusted_verifier.get()
+    }
+
     /// Register a verification key for a specific circuit
     pub fn register_verification_key(
         &mut self, 
@@ -161,7 +345,7 @@ impl ZkVerificationStorage {
         }
         
         let vk_hash = self.compute_vk_hash(vk_json.as_ref());
-        self.circuit_vk_hashes.setter(circuit_name.clone()).set(vk_hash);
+        self.set_circuit_vk_hash(circuit_name.clone(), vk_hash);
         
         // Emit registration event
         evm::log(VerificationKeyRegistered {
@@ -173,6 +357,74 @@ impl ZkVerificationStorage {
         Ok(())
     }
 
+    /// Remove a circuit's verification key (admin only)
+    pub fn unregister_verification_key(&mut self, circuit_name: String) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.admin.get() {
+            return Err(b"Only admin can unregister verification keys".to_vec());
+        }
+        if !self.clear_circuit_vk_hash(circuit_name.clone()) {
+            return Err(b"Circuit not registered".to_vec());
+        }
+        
+        evm::log(VerificationKeyUnregistered {
+            circuitName: circuit_name,
+            admin: msg::sender(),
+        });
+        
+        Ok(())
+    }
+
+    /// List every circuit with a registered verification key
+    pub fn get_registered_circuits(&self) -> Vec<String> {
+        let mut circuits = Vec::new();
+        for i in 0..self.registered_circuits.len() {
+            if let Some(circuit_name) = self.registered_circuits.get(i) {
+                circuits.push(circuit_name);
+            }
+        }
+        circuits
+    }
+
+    /// Number of circuits with a registered verification key
+    pub fn registered_circuit_count(&self) -> U256 {
+        U256::from(self.registered_circuits.len())
+    }
+
+    /// Register the accepted proof size range for a circuit (admin only)
+    pub fn register_proof_size_bounds(
+        &mut self,
+        circuit_name: String,
+        min_size: U256,
+        max_size: U256
+    ) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.admin.get() {
+            return Err(b"Only admin can register proof size bounds".to_vec());
+        }
+        if ProofSizeBounds::new(min_size.saturating_to(), max_size.saturating_to()).is_none() {
+            return Err(b"Invalid proof size bounds".to_vec());
+        }
+        
+        self.circuit_min_proof_sizes.setter(circuit_name.clone()).set(min_size);
+        self.circuit_max_proof_sizes.setter(circuit_name.clone()).set(max_size);
+        
+        evm::log(ProofSizeBoundsRegistered {
+            circuitName: circuit_name,
+            minSize: min_size,
+            maxSize: max_size,
+            admin: msg::sender(),
+        });
+        
+        Ok(())
+    }
+
+    /// Get the registered (min, max) proof size for a circuit ((0, 0) if unregistered)
+    pub fn get_proof_size_bounds(&self, circuit_name: String) -> (U256, U256) {
+        (
+            self.circuit_min_proof_sizes.get(circuit_name.clone()),
+            self.circuit_max_proof_sizes.get(circuit_name),
+        )
+    }
+
     /// Check if a proof has been previously verified
     pub fn is_proof_verified(&self, proof_json: Bytes) -> bool {
         let proof_hash = self.compute_proof_hash(proof_json.as_ref());
@@ -184,6 +436,11 @@ impl ZkVerificationStorage {
         self.circuit_vk_hashes.get(circuit_name)
     }
 
+    /// Get the public-inputs hash a proof was verified against (zero if never verified)
+    pub fn get_public_inputs_hash(&self, proof_hash: [u8; 32]) -> [u8; 32] {
+        self.public_inputs_hashes.get(proof_hash)
+    }
+
     /// Get total number of successful verifications
     pub fn get_total_verifications(&self) -> U256 {
         self.total_verifications.get()
@@ -225,10 +482,156 @@ impl ZkVerificationStorage {
         Ok(())
     }
 
-    /// Compute SHA256 hash of proof data
+    /// Point at a shared EmergencyStop coordinator, or zero to detach (admin only)
+    pub fn set_emergency_stop_contract(&mut self, new_contract: Address) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.admin.get() {
+            return Err(b"Only admin can set emergency stop".to_vec());
+        }
+        
+        let old_contract = self.emergency_stop.get();
+        self.emergency_stop.set(new_contract);
+        
+        evm::log(EmergencyStopContractUpdated {
+            oldContract: old_contract,
+            newContract: new_contract,
+        });
+        Ok(())
+    }
+
+    /// Reject state changes while the shared EmergencyStop coordinator is engaged
+    fn when_not_stopped(&self) -> Result<(), Vec<u8>> {
+        let coordinator = self.emergency_stop.get();
+        if coordinator == Address::ZERO {
+            return Ok(());
+        }
+        
+        let stopped = IEmergencyStop::new(coordinator)
+            .is_stopped(StaticCall::new())
+            .map_err(|_| b"Emergency stop check failed".to_vec())?;
+        if stopped {
+            return Err(b"Emergency stop active".to_vec());
+        }
+        Ok(())
+    }
+
+    /// Store a circuit's verification key hash, listing the circuit on first registration
+    fn set_circuit_vk_hash(&mut self, circuit_name: String, vk_hash: [u8; 32]) {
+        if self.circuit_vk_hashes.get(circuit_name.clone()) == [0u8; 32] {
+            self.registered_circuits.push(circuit_name.clone());
+        }
+        self.circuit_vk_hashes.setter(circuit_name).set(vk_hash);
+    }
+
+    /// Clear a circuit's verification key hash and delist it; false if it was not registered
+    fn clear_circuit_vk_hash(&mut self, circuit_name: String) -> bool {
+        if self.circuit_vk_hashes.get(circuit_name.clone()) == [0u8; 32] {
+            return false;
+        }
+        self.circuit_vk_hashes.setter(circuit_name.clone()).set([0u8; 32]);
+        
+        // Swap-remove to keep the list compact
+        let len = self.registered_circuits.len();
+        for i in 0..len {
+            if self.registered_circuits.get(i) == Some(circuit_name.clone()) {
+                if let Some(last) = self.registered_circuits.get(len - 1) {
+                    self.registered_circuits.setter(i).set(last);
+                }
+                self.registered_circuits.pop();
+                break;
+            }
+        }
+        true
+    }
+
+    /// Mark a proof as verified and link it to its public inputs
+    fn record_verification(&mut self, proof_hash: [u8; 32], public_inputs_hash: [u8; 32]) {
+        self.verified_proofs.setter(proof_hash).set(true);
+        self.public_inputs_hashes.setter(proof_hash).set(public_inputs_hash);
+        self.total_verifications.set(self.total_verifications.get() + U256::from(1));
+    }
+
+    /// Recover the address that signed `digest` through the ecrecover precompile
+    fn recover_signer(&self, digest: [u8; 32], signature: &[u8]) -> Result<Address, Vec<u8>> {
+        let input = ecrecover_input(digest, signature)?;
+        let output = RawCall::new_static()
+            .call(Address::with_last_byte(ECRECOVER_PRECOMPILE), &input)
+            .map_err(|_| b"ecrecover call failed".to_vec())?;
+        
+        // The precompile returns nothing for an unrecoverable signature
+        if output.len() != 32 {
+            return Err(b"Invalid attestation signature".to_vec());
+        }
+        Ok(Address::from_slice(&output[12..]))
+    }
+
+    /// Proof size bounds registered for a circuit, if any
+    fn registered_proof_size_bounds(&self, circuit_name: &str) -> Option<ProofSizeBounds> {
+        let min_size = self.circuit_min_proof_sizes.get(circuit_name.to_string());
+        let max_size = self.circuit_max_proof_sizes.get(circuit_name.to_string());
+        ProofSizeBounds::new(min_size.saturating_to(), max_size.saturating_to())
+    }
+
+    /// Shared verification path: enforces proof size bounds, runs the verifier, then records
+    /// and logs the outcome. The inner `Err` carries a malformed-input or verifier error
+    fn run_verification(
+        &mut self,
+        proof_bytes: &[u8],
+        vk_bytes: &[u8]
+    ) -> Result<Result<VerificationResult, String>, Vec<u8>> {
+        let proof_hash = self.compute_proof_hash(proof_bytes);
+        
+        // Enforce the circuit's registered proof size before running the verifier
+        if let Some((circuit_name, proof_size)) = proof_circuit_and_size(proof_bytes) {
+            let bounds = self.registered_proof_size_bounds(&circuit_name);
+            let checked = bounds.map_or(Ok(()), |bounds| bounds.check(proof_size));
+            if let Err(error) = checked {
+                let reason = format!("{} for circuit {}", error, circuit_name);
+                evm::log(ProofVerificationFailed {
+                    proofHash: proof_hash,
+                    circuitName: circuit_name.clone(),
+                    verifier: msg::sender(),
+                    reason: reason.clone(),
+                });
+                return Ok(Ok(rejected_result(circuit_name, proof_hash, reason)));
+            }
+        }
+        
+        // Perform detailed verification
+        let result = match verify_noir_proof_with_result(proof_bytes, vk_bytes) {
+            Ok(result) => result,
+            Err(error_msg) => return Ok(Err(error_msg)),
+        };
+        
+        if result.valid {
+            let public_inputs_hash = self.hex_to_bytes32(&result.public_inputs_hash)?;
+            
+            // Store verification result
+            self.record_verification(proof_hash, public_inputs_hash);
+            
+            // Emit success event
+            evm::log(ProofVerified {
+                proofHash: proof_hash,
+                circuitName: result.circuit_name.clone(),
+                verifier: msg::sender(),
+                publicInputsHash: public_inputs_hash,
+                gasUsed: U256::from(result.gas_used.unwrap_or(0)),
+            });
+        } else {
+            // Emit failure event
+            evm::log(ProofVerificationFailed {
+                proofHash: proof_hash,
+                circuitName: result.circuit_name.clone(),
+                verifier: msg::sender(),
+                reason: result.error.clone().unwrap_or("Verification failed".to_string()),
+            });
+        }
+        
+        Ok(Ok(result))
+    }
+
+    /// Compute the canonical proof id of a proof JSON
     fn compute_proof_hash(&self, proof_data: &[u8]) -> [u8; 32] {
-        use stylus_sdk::crypto::keccak;
-        keccak(proof_data)
+        proof_id_of_json(proof_data)
     }
 
     /// Compute SHA256 hash of verification key data
@@ -273,8 +676,150 @@ impl ZkVerificationStorage {
         let proof_size_cost = proof_json.len() * 10; // Per-byte cost
         let vk_size_cost = vk_json.len() * 5; // VK processing cost
         
-        U256::from(base_cost + proof_size_cost + vk_size_cost)
+        // One scalar multiplication per public input dominates Groth16 cost
+        let input_cost = count_public_inputs(proof_json.as_ref()) * GAS_PER_PUBLIC_INPUT;
+        
+        U256::from(base_cost + proof_size_cost + vk_size_cost + input_cost)
+    }
+}
+
+/// Per-input verification cost, matching `zk_noir_verifier::utils::estimate_verification_gas`
+const GAS_PER_PUBLIC_INPUT: usize = 5_000;
+
+/// Count the public inputs in a proof JSON (`public_inputs` or `publicInputs`)
+fn count_public_inputs(proof_json: &[u8]) -> usize {
+    let parsed: serde_json::Value = match serde_json::from_slice(proof_json) {
+        Ok(value) => value,
+        Err(_) => return 0,
+    };
+    
+    parsed
+        .get("public_inputs")
+        .or_else(|| parsed.get("publicInputs"))
+        .and_then(|inputs| inputs.as_array())
+        .map(|inputs| inputs.len())
+        .unwrap_or(0)
+}
+
+/// Result for a well-formed proof rejected before reaching the verifier
+fn rejected_result(
+    circuit_name: String,
+    proof_hash: [u8; 32],
+    reason: String,
+) -> VerificationResult {
+    VerificationResult {
+        valid: false,
+        circuit_name,
+        proof_hash: hex::encode(proof_hash),
+        public_inputs_hash: String::new(),
+        gas_used: None,
+        error: Some(reason),
+        verified_at: 0,
+    }
+}
+
+/// Calldata for a verification callback: selector followed by the public-inputs hash
+fn callback_calldata(selector: FixedBytes<4>, public_inputs_hash: [u8; 32]) -> Vec<u8> {
+    let mut calldata = Vec::with_capacity(36);
+    calldata.extend_from_slice(selector.as_slice());
+    calldata.extend_from_slice(&public_inputs_hash);
+    calldata
+}
+
+/// Address of the ecrecover precompile
+const ECRECOVER_PRECOMPILE: u8 = 0x01;
+
+/// EIP-191 personal-message digest the trusted verifier signs for a proof id, bound to one
+/// chain and one verifier contract: proof id (32) ++ chain id (32, big-endian) ++ address (20)
+fn attestation_digest(proof_hash: [u8; 32], chain_id: u64, verifier_contract: Address) -> [u8; 32] {
+    let mut message = Vec::with_capacity(112);
+    message.extend_from_slice(b"\x19Ethereum Signed Message:\n84");
+    message.extend_from_slice(&proof_hash);
+    message.extend_from_slice(&U256::from(chain_id).to_be_bytes::<32>());
+    message.extend_from_slice(verifier_contract.as_slice());
+    stylus_sdk::crypto::keccak(&message).into()
+}
+
+/// Build the ecrecover precompile input (digest, v, r, s) from a 65-byte (r, s, v) signature
+fn ecrecover_input(digest: [u8; 32], signature: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    if signature.len() != 65 {
+        return Err(b"Invalid attestation signature length".to_vec());
     }
+    
+    // Accept both 0/1 and 27/28 recovery ids
+    let v = match signature[64] {
+        0 | 1 => signature[64] + 27,
+        27 | 28 => signature[64],
+        _ => return Err(b"Invalid attestation signature".to_vec()),
+    };
+    
+    let mut input = Vec::with_capacity(128);
+    input.extend_from_slice(&digest);
+    input.extend_from_slice(&[0u8; 31]);
+    input.push(v);
+    input.extend_from_slice(&signature[..64]);
+    Ok(input)
+}
+
+/// Only signatures from the configured trusted verifier are accepted
+fn check_attestation_signer(signer: Address, trusted_verifier: Address) -> Result<(), Vec<u8>> {
+    if signer == Address::ZERO || signer != trusted_verifier {
+        return Err(b"Attestation not signed by trusted verifier".to_vec());
+    }
+    Ok(())
+}
+
+/// Canonical proof id (`zk_noir_verifier::proof_id`) of a proof JSON, so on-chain records
+/// match off-chain attestations. JSON without a parseable proof and public inputs is
+/// identified by its raw bytes
+pub fn proof_id_of_json(proof_json: &[u8]) -> [u8; 32] {
+    match proof_parts(proof_json) {
+        Some((proof_bytes, public_inputs)) => proof_id(&proof_bytes, &public_inputs),
+        None => proof_id(proof_json, &[]),
+    }
+}
+
+/// Extract the proof bytes and concatenated 32-byte public inputs from a proof JSON
+fn proof_parts(proof_json: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
+    let parsed: serde_json::Value = serde_json::from_slice(proof_json).ok()?;
+    
+    let proof_bytes = parsed
+        .get("proof")?
+        .as_array()?
+        .iter()
+        .map(|byte| u8::try_from(byte.as_u64()?).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    
+    let mut public_inputs = Vec::new();
+    for input in parsed.get("public_inputs")?.as_array()? {
+        let field = FieldElement::from_hex(input.as_str()?).ok()?;
+        public_inputs.extend_from_slice(&field.bytes);
+    }
+    Some((proof_bytes, public_inputs))
+}
+
+/// Public inputs of a proof JSON as 32-byte field elements, in circuit order
+pub fn proof_public_inputs(proof_json: &[u8]) -> Option<Vec<[u8; 32]>> {
+    let (_, public_inputs) = proof_parts(proof_json)?;
+    Some(
+        public_inputs
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut input = [0u8; 32];
+                input.copy_from_slice(chunk);
+                input
+            })
+            .collect(),
+    )
+}
+
+/// Extract the circuit name and proof length from a proof JSON
+fn proof_circuit_and_size(proof_json: &[u8]) -> Option<(String, usize)> {
+    let parsed: serde_json::Value = serde_json::from_slice(proof_json).ok()?;
+    
+    let circuit_name = parsed.get("circuit_name")?.as_str()?.to_string();
+    let proof_size = parsed.get("proof")?.as_array()?.len();
+    Some((circuit_name, proof_size))
 }
 
 #[cfg(test)]
@@ -291,4 +836,381 @@ mod tests {
         
         assert_eq!(hash1, hash2); // Should be deterministic
     }
+
+    #[test]
+    fn test_proof_id_matches_off_chain() {
+        use crate::zk_noir_verifier::NoirProof;
+        
+        let storage = ZkVerificationStorage::default();
+        let on_chain_json = br#"{
+            "circuit_name": "age_proof",
+            "proof": [222, 173, 190, 239],
+            "public_inputs": ["0x12", "0x0034"],
+            "generated_at": "2024-01-01T00:00:00Z"
+        }"#;
+        let off_chain = NoirProof {
+            proof: "0xdeadbeef".to_string(),
+            public_inputs: vec!["0x12".to_string(), "0x34".to_string()],
+        };
+        
+        let on_chain_id = storage.compute_proof_hash(on_chain_json);
+        assert_eq!(on_chain_id, off_chain.proof_id().unwrap());
+        
+        let mut public_inputs = vec![0u8; 64];
+        public_inputs[31] = 0x12;
+        public_inputs[63] = 0x34;
+        assert_eq!(on_chain_id, proof_id(&[0xde, 0xad, 0xbe, 0xef], &public_inputs));
+        
+        // Unparseable input falls back to hashing the raw bytes
+        assert_eq!(storage.compute_proof_hash(b"not json"), proof_id(b"not json", &[]));
+    }
+
+    #[test]
+    fn test_proof_attestation() {
+        use stylus_sdk::testing::TestVM;
+        
+        let admin = Address::repeat_byte(0x0a);
+        let trusted = Address::repeat_byte(0x7e);
+        let submitter = Address::repeat_byte(0x5b);
+        let vm = TestVM::default();
+        vm.set_chain_id(42_161);
+        vm.set_sender(admin);
+        let mut storage = ZkVerificationStorage::from(&vm);
+        storage.initialize(admin).unwrap();
+        
+        let proof_json: &[u8] = br#"{"proof": [1, 2, 3], "public_inputs": ["0x01"]}"#;
+        let proof_hash = storage.compute_proof_hash(proof_json);
+        let mut signature = vec![0x11u8; 32];
+        signature.extend_from_slice(&[0x22u8; 32]);
+        signature.push(1);
+        let signature = Bytes::from(signature);
+        
+        // Nothing is accepted until the admin configures a verifier
+        vm.set_sender(submitter);
+        assert_eq!(
+            storage.attest_proof(proof_hash, signature.clone()),
+            Err(b"No trusted verifier configured".to_vec())
+        );
+        assert_eq!(
+            storage.set_trusted_verifier(trusted),
+            Err(b"Only admin can set trusted verifier".to_vec())
+        );
+        vm.set_sender(admin);
+        storage.set_trusted_verifier(trusted).unwrap();
+        assert_eq!(storage.get_trusted_verifier(), trusted);
+        
+        // The precompile gets the personal-message digest of the proof id, chain id and this
+        // contract's address, then v, r, s
+        let contract_address = vm.contract_address();
+        let ecrecover_call = |proof_hash: [u8; 32], chain_id: u64| {
+            let mut message = b"\x19Ethereum Signed Message:\n84".to_vec();
+            message.extend_from_slice(&proof_hash);
+            message.extend_from_slice(&U256::from(chain_id).to_be_bytes::<32>());
+            message.extend_from_slice(contract_address.as_slice());
+            let mut input = stylus_sdk::crypto::keccak(&message).to_vec();
+            input.extend_from_slice(&[0u8; 31]);
+            input.push(28);
+            input.extend_from_slice(&signature[..64]);
+            input
+        };
+        let input = ecrecover_call(proof_hash, 42_161);
+        let ecrecover = Address::with_last_byte(ECRECOVER_PRECOMPILE);
+        
+        // A signature recovering to anyone else is rejected
+        vm.set_sender(submitter);
+        vm.mock_static_call(ecrecover, input.clone(), Ok(submitter.into_word().to_vec()));
+        assert_eq!(
+            storage.attest_proof(proof_hash, signature.clone()),
+            Err(b"Attestation not signed by trusted verifier".to_vec())
+        );
+        
+        // So is an unrecoverable signature or a malformed one
+        vm.mock_static_call(ecrecover, input.clone(), Ok(Vec::new()));
+        assert_eq!(
+            storage.attest_proof(proof_hash, signature.clone()),
+            Err(b"Invalid attestation signature".to_vec())
+        );
+        assert_eq!(
+            storage.attest_proof(proof_hash, Bytes::from(signature[..64].to_vec())),
+            Err(b"Invalid attestation signature length".to_vec())
+        );
+        assert!(!storage.is_proof_verified(Bytes::from(proof_json.to_vec())));
+        
+        // The trusted verifier's signature marks the proof verified, from any submitter
+        vm.mock_static_call(ecrecover, input, Ok(trusted.into_word().to_vec()));
+        storage.attest_proof(proof_hash, signature.clone()).unwrap();
+        assert!(storage.is_proof_verified(Bytes::from(proof_json.to_vec())));
+        assert_eq!(storage.get_public_inputs_hash(proof_hash), [0u8; 32]);
+        assert_eq!(storage.get_total_verifications(), U256::from(1));
+        let logs = vm.get_emitted_logs();
+        let (topics, _) = logs.last().unwrap().clone();
+        assert_eq!(topics[0], ProofAttested::SIGNATURE_HASH);
+        assert_eq!(topics[1], FixedBytes::<32>::from(proof_hash));
+        assert_eq!(topics[2], trusted.into_word());
+        assert_eq!(topics[3], submitter.into_word());
+        
+        // Resubmitting the same attestation records nothing further
+        storage.attest_proof(proof_hash, signature.clone()).unwrap();
+        assert_eq!(storage.get_total_verifications(), U256::from(1));
+        assert_eq!(vm.get_emitted_logs().len(), logs.len());
+        
+        // An attestation made for another chain recovers to some other key here
+        let other_proof = [0x0fu8; 32];
+        let foreign = Address::repeat_byte(0xf0);
+        vm.mock_static_call(ecrecover, ecrecover_call(other_proof, 1), Ok(trusted.into_word().to_vec()));
+        vm.mock_static_call(ecrecover, ecrecover_call(other_proof, 42_161), Ok(foreign.into_word().to_vec()));
+        assert_eq!(
+            storage.attest_proof(other_proof, signature.clone()),
+            Err(b"Attestation not signed by trusted verifier".to_vec())
+        );
+        assert_eq!(storage.get_total_verifications(), U256::from(1));
+        
+        // Clearing the verifier disables attestations again
+        vm.set_sender(admin);
+        storage.set_trusted_verifier(Address::ZERO).unwrap();
+        assert_eq!(
+            storage.attest_proof(other_proof, signature),
+            Err(b"No trusted verifier configured".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_verify_and_callback_calls_registered_selector() {
+        use stylus_sdk::testing::TestVM;
+        
+        let admin = Address::repeat_byte(0x01);
+        let dao = Address::repeat_byte(0xda);
+        let vm = TestVM::default();
+        vm.set_sender(admin);
+        let mut storage = ZkVerificationStorage::from(&vm);
+        storage.initialize(admin).unwrap();
+        
+        let bytes = |json: &str| Bytes::from(json.as_bytes().to_vec());
+        let vk_json = bytes(r#"{
+            "circuit_name": "membership",
+            "verification_key": {
+                "alpha_g1": "0x01", "beta_g2": "0x02", "gamma_g2": "0x03", "delta_g2": "0x04",
+                "ic": []
+            },
+            "key_type": "groth16",
+            "curve": "bn254",
+            "generated_at": "2024-01-01T00:00:00Z"
+        }"#);
+        let input = "0x5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e5e";
+        let proof_json = bytes(&format!(
+            r#"{{"circuit_name": "membership", "proof": {:?}, "public_inputs": ["{}"],
+                "generated_at": "2024-01-01T00:00:00Z"}}"#,
+            vec![7u8; 64],
+            input
+        ));
+        
+        // Unregistered targets can't be called back
+        assert_eq!(
+            storage.verify_and_callback(proof_json.clone(), vk_json.clone(), dao),
+            Err(b"Callback target not allowed".to_vec())
+        );
+        
+        // Only the admin registers a target, together with the one selector it accepts
+        let selector = FixedBytes::<4>::from_slice(
+            &stylus_sdk::crypto::keccak(b"onProofVerified(bytes32)")[..4],
+        );
+        vm.set_sender(Address::repeat_byte(0x02));
+        assert!(storage.set_callback_target(dao, selector).is_err());
+        vm.set_sender(admin);
+        storage.set_callback_target(dao, selector).unwrap();
+        assert!(storage.is_callback_target(dao));
+        assert_eq!(storage.callback_selector(dao), selector);
+        
+        // A single public input hashes to itself under the mock Poseidon
+        let inputs_hash = storage.hex_to_bytes32(input).unwrap();
+        vm.mock_call(dao, callback_calldata(selector, inputs_hash), U256::ZERO, Ok(Vec::new()));
+        assert_eq!(storage.verify_and_callback(proof_json.clone(), vk_json.clone(), dao), Ok(true));
+        let proof_hash = storage.compute_proof_hash(proof_json.as_ref());
+        assert_eq!(storage.get_public_inputs_hash(proof_hash), inputs_hash);
+        
+        // A reverting callback reverts the call
+        let other = Address::repeat_byte(0xdb);
+        storage.set_callback_target(other, selector).unwrap();
+        vm.mock_call(other, callback_calldata(selector, inputs_hash), U256::ZERO, Err(b"nope".to_vec()));
+        assert_eq!(
+            storage.verify_and_callback(proof_json.clone(), vk_json.clone(), other),
+            Err(b"Verification callback failed".to_vec())
+        );
+        
+        // Clearing the selector disallows the target again
+        storage.set_callback_target(dao, FixedBytes::ZERO).unwrap();
+        assert!(!storage.is_callback_target(dao));
+        assert_eq!(
+            storage.verify_and_callback(proof_json, vk_json, dao),
+            Err(b"Callback target not allowed".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_detailed_verification_distinguishes_invalid_from_malformed() {
+        let mut storage = ZkVerificationStorage::default();
+        let vk_json = br#"{
+            "circuit_name": "age_proof",
+            "verification_key": {
+                "alpha_g1": "0x01", "beta_g2": "0x02", "gamma_g2": "0x03", "delta_g2": "0x04",
+                "ic": []
+            },
+            "key_type": "groth16",
+            "curve": "bn254",
+            "generated_at": "2024-01-01T00:00:00Z"
+        }"#;
+        // Well-formed, but the proof is shorter than the verifier accepts
+        let short_proof = br#"{
+            "circuit_name": "age_proof",
+            "proof": [1, 2, 3, 4],
+            "public_inputs": ["0x0000000000000000000000000000000000000000000000000000000000000012"],
+            "generated_at": "2024-01-01T00:00:00Z"
+        }"#;
+        
+        let bytes = |json: &[u8]| Bytes::from(json.to_vec());
+        
+        let result = storage.verify_zk_proof_detailed(bytes(short_proof), bytes(vk_json)).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.circuit_name, "age_proof");
+        
+        // Malformed input surfaces the parser error instead of `false`
+        let malformed = storage.verify_zk_proof_detailed(bytes(b"not json"), bytes(vk_json));
+        let reason = String::from_utf8(malformed.err().unwrap()).unwrap();
+        assert!(reason.starts_with("Parse error"));
+        
+        // The bool version collapses both to false
+        assert_eq!(storage.verify_zk_proof(bytes(short_proof), bytes(vk_json)), Ok(false));
+        assert_eq!(storage.verify_zk_proof(bytes(b"not json"), bytes(vk_json)), Ok(false));
+    }
+
+    #[test]
+    fn test_registered_circuit_enumeration() {
+        let mut storage = ZkVerificationStorage::default();
+        assert_eq!(storage.registered_circuit_count(), U256::ZERO);
+        
+        storage.set_circuit_vk_hash("age_proof".to_string(), [0x01; 32]);
+        storage.set_circuit_vk_hash("citizenship_proof".to_string(), [0x02; 32]);
+        storage.set_circuit_vk_hash("attribute_proof".to_string(), [0x03; 32]);
+        
+        // Re-registering a key updates it without listing the circuit twice
+        storage.set_circuit_vk_hash("age_proof".to_string(), [0x04; 32]);
+        assert_eq!(storage.registered_circuit_count(), U256::from(3));
+        assert_eq!(
+            storage.get_registered_circuits(),
+            vec!["age_proof", "citizenship_proof", "attribute_proof"]
+        );
+        assert_eq!(storage.get_circuit_vk_hash("age_proof".to_string()), [0x04; 32]);
+        
+        // Unregistering swaps the last circuit into the freed slot
+        assert!(storage.clear_circuit_vk_hash("age_proof".to_string()));
+        assert!(!storage.clear_circuit_vk_hash("age_proof".to_string()));
+        assert_eq!(storage.registered_circuit_count(), U256::from(2));
+        assert_eq!(storage.get_registered_circuits(), vec!["attribute_proof", "citizenship_proof"]);
+        assert_eq!(storage.get_circuit_vk_hash("age_proof".to_string()), [0u8; 32]);
+    }
+
+    #[test]
+    fn test_public_inputs_hash_recorded_on_verification() {
+        let mut storage = ZkVerificationStorage::default();
+        let proof_hash = storage.compute_proof_hash(b"age proof");
+        let inputs_hash = storage
+            .hex_to_bytes32("0x1111111111111111111111111111111111111111111111111111111111111111")
+            .unwrap();
+        
+        assert_eq!(storage.get_public_inputs_hash(proof_hash), [0u8; 32]);
+        
+        storage.record_verification(proof_hash, inputs_hash);
+        
+        assert!(storage.verified_proofs.get(proof_hash));
+        assert_eq!(storage.get_public_inputs_hash(proof_hash), inputs_hash);
+        assert_eq!(storage.get_total_verifications(), U256::from(1));
+    }
+
+    #[test]
+    fn test_circuit_proof_size_bounds() {
+        use stylus_sdk::testing::TestVM;
+        
+        let admin = Address::repeat_byte(0x01);
+        let vm = TestVM::default();
+        vm.set_sender(admin);
+        let mut storage = ZkVerificationStorage::from(&vm);
+        storage.initialize(admin).unwrap();
+        
+        let bytes = |json: &str| Bytes::from(json.as_bytes().to_vec());
+        let vk_json = |circuit: &str| bytes(&format!(
+            r#"{{"circuit_name": "{}",
+                "verification_key": {{"alpha_g1": "0x01", "beta_g2": "0x02", "gamma_g2": "0x03",
+                                      "delta_g2": "0x04", "ic": []}},
+                "key_type": "groth16", "curve": "bn254", "generated_at": "2024-01-01T00:00:00Z"}}"#,
+            circuit
+        ));
+        let proof_json = |circuit: &str, len: usize| bytes(&format!(
+            r#"{{"circuit_name": "{}", "proof": {:?},
+                "public_inputs": ["0x0000000000000000000000000000000000000000000000000000000000000012"],
+                "generated_at": "2024-01-01T00:00:00Z"}}"#,
+            circuit,
+            vec![7u8; len]
+        ));
+        
+        // Only the admin registers bounds, and only a non-empty range
+        vm.set_sender(Address::repeat_byte(0x02));
+        assert!(storage.register_proof_size_bounds("age_proof".into(), U256::from(64), U256::from(80)).is_err());
+        vm.set_sender(admin);
+        assert_eq!(
+            storage.register_proof_size_bounds("age_proof".into(), U256::from(81), U256::from(80)),
+            Err(b"Invalid proof size bounds".to_vec())
+        );
+        storage.register_proof_size_bounds("age_proof".into(), U256::from(64), U256::from(80)).unwrap();
+        assert_eq!(storage.get_proof_size_bounds("age_proof".into()), (U256::from(64), U256::from(80)));
+        
+        // In range, the proof goes on to the verifier
+        assert_eq!(storage.verify_zk_proof(proof_json("age_proof", 80), vk_json("age_proof")), Ok(true));
+        
+        // Out of range, it is rejected without being recorded, naming the bounds it missed
+        let oversized = proof_json("age_proof", 96);
+        let result = storage
+            .verify_zk_proof_detailed(oversized.clone(), vk_json("age_proof"))
+            .unwrap();
+        assert!(!result.valid);
+        assert_eq!(
+            result.error,
+            Some("Proof size 96 outside bounds [64, 80] for circuit age_proof".to_string())
+        );
+        assert_eq!(storage.verify_zk_proof(oversized.clone(), vk_json("age_proof")), Ok(false));
+        assert!(!storage.is_proof_verified(oversized));
+        
+        // Circuits without registered bounds are left to the verifier's own checks
+        assert_eq!(storage.get_proof_size_bounds("membership".into()), (U256::ZERO, U256::ZERO));
+        assert_eq!(storage.verify_zk_proof(proof_json("membership", 96), vk_json("membership")), Ok(true));
+    }
+
+    #[test]
+    fn test_no_emergency_stop_configured() {
+        // Without a coordinator the stop check never blocks verification
+        let storage = ZkVerificationStorage::default();
+        assert_eq!(storage.emergency_stop.get(), Address::ZERO);
+        assert!(storage.when_not_stopped().is_ok());
+    }
+
+    #[test]
+    fn test_gas_estimate_scales_with_public_inputs() {
+        let proof_with_inputs = |count: usize| {
+            let inputs: Vec<String> = (0..count).map(|i| format!("\"0x{:064x}\"", i)).collect();
+            Bytes::from(format!(
+                "{{\"circuit_name\":\"age_proof\",\"proof\":[1,2,3],\"public_inputs\":[{}]}}",
+                inputs.join(",")
+            ).into_bytes())
+        };
+        let vk = Bytes::from(b"{}".to_vec());
+        
+        let one_input = proof_with_inputs(1);
+        let ten_inputs = proof_with_inputs(10);
+        assert_eq!(count_public_inputs(one_input.as_ref()), 1);
+        assert_eq!(count_public_inputs(ten_inputs.as_ref()), 10);
+        
+        let small = ZkVerificationStorage::estimate_verification_gas(one_input, vk.clone());
+        let large = ZkVerificationStorage::estimate_verification_gas(ten_inputs, vk);
+        assert!(large > small);
+        assert!(large - small >= U256::from(9 * GAS_PER_PUBLIC_INPUT));
+    }
 }
\ No newline at end of file
@@ -39,9 +39,10 @@
 //! **On-Chain Verification**: Expensive gas costs (50,000-200,000 gas per proof)
 //! **Off-Chain + Attestation**: Recommended for production (see alternative approach below)
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use hex;
+use stylus_sdk::{alloy_primitives::U256, crypto::keccak};
 
 /// Re-exports for external crate dependencies
 /// Note: These would need to be added to Cargo.toml
@@ -49,6 +50,43 @@ use hex;
 // use barretenberg::{Verifier, FieldElement};
 // use ark_bn254::{Fr as BN254Fr, G1Affine, G2Affine};
 
+/// Maximum number of public input field elements accepted by `verify_noir_proof_raw`
+pub const MAX_PUBLIC_INPUTS: usize = 32;
+
+/// Accepted proof length in bytes, inclusive on both ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSizeBounds {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Proof sizes accepted for circuits without their own bounds (typical Groth16 proof is 192 bytes)
+pub const DEFAULT_PROOF_SIZE_BOUNDS: ProofSizeBounds = ProofSizeBounds { min: 64, max: 512 };
+
+impl ProofSizeBounds {
+    /// Bounds for a circuit, or None unless `0 < min <= max`
+    pub fn new(min: usize, max: usize) -> Option<Self> {
+        if min == 0 || min > max {
+            return None;
+        }
+        Some(Self { min, max })
+    }
+
+    /// Reject a proof of `size` bytes outside the bounds
+    pub fn check(&self, size: usize) -> Result<(), VerificationError> {
+        if size < self.min || size > self.max {
+            return Err(VerificationError::ProofSizeOutOfBounds { size, min: self.min, max: self.max });
+        }
+        Ok(())
+    }
+}
+
+impl Default for ProofSizeBounds {
+    fn default() -> Self {
+        DEFAULT_PROOF_SIZE_BOUNDS
+    }
+}
+
 /// Error types for proof verification
 #[derive(Debug, Clone, PartialEq)]
 pub enum VerificationError {
@@ -60,6 +98,7 @@ pub enum VerificationError {
     InvalidHexEncoding,
     UnsupportedCurve,
     UnsupportedProtocol,
+    ProofSizeOutOfBounds { size: usize, min: usize, max: usize },
 }
 
 impl std::fmt::Display for VerificationError {
@@ -73,6 +112,9 @@ impl std::fmt::Display for VerificationError {
             VerificationError::InvalidHexEncoding => write!(f, "Invalid hex encoding"),
             VerificationError::UnsupportedCurve => write!(f, "Unsupported elliptic curve"),
             VerificationError::UnsupportedProtocol => write!(f, "Unsupported proof protocol"),
+            VerificationError::ProofSizeOutOfBounds { size, min, max } => {
+                write!(f, "Proof size {} outside bounds [{}, {}]", size, min, max)
+            }
         }
     }
 }
@@ -89,6 +131,22 @@ pub struct NoirProof {
     pub public_inputs: Vec<String>,
 }
 
+impl NoirProof {
+    /// Canonical identifier of this proof (see `proof_id`)
+    pub fn proof_id(&self) -> Result<[u8; 32], VerificationError> {
+        let clean_hex = self.proof.strip_prefix("0x").unwrap_or(&self.proof);
+        let proof_bytes = hex::decode(clean_hex)
+            .map_err(|_| VerificationError::InvalidHexEncoding)?;
+        
+        let mut public_inputs = Vec::with_capacity(self.public_inputs.len() * 32);
+        for input in &self.public_inputs {
+            public_inputs.extend_from_slice(&FieldElement::from_hex(input)?.bytes);
+        }
+        
+        Ok(proof_id(&proof_bytes, &public_inputs))
+    }
+}
+
 /// Verification key structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationKey {
@@ -102,6 +160,16 @@ pub struct VerificationKey {
     pub curve: Option<String>,
     /// Proof protocol (groth16, plonk, etc.)
     pub protocol: Option<String>,
+    /// Groth16 IC points (one per public input, plus the constant term)
+    #[serde(default)]
+    pub ic: Option<Vec<String>>,
+}
+
+impl VerificationKey {
+    /// Number of public inputs the circuit expects, if the key declares its IC points
+    pub fn expected_public_inputs(&self) -> Option<usize> {
+        self.ic.as_ref().map(|ic| ic.len().saturating_sub(1))
+    }
 }
 
 /// Field element representation compatible with BN254
@@ -160,6 +228,16 @@ impl FieldElement {
         Self::from_bytes(be_bytes)
     }
 
+    /// Create a field element from a 256-bit integer
+    pub fn from_u256(value: U256) -> Self {
+        Self::from_bytes(value.to_be_bytes::<32>())
+    }
+
+    /// Convert to a 256-bit integer
+    pub fn to_u256(&self) -> U256 {
+        U256::from_be_bytes(self.bytes)
+    }
+
     /// Check if field element is valid (less than BN254 prime)
     pub fn is_valid_bn254(&self) -> bool {
         // BN254 prime: 21888242871839275222246405745257275088548364400416034343698204186575808495617
@@ -170,8 +248,8 @@ impl FieldElement {
             0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
         ];
         
-        // Compare bytes in big-endian format
-        self.bytes < BN254_PRIME
+        // Numeric comparison against the prime, independent of construction path
+        self.to_u256() < U256::from_be_bytes(BN254_PRIME)
     }
 }
 
@@ -195,12 +273,42 @@ impl Default for PoseidonParams {
     }
 }
 
+/// How `NoirVerifier::verify` and `NoirVerifier::verify_raw` decide validity
+/// 
+/// The mode belongs to a `NoirVerifier` instance and is chosen by whoever builds it. The
+/// contracts verify through `zk_verifier` and the free `verify_noir_proof_raw` functions, which
+/// do not consult it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationMode {
+    /// Accept any non-empty proof without cryptographic checks (testing only)
+    Mock,
+    /// Check the verification key, proof size and public inputs. The cryptographic check
+    /// itself is still a placeholder that accepts, so this is not real verification yet
+    Native,
+    /// Accept only proofs whose `proof_id` was attested by an off-chain verifier
+    AttestationOnly,
+}
+
+impl Default for VerificationMode {
+    /// Native, so mock verification is only ever switched on explicitly. This does not fail
+    /// closed: Native still accepts any well-formed proof until its placeholder is replaced
+    fn default() -> Self {
+        VerificationMode::Native
+    }
+}
+
 /// Main verification interface
 pub struct NoirVerifier {
     /// Verification key for the circuit
     verification_key: Option<VerificationKey>,
     /// Poseidon parameters for field operations
     poseidon_params: PoseidonParams,
+    /// Runtime choice of verification backend
+    mode: VerificationMode,
+    /// Proof sizes accepted for this verifier's circuit
+    proof_size_bounds: ProofSizeBounds,
+    /// Proof ids accepted in `AttestationOnly` mode
+    attested_proofs: HashSet<[u8; 32]>,
 }
 
 impl NoirVerifier {
@@ -209,6 +317,73 @@ impl NoirVerifier {
         Self {
             verification_key: vk,
             poseidon_params: PoseidonParams::default(),
+            mode: VerificationMode::default(),
+            proof_size_bounds: ProofSizeBounds::default(),
+            attested_proofs: HashSet::new(),
+        }
+    }
+
+    /// Current verification mode
+    pub fn mode(&self) -> VerificationMode {
+        self.mode
+    }
+
+    /// Switch this verifier's mode
+    pub fn set_mode(&mut self, mode: VerificationMode) {
+        self.mode = mode;
+    }
+
+    /// Proof sizes this verifier accepts
+    pub fn proof_size_bounds(&self) -> ProofSizeBounds {
+        self.proof_size_bounds
+    }
+
+    /// Replace the default proof size bounds with the circuit's own
+    pub fn set_proof_size_bounds(&mut self, bounds: ProofSizeBounds) {
+        self.proof_size_bounds = bounds;
+    }
+
+    /// Record an off-chain attestation for a proof id (see `proof_id`)
+    pub fn record_attestation(&mut self, proof_id: [u8; 32]) {
+        self.attested_proofs.insert(proof_id);
+    }
+
+    /// Verify a parsed proof according to the current mode
+    pub fn verify(&self, proof: &NoirProof) -> Result<bool, VerificationError> {
+        match self.mode {
+            VerificationMode::Mock => self.verify_mock(proof),
+            VerificationMode::Native => {
+                let vk = self
+                    .verification_key
+                    .as_ref()
+                    .ok_or(VerificationError::InvalidVerificationKey)?;
+                self.verify_with_vk(proof, vk)
+            }
+            VerificationMode::AttestationOnly => {
+                Ok(self.attested_proofs.contains(&proof.proof_id()?))
+            }
+        }
+    }
+
+    /// Verify raw proof bytes according to the current mode
+    pub fn verify_raw(&self, proof_bytes: &[u8], public_inputs: &[u8]) -> bool {
+        match self.mode {
+            VerificationMode::Mock => !proof_bytes.is_empty(),
+            VerificationMode::Native => match &self.verification_key {
+                Some(vk) => {
+                    vk_input_count_matches(public_inputs, vk)
+                        && verify_noir_proof_raw_with_bounds(
+                            proof_bytes,
+                            public_inputs,
+                            0,
+                            self.proof_size_bounds,
+                        )
+                }
+                None => false,
+            },
+            VerificationMode::AttestationOnly => {
+                self.attested_proofs.contains(&proof_id(proof_bytes, public_inputs))
+            }
         }
     }
 
@@ -242,16 +417,47 @@ impl NoirVerifier {
     /// Verify proof using native Rust verification (if available)
     #[cfg(feature = "native_verification")]
     pub fn verify_native(&self, proof: &NoirProof) -> Result<bool, VerificationError> {
+        let vk = self
+            .verification_key
+            .as_ref()
+            .ok_or(VerificationError::InvalidVerificationKey)?;
+        self.verify_with_vk(proof, vk)
+    }
+
+    /// Verify proof against a verification key supplied at call time
+    pub fn verify_with_vk(&self, proof: &NoirProof, vk: &VerificationKey) -> Result<bool, VerificationError> {
+        // Validate the verification key before touching the proof
+        if vk.key_as_hex.is_none() && vk.key_as_bytes.is_none() {
+            return Err(VerificationError::InvalidVerificationKey);
+        }
+        
+        if let Some(curve) = &vk.curve {
+            if !curve.eq_ignore_ascii_case("bn254") {
+                return Err(VerificationError::UnsupportedCurve);
+            }
+        }
+        
+        if let Some(protocol) = &vk.protocol {
+            match protocol.to_ascii_lowercase().as_str() {
+                "groth16" | "plonk" => {},
+                _ => return Err(VerificationError::UnsupportedProtocol),
+            }
+        }
+        
+        // Reject proofs whose arity doesn't match the circuit
+        if let Some(expected) = vk.expected_public_inputs() {
+            if proof.public_inputs.len() != expected {
+                return Err(VerificationError::InvalidPublicInputs);
+            }
+        }
+        
         // This would use actual noir_rs or barretenberg-rs crates
         // Placeholder implementation:
         
         let proof_bytes = self.extract_proof_bytes(&proof.proof)?;
         let public_inputs = self.parse_public_inputs(&proof.public_inputs)?;
         
-        // Validate proof size (typical Groth16 proof is 192 bytes)
-        if proof_bytes.len() < 64 || proof_bytes.len() > 512 {
-            return Err(VerificationError::InvalidProofFormat);
-        }
+        self.proof_size_bounds.check(proof_bytes.len())?;
         
         // Validate public inputs are valid field elements
         for input in &public_inputs {
@@ -261,7 +467,7 @@ impl NoirVerifier {
         }
         
         // TODO: Implement actual verification using noir_rs
-        // let verifier = noir_rs::Verifier::new(&self.verification_key)?;
+        // let verifier = noir_rs::Verifier::new(vk)?;
         // let proof_obj = noir_rs::Proof::from_bytes(&proof_bytes)?;
         // let inputs_obj = noir_rs::PublicInputs::from_field_elements(&public_inputs)?;
         // Ok(verifier.verify(&proof_obj, &inputs_obj)?)
@@ -285,7 +491,7 @@ impl NoirVerifier {
 /// 
 /// # Arguments
 /// 
-/// * `proof_bytes` - Raw proof bytes (64-192 bytes typical)
+/// * `proof_bytes` - Raw proof bytes, within `DEFAULT_PROOF_SIZE_BOUNDS`
 /// * `public_inputs` - Serialized public inputs (32 bytes per field element)
 /// 
 /// # Returns
@@ -301,8 +507,35 @@ impl NoirVerifier {
 /// let is_valid = verify_noir_proof_raw(&proof_data, &public_data);
 /// ```
 pub fn verify_noir_proof_raw(proof_bytes: &[u8], public_inputs: &[u8]) -> bool {
+    verify_noir_proof_raw_with_min_inputs(proof_bytes, public_inputs, 0)
+}
+
+/// Verify raw proof bytes, rejecting proofs with fewer than `min_public_inputs` field elements
+/// 
+/// Circuits without public inputs pass `0`; flows that parse a fixed layout from the inputs
+/// should pass the number of elements they read so an input-less proof cannot slip through.
+pub fn verify_noir_proof_raw_with_min_inputs(
+    proof_bytes: &[u8],
+    public_inputs: &[u8],
+    min_public_inputs: usize,
+) -> bool {
+    verify_noir_proof_raw_with_bounds(
+        proof_bytes,
+        public_inputs,
+        min_public_inputs,
+        DEFAULT_PROOF_SIZE_BOUNDS,
+    )
+}
+
+/// Verify raw proof bytes whose circuit has its own proof size bounds
+pub fn verify_noir_proof_raw_with_bounds(
+    proof_bytes: &[u8],
+    public_inputs: &[u8],
+    min_public_inputs: usize,
+    bounds: ProofSizeBounds,
+) -> bool {
     // Validate input sizes
-    if proof_bytes.is_empty() || proof_bytes.len() > 512 {
+    if bounds.check(proof_bytes.len()).is_err() {
         return false;
     }
     
@@ -310,8 +543,13 @@ pub fn verify_noir_proof_raw(proof_bytes: &[u8], public_inputs: &[u8]) -> bool {
         return false; // Public inputs must be multiples of 32 bytes
     }
     
-    // Parse public inputs into field elements
+    // Bound the input count before parsing to avoid griefing
     let num_inputs = public_inputs.len() / 32;
+    if num_inputs > MAX_PUBLIC_INPUTS || num_inputs < min_public_inputs {
+        return false;
+    }
+    
+    // Parse public inputs into field elements
     let mut field_inputs = Vec::with_capacity(num_inputs);
     
     for i in 0..num_inputs {
@@ -347,6 +585,30 @@ pub fn verify_noir_proof_raw(proof_bytes: &[u8], public_inputs: &[u8]) -> bool {
     }
 }
 
+/// Canonical proof identifier: keccak256(proof_bytes ++ public_inputs)
+/// 
+/// `public_inputs` are the concatenated 32-byte big-endian field elements. Off-chain
+/// attestations and the on-chain verifiers must both key proofs by this value.
+pub fn proof_id(proof_bytes: &[u8], public_inputs: &[u8]) -> [u8; 32] {
+    let mut preimage = Vec::with_capacity(proof_bytes.len() + public_inputs.len());
+    preimage.extend_from_slice(proof_bytes);
+    preimage.extend_from_slice(public_inputs);
+    keccak(&preimage).into()
+}
+
+/// Verify raw proof bytes, first checking the input count against a verification key
+pub fn verify_noir_proof_raw_for_vk(proof_bytes: &[u8], public_inputs: &[u8], vk: &VerificationKey) -> bool {
+    vk_input_count_matches(public_inputs, vk) && verify_noir_proof_raw(proof_bytes, public_inputs)
+}
+
+/// Whether serialized public inputs hold the number of elements the key declares, if any
+fn vk_input_count_matches(public_inputs: &[u8], vk: &VerificationKey) -> bool {
+    match vk.expected_public_inputs() {
+        Some(expected) => public_inputs.len() == expected * 32,
+        None => true,
+    }
+}
+
 /// Helper function to convert between Noir and Stylus field representations
 /// 
 /// Noir typically uses big-endian field elements, while some Stylus
@@ -429,6 +691,17 @@ pub mod utils {
             .collect()
     }
     
+    /// Verify proofs in order, stopping at the first failure and returning its index
+    pub fn batch_verify_first_failure(proofs: &[(Vec<u8>, Vec<u8>)]) -> Result<(), usize> {
+        match proofs
+            .iter()
+            .position(|(proof_bytes, public_inputs)| !verify_noir_proof_raw(proof_bytes, public_inputs))
+        {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+    
     /// Calculate gas estimate for proof verification
     pub fn estimate_verification_gas(proof_size: usize, num_public_inputs: usize) -> u64 {
         // Base cost for verification logic
@@ -479,7 +752,7 @@ pub mod utils {
 ///    // Off-chain verification
 ///    let is_valid = verify_noir_proof_raw(proof_bytes, public_inputs);
 ///    if is_valid {
-///        let proof_hash = keccak256(&proof_bytes);
+///        let proof_hash = proof_id(&proof_bytes, &public_inputs);
 ///        let signature = sign_verification_result(proof_hash, private_key);
 ///        submit_attestation(proof_hash, signature);
 ///    }
@@ -509,6 +782,24 @@ mod tests {
         assert!(max_field.is_valid_bn254());
     }
 
+    #[test]
+    fn test_field_element_prime_boundary() {
+        let prime = FieldElement::from_hex("0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47").unwrap();
+        assert!(!prime.is_valid_bn254());
+        
+        let prime_minus_one = FieldElement::from_u256(prime.to_u256() - U256::from(1));
+        assert!(prime_minus_one.is_valid_bn254());
+        assert_eq!(FieldElement::from_u256(prime_minus_one.to_u256()), prime_minus_one);
+        
+        // Little-endian construction near the boundary round-trips to the same value
+        let le_below = FieldElement::from_le_bytes(prime_minus_one.to_le_bytes());
+        assert_eq!(le_below.to_u256(), prime_minus_one.to_u256());
+        assert!(le_below.is_valid_bn254());
+        
+        let le_prime = FieldElement::from_le_bytes(prime.to_le_bytes());
+        assert!(!le_prime.is_valid_bn254());
+    }
+
     #[test]
     fn test_proof_parsing() {
         let proof_json = r#"
@@ -525,6 +816,82 @@ mod tests {
         assert_eq!(proof.public_inputs.len(), 3);
     }
 
+    #[test]
+    fn test_verify_with_matching_vk() {
+        let verifier = NoirVerifier::new(None);
+        let proof = NoirProof {
+            proof: format!("0x{}", "ab".repeat(192)),
+            public_inputs: vec!["0x1".to_string(), "0x2".to_string()],
+        };
+        let vk = VerificationKey {
+            key_as_hex: Some("0x1234".to_string()),
+            key_as_bytes: None,
+            curve: Some("bn254".to_string()),
+            protocol: Some("groth16".to_string()),
+            ic: None,
+        };
+        
+        assert_eq!(verifier.verify_with_vk(&proof, &vk), Ok(true));
+    }
+
+    #[test]
+    fn test_verify_with_mismatching_vk() {
+        let verifier = NoirVerifier::new(None);
+        let proof = NoirProof {
+            proof: format!("0x{}", "ab".repeat(192)),
+            public_inputs: vec!["0x1".to_string()],
+        };
+        let base_vk = VerificationKey {
+            key_as_hex: Some("0x1234".to_string()),
+            key_as_bytes: None,
+            curve: Some("bn254".to_string()),
+            protocol: Some("groth16".to_string()),
+            ic: None,
+        };
+        
+        let wrong_curve = VerificationKey { curve: Some("bls12-381".to_string()), ..base_vk.clone() };
+        assert_eq!(verifier.verify_with_vk(&proof, &wrong_curve), Err(VerificationError::UnsupportedCurve));
+        
+        let wrong_protocol = VerificationKey { protocol: Some("stark".to_string()), ..base_vk.clone() };
+        assert_eq!(verifier.verify_with_vk(&proof, &wrong_protocol), Err(VerificationError::UnsupportedProtocol));
+        
+        let empty_key = VerificationKey { key_as_hex: None, ..base_vk };
+        assert_eq!(verifier.verify_with_vk(&proof, &empty_key), Err(VerificationError::InvalidVerificationKey));
+    }
+
+    #[test]
+    fn test_public_input_count_must_match_vk() {
+        let verifier = NoirVerifier::new(None);
+        let vk = VerificationKey {
+            key_as_hex: Some("0x1234".to_string()),
+            key_as_bytes: None,
+            curve: Some("bn254".to_string()),
+            protocol: Some("groth16".to_string()),
+            ic: Some(vec!["0xa".to_string(), "0xb".to_string(), "0xc".to_string()]),
+        };
+        assert_eq!(vk.expected_public_inputs(), Some(2));
+        
+        let proof_with = |inputs: &[&str]| NoirProof {
+            proof: format!("0x{}", "ab".repeat(192)),
+            public_inputs: inputs.iter().map(|s| s.to_string()).collect(),
+        };
+        
+        assert_eq!(verifier.verify_with_vk(&proof_with(&["0x1", "0x2"]), &vk), Ok(true));
+        assert_eq!(
+            verifier.verify_with_vk(&proof_with(&["0x1"]), &vk),
+            Err(VerificationError::InvalidPublicInputs)
+        );
+        assert_eq!(
+            verifier.verify_with_vk(&proof_with(&["0x1", "0x2", "0x3"]), &vk),
+            Err(VerificationError::InvalidPublicInputs)
+        );
+        
+        let proof_bytes = vec![0u8; 192];
+        assert!(verify_noir_proof_raw_for_vk(&proof_bytes, &vec![0u8; 64], &vk));
+        assert!(!verify_noir_proof_raw_for_vk(&proof_bytes, &vec![0u8; 32], &vk));
+        assert!(!verify_noir_proof_raw_for_vk(&proof_bytes, &vec![0u8; 96], &vk));
+    }
+
     #[test]
     fn test_raw_verification_interface() {
         let proof_bytes = vec![0u8; 192]; // Typical Groth16 proof size
@@ -535,6 +902,79 @@ mod tests {
         assert!(result == true || result == false);
     }
 
+    #[test]
+    fn test_public_input_cap() {
+        let proof_bytes = vec![0u8; 192];
+        
+        let at_cap = vec![0u8; 32 * MAX_PUBLIC_INPUTS];
+        assert!(verify_noir_proof_raw(&proof_bytes, &at_cap));
+        
+        let over_cap = vec![0u8; 32 * (MAX_PUBLIC_INPUTS + 1)];
+        assert!(!verify_noir_proof_raw(&proof_bytes, &over_cap));
+    }
+
+    #[test]
+    fn test_per_circuit_proof_size_bounds() {
+        assert_eq!(ProofSizeBounds::new(0, 128), None);
+        assert_eq!(ProofSizeBounds::new(160, 128), None);
+        let tight = ProofSizeBounds::new(128, 160).unwrap();
+        
+        let vk = VerificationKey {
+            key_as_hex: Some("0x1234".to_string()),
+            key_as_bytes: None,
+            curve: Some("bn254".to_string()),
+            protocol: Some("groth16".to_string()),
+            ic: None,
+        };
+        let proof_of = |len: usize| NoirProof {
+            proof: format!("0x{}", "ab".repeat(len)),
+            public_inputs: vec!["0x1".to_string()],
+        };
+        let mut verifier = NoirVerifier::new(Some(vk.clone()));
+        verifier.set_mode(VerificationMode::Native);
+        
+        // The defaults take a 192 byte proof...
+        assert_eq!(verifier.proof_size_bounds(), DEFAULT_PROOF_SIZE_BOUNDS);
+        assert_eq!(verifier.verify(&proof_of(192)), Ok(true));
+        assert!(verifier.verify_raw(&[0xab; 192], &[0u8; 32]));
+        
+        // ...which a circuit registered at 128-160 bytes rejects with the bounds it missed
+        verifier.set_proof_size_bounds(tight);
+        assert_eq!(
+            verifier.verify_with_vk(&proof_of(192), &vk),
+            Err(VerificationError::ProofSizeOutOfBounds { size: 192, min: 128, max: 160 })
+        );
+        assert_eq!(
+            VerificationError::ProofSizeOutOfBounds { size: 192, min: 128, max: 160 }.to_string(),
+            "Proof size 192 outside bounds [128, 160]"
+        );
+        assert!(!verifier.verify_raw(&[0xab; 192], &[0u8; 32]));
+        assert!(!verifier.verify_raw(&[0xab; 127], &[0u8; 32]));
+        assert_eq!(verifier.verify(&proof_of(160)), Ok(true));
+        assert!(verifier.verify_raw(&[0xab; 128], &[0u8; 32]));
+        
+        // The raw entry points apply the same bounds
+        assert!(!verify_noir_proof_raw(&[0xab; 63], &[0u8; 32]));
+        assert!(!verify_noir_proof_raw(&[0xab; 513], &[0u8; 32]));
+        assert!(verify_noir_proof_raw(&[0xab; 512], &[0u8; 32]));
+        assert!(!verify_noir_proof_raw_with_bounds(&[0xab; 192], &[0u8; 32], 0, tight));
+        assert!(verify_noir_proof_raw_with_bounds(&[0xab; 144], &[0u8; 32], 0, tight));
+    }
+
+    #[test]
+    fn test_minimum_public_inputs() {
+        let proof_bytes = vec![0u8; 192];
+        
+        // Zero inputs are accepted by default
+        assert!(verify_noir_proof_raw(&proof_bytes, &[]));
+        assert!(verify_noir_proof_raw_with_min_inputs(&proof_bytes, &[], 0));
+        
+        // ...and rejected once a minimum is required
+        assert!(!verify_noir_proof_raw_with_min_inputs(&proof_bytes, &[], 5));
+        assert!(!verify_noir_proof_raw_with_min_inputs(&proof_bytes, &vec![0u8; 32 * 4], 5));
+        assert!(verify_noir_proof_raw_with_min_inputs(&proof_bytes, &vec![0u8; 32 * 5], 5));
+    }
+
     #[test]
     fn test_field_conversion() {
         let input = vec![1u8; 32]; // One field element
@@ -560,6 +1000,95 @@ mod tests {
         assert_eq!(results.len(), 2);
     }
 
+    #[test]
+    fn test_batch_first_failure() {
+        let valid = (vec![0u8; 192], vec![0u8; 32]);
+        
+        let all_valid = vec![valid.clone(), valid.clone(), valid.clone()];
+        assert_eq!(utils::batch_verify_first_failure(&all_valid), Ok(()));
+        
+        let failing_at_two = vec![
+            valid.clone(),
+            valid.clone(),
+            (Vec::new(), vec![0u8; 32]), // Empty proof is rejected
+            (Vec::new(), vec![0u8; 32]),
+        ];
+        assert_eq!(utils::batch_verify_first_failure(&failing_at_two), Err(2));
+    }
+
+    #[test]
+    fn test_proof_id() {
+        let proof = NoirProof {
+            proof: "0xdeadbeef".to_string(),
+            public_inputs: vec!["0x01".to_string(), "0x02".to_string()],
+        };
+        
+        let mut public_inputs = vec![0u8; 64];
+        public_inputs[31] = 1;
+        public_inputs[63] = 2;
+        let expected = proof_id(&[0xde, 0xad, 0xbe, 0xef], &public_inputs);
+        assert_eq!(proof.proof_id().unwrap(), expected);
+        
+        // Inputs are part of the identity
+        assert_ne!(expected, proof_id(&[0xde, 0xad, 0xbe, 0xef], &[]));
+        
+        let bad_hex = NoirProof { proof: "0xzz".to_string(), public_inputs: Vec::new() };
+        assert_eq!(bad_hex.proof_id(), Err(VerificationError::InvalidHexEncoding));
+    }
+
+    #[test]
+    fn test_verification_modes() {
+        let vk = VerificationKey {
+            key_as_hex: Some("0x1234".to_string()),
+            key_as_bytes: None,
+            curve: Some("bn254".to_string()),
+            protocol: Some("groth16".to_string()),
+            ic: Some(vec!["0xa".to_string(), "0xb".to_string()]),
+        };
+        let proof = NoirProof {
+            proof: format!("0x{}", "ab".repeat(192)),
+            public_inputs: vec!["0x1".to_string()],
+        };
+        let short_proof = NoirProof { proof: "0xabcd".to_string(), ..proof.clone() };
+        let (proof_bytes, public_inputs) = (vec![0xab; 192], vec![0u8; 32]);
+        
+        // Native unless told otherwise, with or without the native_verification feature
+        let mut verifier = NoirVerifier::new(Some(vk));
+        assert_eq!(verifier.mode(), VerificationMode::Native);
+        assert_eq!(VerificationMode::default(), VerificationMode::Native);
+        
+        // Mock accepts anything non-empty
+        verifier.set_mode(VerificationMode::Mock);
+        assert_eq!(verifier.verify(&short_proof), Ok(true));
+        assert!(verifier.verify_raw(&[1], &[0xff; 3]));
+        assert!(!verifier.verify_raw(&[], &public_inputs));
+        
+        // Native enforces the key and proof structure
+        verifier.set_mode(VerificationMode::Native);
+        assert_eq!(verifier.verify(&proof), Ok(true));
+        assert_eq!(
+            verifier.verify(&short_proof),
+            Err(VerificationError::ProofSizeOutOfBounds { size: 2, min: 64, max: 512 })
+        );
+        assert!(verifier.verify_raw(&proof_bytes, &public_inputs));
+        assert!(!verifier.verify_raw(&proof_bytes, &[]));
+        
+        let mut keyless = NoirVerifier::new(None);
+        keyless.set_mode(VerificationMode::Native);
+        assert_eq!(keyless.verify(&proof), Err(VerificationError::InvalidVerificationKey));
+        assert!(!keyless.verify_raw(&proof_bytes, &public_inputs));
+        
+        // AttestationOnly accepts exactly the attested proof ids
+        verifier.set_mode(VerificationMode::AttestationOnly);
+        assert_eq!(verifier.verify(&proof), Ok(false));
+        assert!(!verifier.verify_raw(&proof_bytes, &public_inputs));
+        verifier.record_attestation(proof.proof_id().unwrap());
+        verifier.record_attestation(proof_id(&proof_bytes, &public_inputs));
+        assert_eq!(verifier.verify(&proof), Ok(true));
+        assert!(verifier.verify_raw(&proof_bytes, &public_inputs));
+        assert_eq!(verifier.verify(&short_proof), Ok(false));
+    }
+
     #[test]
     fn test_gas_estimation() {
         let gas_estimate = utils::estimate_verification_gas(192, 2);